@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::types::delete::DeleteOptions;
 use crate::{
     commands::common::watch, manager::k8s::cluster_resources::K8sClusterResources,
     utils::watcher::WatchManager,
@@ -46,6 +47,12 @@ pub async fn watch_cluster_roles(
 pub async fn delete_cluster_roles(
     name: String,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sClusterResources::<ClusterRole>::delete(name, resource_names).await?)
+    Ok(K8sClusterResources::<ClusterRole>::delete(
+        name,
+        resource_names,
+        options.unwrap_or_default(),
+    )
+    .await?)
 }
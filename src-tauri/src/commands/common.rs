@@ -82,9 +82,3 @@ pub fn restart_patch() -> Value {
         }
     })
 }
-
-pub fn scale_patch(replicas: i32) -> Value {
-    serde_json::json!({
-        "spec": { "replicas": replicas }
-    })
-}
@@ -1,8 +1,12 @@
+use crate::manager::k8s::metrics_history::K8sMetricsHistory;
+use crate::manager::k8s::port_forward::spawn_auto_start;
 use crate::utils::connections::ConnectionsManager;
 use crate::utils::watcher::WatchManager;
+use tauri::AppHandle;
 
 #[tauri::command]
 pub async fn set_context_connection(
+    app_handle: AppHandle,
     name: String,
     connected: bool,
     cm: tauri::State<'_, ConnectionsManager>,
@@ -12,9 +16,13 @@ pub async fn set_context_connection(
     // without direct AppHandle access) see consistent connection status.
     cm.set(name.clone(), connected).await;
     ConnectionsManager::global().set(name.clone(), connected).await;
-    if !connected {
+    if connected {
+        spawn_auto_start(app_handle.clone(), name.clone());
+        K8sMetricsHistory::start_recording(app_handle, name).await;
+    } else {
         // Abort any watchers tied to this context name
         let _ = wm.unwatch(&name).await;
+        K8sMetricsHistory::stop_recording(&app_handle, &name).await;
     }
     Ok(())
 }
@@ -34,4 +42,4 @@ pub async fn get_context_connection(
 ) -> Result<bool, String> {
     let _ = cm; // prefer reading from the global store
     Ok(ConnectionsManager::global().is_connected(&name).await)
-}
\ No newline at end of file
+}
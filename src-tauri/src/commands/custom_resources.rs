@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use crate::commands::common::watch;
 use crate::manager::k8s::dynamic_resources::DynamicK8sResources;
+use crate::types::delete::DeleteOptions;
 use crate::utils::watcher::WatchManager;
 use serde_json::Value;
 use tauri::AppHandle;
@@ -118,6 +119,7 @@ pub async fn delete_custom_resources(
     plural: String,
     is_namespaced: bool,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
     DynamicK8sResources::delete(
         name,
@@ -128,6 +130,7 @@ pub async fn delete_custom_resources(
         plural,
         is_namespaced,
         resource_names,
+        options.unwrap_or_default(),
     )
     .await
 }
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
+use crate::manager::k8s::scaling::K8sScaling;
+use crate::types::delete::DeleteOptions;
 use crate::{
-    commands::common::{restart_patch, scale_patch, watch},
+    commands::common::{restart_patch, watch},
     manager::k8s::resources::K8sResources,
     utils::watcher::WatchManager,
 };
@@ -58,8 +60,15 @@ pub async fn delete_daemon_sets(
     name: String,
     namespace: Option<String>,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sResources::<DaemonSet>::delete(name, namespace, resource_names).await?)
+    Ok(K8sResources::<DaemonSet>::delete(
+        name,
+        namespace,
+        resource_names,
+        options.unwrap_or_default(),
+    )
+    .await?)
 }
 
 #[tauri::command]
@@ -78,6 +87,7 @@ pub async fn restart_daemon_set(
     .await
 }
 
+// Kept so callers get a clear error: DaemonSets have no replica count.
 #[tauri::command]
 pub async fn scale_daemon_set(
     name: String,
@@ -85,12 +95,14 @@ pub async fn scale_daemon_set(
     resource_name: String,
     replicas: i32,
 ) -> Result<Value, String> {
-    K8sResources::<DaemonSet>::patch(
+    K8sScaling::scale_object(
         name,
+        "apps/v1",
+        "DaemonSet",
         namespace,
         resource_name,
-        scale_patch(replicas),
-        "merge".into(),
+        replicas,
+        false,
     )
     .await
 }
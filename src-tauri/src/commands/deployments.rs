@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use crate::manager::k8s::scaling::K8sScaling;
+use crate::types::delete::DeleteOptions;
 use crate::{
     commands::common::watch, manager::k8s::resources::K8sResources, utils::watcher::WatchManager,
 };
@@ -57,8 +59,15 @@ pub async fn delete_deployments(
     name: String,
     namespace: Option<String>,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sResources::<Deployment>::delete(name, namespace, resource_names).await?)
+    Ok(K8sResources::<Deployment>::delete(
+        name,
+        namespace,
+        resource_names,
+        options.unwrap_or_default(),
+    )
+    .await?)
 }
 
 #[tauri::command]
@@ -82,16 +91,23 @@ pub async fn restart_deployment(
     K8sResources::<Deployment>::patch(name, namespace, resource_name, patch, "merge".into()).await
 }
 
+// Fails with a warning when an HPA manages the Deployment unless `force` is set.
 #[tauri::command]
 pub async fn scale_deployment(
     name: String,
     namespace: Option<String>,
     resource_name: String,
     replicas: i32,
+    force: Option<bool>,
 ) -> Result<Value, String> {
-    let patch: Value = serde_json::json!({
-        "spec": { "replicas": replicas }
-    });
-
-    K8sResources::<Deployment>::patch(name, namespace, resource_name, patch, "merge".into()).await
+    K8sScaling::scale_object(
+        name,
+        "apps/v1",
+        "Deployment",
+        namespace,
+        resource_name,
+        replicas,
+        force.unwrap_or(false),
+    )
+    .await
 }
@@ -1,5 +1,10 @@
 use crate::commands::common::watch as watch_common;
-use crate::manager::k8s::helm::HelmManager;
+use crate::manager::k8s::helm::{HelmManager, UpgradeArgs};
+use crate::types::helm::{
+    HelmChartInfo, HelmChartResolution, HelmInstallOptions, HelmReleaseDetails, HelmReleaseDiff,
+    HelmReleaseResource, HelmRepoAddOptions, HelmTemplateOptions, HelmTemplateResult,
+};
+use crate::utils::helm_jobs::{HelmJobItem, HelmJobManager};
 use crate::utils::watcher::WatchManager;
 use serde_json::Value;
 use std::sync::Arc;
@@ -15,11 +20,12 @@ pub async fn helm_list_releases(
 
 #[tauri::command]
 pub async fn helm_uninstall_releases(
+    app_handle: AppHandle,
     name: String,
     namespace: Option<String>,
     release_names: Vec<String>,
-) -> Result<Vec<Result<String, String>>, String> {
-    HelmManager::uninstall_releases(name, namespace, release_names).await
+) -> Result<Vec<HelmJobItem>, String> {
+    HelmManager::uninstall_releases(app_handle, name, namespace, release_names).await
 }
 
 #[tauri::command]
@@ -51,8 +57,9 @@ pub async fn helm_get_values(
     name: String,
     namespace: Option<String>,
     release_name: String,
+    all: Option<bool>,
 ) -> Result<String, String> {
-    HelmManager::get_values(name, namespace, release_name).await
+    HelmManager::get_values(name, namespace, release_name, all.unwrap_or(true)).await
 }
 
 #[tauri::command]
@@ -64,8 +71,49 @@ pub async fn helm_get_history(
     HelmManager::get_history(name, namespace, release_name).await
 }
 
+#[tauri::command]
+pub async fn helm_get_manifest(
+    name: String,
+    namespace: Option<String>,
+    release_name: String,
+    revision: Option<i32>,
+) -> Result<String, String> {
+    HelmManager::get_manifest(name, namespace, release_name, revision).await
+}
+
+#[tauri::command]
+pub async fn helm_get_release(
+    name: String,
+    namespace: Option<String>,
+    release_name: String,
+    revision: Option<i32>,
+) -> Result<HelmReleaseDetails, String> {
+    HelmManager::get_release(name, namespace, release_name, revision).await
+}
+
+#[tauri::command]
+pub async fn helm_install_release(
+    app_handle: AppHandle,
+    name: String,
+    namespace: String,
+    release_name: String,
+    chart: String,
+    options: Option<HelmInstallOptions>,
+) -> Result<HelmJobItem, String> {
+    HelmManager::install_release(
+        app_handle,
+        name,
+        namespace,
+        release_name,
+        chart,
+        options.unwrap_or_default(),
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn helm_upgrade_release(
+    app_handle: AppHandle,
     name: String,
     namespace: Option<String>,
     release_name: String,
@@ -73,25 +121,143 @@ pub async fn helm_upgrade_release(
     values: Option<Value>,
     reuse_values: bool,
     version: Option<String>,
-) -> Result<String, String> {
-    HelmManager::upgrade_release(
-        name,
+) -> Result<HelmJobItem, String> {
+    let args = UpgradeArgs {
+        context_name: name,
         namespace,
         release_name,
         chart,
         values,
         reuse_values,
         version,
-    )
-    .await
+    };
+    HelmManager::upgrade_release(app_handle, args).await
+}
+
+#[tauri::command]
+pub async fn helm_diff_revisions(
+    name: String,
+    namespace: Option<String>,
+    release_name: String,
+    from_revision: i32,
+    to_revision: Option<i32>,
+) -> Result<HelmReleaseDiff, String> {
+    HelmManager::diff_revisions(name, namespace, release_name, from_revision, to_revision).await
+}
+
+#[tauri::command]
+pub async fn helm_diff_upgrade(
+    name: String,
+    namespace: Option<String>,
+    release_name: String,
+    chart: Option<String>,
+    values: Option<Value>,
+    reuse_values: bool,
+    version: Option<String>,
+) -> Result<HelmReleaseDiff, String> {
+    HelmManager::diff_upgrade(name, namespace, release_name, chart, values, reuse_values, version)
+        .await
 }
 
 #[tauri::command]
 pub async fn helm_rollback_release(
+    app_handle: AppHandle,
     name: String,
     namespace: Option<String>,
     release_name: String,
     revision: i32,
+) -> Result<HelmJobItem, String> {
+    HelmManager::rollback_release(app_handle, name, namespace, release_name, revision).await
+}
+
+#[tauri::command]
+pub async fn helm_list_repos() -> Result<Vec<Value>, String> {
+    HelmManager::list_repositories().await
+}
+
+#[tauri::command]
+pub async fn helm_add_repo(
+    repo_name: String,
+    url: String,
+    options: Option<HelmRepoAddOptions>,
+) -> Result<String, String> {
+    HelmManager::add_repository(repo_name, url, options.unwrap_or_default()).await
+}
+
+#[tauri::command]
+pub async fn helm_remove_repos(
+    repo_names: Vec<String>,
+) -> Result<Vec<Result<String, String>>, String> {
+    HelmManager::remove_repositories(repo_names).await
+}
+
+#[tauri::command]
+pub async fn helm_update_repos(repo_names: Option<Vec<String>>) -> Result<String, String> {
+    HelmManager::update_repositories(repo_names).await
+}
+
+#[tauri::command]
+pub async fn helm_registry_login(
+    registry: String,
+    username: String,
+    password: String,
+    insecure: Option<bool>,
 ) -> Result<String, String> {
-    HelmManager::rollback_release(name, namespace, release_name, revision).await
+    HelmManager::registry_login(registry, username, password, insecure.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn helm_registry_logout(registry: String) -> Result<String, String> {
+    HelmManager::registry_logout(registry).await
+}
+
+#[tauri::command]
+pub async fn helm_show_chart(
+    chart: String,
+    version: Option<String>,
+) -> Result<HelmChartInfo, String> {
+    HelmManager::show_chart(chart, version).await
+}
+
+#[tauri::command]
+pub async fn helm_release_resources(
+    name: String,
+    namespace: Option<String>,
+    release_name: String,
+) -> Result<Vec<HelmReleaseResource>, String> {
+    HelmManager::release_resources(name, namespace, release_name).await
+}
+
+#[tauri::command]
+pub async fn helm_resolve_upgrade_chart(
+    name: String,
+    namespace: Option<String>,
+    release_name: String,
+) -> Result<HelmChartResolution, String> {
+    HelmManager::resolve_upgrade_chart(name, namespace, release_name).await
+}
+
+#[tauri::command]
+pub async fn helm_list_jobs(
+    state: tauri::State<'_, HelmJobManager>,
+) -> Result<Vec<HelmJobItem>, String> {
+    Ok(state.list().await)
+}
+
+#[tauri::command]
+pub async fn cancel_helm_job(
+    state: tauri::State<'_, HelmJobManager>,
+    job_id: String,
+) -> Result<(), String> {
+    state.cancel(&job_id).await
+}
+
+#[tauri::command]
+pub async fn helm_template(
+    release_name: String,
+    chart: String,
+    namespace: Option<String>,
+    options: Option<HelmTemplateOptions>,
+) -> Result<HelmTemplateResult, String> {
+    HelmManager::template(release_name, chart, namespace, options.unwrap_or_default()).await
 }
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use crate::manager::k8s::scaling::K8sScaling;
+use crate::types::delete::DeleteOptions;
 use crate::{
     commands::common::watch, manager::k8s::resources::K8sResources, utils::watcher::WatchManager,
 };
@@ -56,6 +58,25 @@ pub async fn delete_horizontal_pod_autoscalers(
     name: String,
     namespace: Option<String>,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sResources::<HorizontalPodAutoscaler>::delete(name, namespace, resource_names).await?)
+    Ok(K8sResources::<HorizontalPodAutoscaler>::delete(
+        name,
+        namespace,
+        resource_names,
+        options.unwrap_or_default(),
+    )
+    .await?)
+}
+
+// Offered instead of scaling a target the autoscaler manages.
+#[tauri::command]
+pub async fn update_horizontal_pod_autoscaler_replicas(
+    name: String,
+    namespace: String,
+    resource_name: String,
+    min_replicas: Option<i32>,
+    max_replicas: Option<i32>,
+) -> Result<Value, String> {
+    K8sScaling::update_autoscaler(name, namespace, resource_name, min_replicas, max_replicas).await
 }
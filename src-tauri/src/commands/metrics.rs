@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::manager::k8s::metrics_history::K8sMetricsHistory;
+use crate::state::AppState;
+use crate::types::metrics::{
+    MetricsHistorySeries, MetricsHistorySettings, MetricsReport, MetricsSeriesKind, NodeUsage,
+    PodUsage,
+};
+use crate::{
+    commands::common::watch, manager::k8s::metrics::K8sMetrics, utils::watcher::WatchManager,
+};
+use k8s_openapi::chrono::Utc;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn get_pod_metrics(
+    name: String,
+    namespaces: Option<Vec<String>>,
+) -> Result<MetricsReport<PodUsage>, String> {
+    K8sMetrics::pod_metrics(name, namespaces).await
+}
+
+#[tauri::command]
+pub async fn get_node_metrics(name: String) -> Result<MetricsReport<NodeUsage>, String> {
+    K8sMetrics::node_metrics(name).await
+}
+
+#[tauri::command]
+pub async fn watch_pod_metrics(
+    app_handle: AppHandle,
+    name: String,
+    namespaces: Option<Vec<String>>,
+    state: tauri::State<'_, WatchManager>,
+) -> Result<String, String> {
+    watch(
+        app_handle,
+        name,
+        "metrics/pods".to_string(),
+        namespaces,
+        state,
+        Arc::new(|app_handle, name, namespaces, event_name| {
+            K8sMetrics::watch_pod_metrics(app_handle, name, namespaces, event_name)
+        }),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn watch_node_metrics(
+    app_handle: AppHandle,
+    name: String,
+    state: tauri::State<'_, WatchManager>,
+) -> Result<String, String> {
+    watch(
+        app_handle,
+        name,
+        "metrics/nodes".to_string(),
+        None,
+        state,
+        Arc::new(|app_handle, name, _namespaces, event_name| {
+            K8sMetrics::watch_node_metrics(app_handle, name, event_name)
+        }),
+    )
+    .await
+}
+
+// `from` and `to` are unix milliseconds; the last 24 hours by default.
+#[tauri::command]
+pub async fn query_metrics_history(
+    name: String,
+    kind: MetricsSeriesKind,
+    keys: Vec<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<MetricsHistorySeries>, String> {
+    let to: i64 = to.unwrap_or_else(|| Utc::now().timestamp_millis());
+    let from: i64 = from.unwrap_or(to - 24 * 3600 * 1000);
+    K8sMetricsHistory::query(&state, &name, kind, keys, from, to)
+}
+
+#[tauri::command]
+pub async fn list_metrics_history_series(
+    name: String,
+    kind: MetricsSeriesKind,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    K8sMetricsHistory::list_series(&state, &name, kind)
+}
+
+#[tauri::command]
+pub async fn get_metrics_history_settings(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<MetricsHistorySettings, String> {
+    K8sMetricsHistory::settings(&state, &name)
+}
+
+#[tauri::command]
+pub async fn set_metrics_history_settings(
+    name: String,
+    settings: MetricsHistorySettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<MetricsHistorySettings, String> {
+    K8sMetricsHistory::save_settings(&state, &name, settings)
+}
+
+#[tauri::command]
+pub async fn clear_metrics_history(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    K8sMetricsHistory::clear(&state, &name)
+}
@@ -19,6 +19,7 @@ pub mod ingresses;
 pub mod jobs;
 pub mod leases;
 pub mod limit_ranges;
+pub mod metrics;
 pub mod mutating_webhooks;
 pub mod namespaces;
 pub mod network_policies;
@@ -29,16 +30,20 @@ pub mod pod_disruption_budgets;
 pub mod pods;
 pub mod port_forward;
 pub mod priority_classes;
+pub mod prometheus;
 pub mod replica_sets;
 pub mod replication_controllers;
 pub mod resource_quotas;
 pub mod role_bindings;
 pub mod roles;
+pub mod rollouts;
 pub mod runtime_classes;
+pub mod scaling;
 pub mod secrets;
 pub mod service_accounts;
 pub mod services;
 pub mod stateful_sets;
 pub mod storage_classes;
+pub mod termination;
 pub mod validating_webhooks;
 pub mod warmup;
@@ -1,12 +1,18 @@
 use std::sync::Arc;
 
+use crate::manager::k8s::nodes::K8sNodes;
+use crate::types::delete::DeleteOptions;
+use crate::types::nodes::{NodeDrainOptions, NodeDrainReport, NodeTaintRemoval, NodeTaintSpec};
 use crate::{
-    commands::common::watch, manager::k8s::cluster_resources::K8sClusterResources,
-    utils::watcher::WatchManager,
+    commands::common::watch,
+    manager::k8s::cluster_resources::K8sClusterResources,
+    utils::{drains::DrainManager, watcher::WatchManager},
 };
 use k8s_openapi::api::core::v1::Node;
 use serde_json::Value;
+use std::collections::BTreeMap;
 use tauri::AppHandle;
+use tokio_util::sync::CancellationToken;
 
 #[tauri::command]
 pub async fn list_nodes(name: String) -> Result<Vec<Value>, String> {
@@ -36,6 +42,90 @@ pub async fn watch_nodes(
 pub async fn delete_nodes(
     name: String,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sClusterResources::<Node>::delete(name, resource_names).await?)
+    Ok(K8sClusterResources::<Node>::delete(name, resource_names, options.unwrap_or_default())
+        .await?)
+}
+
+#[tauri::command]
+pub async fn cordon_nodes(
+    name: String,
+    resource_names: Vec<String>,
+) -> Result<Vec<Result<String, String>>, String> {
+    K8sNodes::set_unschedulable(name, resource_names, true).await
+}
+
+#[tauri::command]
+pub async fn uncordon_nodes(
+    name: String,
+    resource_names: Vec<String>,
+) -> Result<Vec<Result<String, String>>, String> {
+    K8sNodes::set_unschedulable(name, resource_names, false).await
+}
+
+// Progress is emitted on k8s://{name}/nodes/{node}/drain while the drain runs.
+#[tauri::command]
+pub async fn drain_node(
+    app_handle: AppHandle,
+    name: String,
+    node_name: String,
+    options: Option<NodeDrainOptions>,
+    state: tauri::State<'_, DrainManager>,
+) -> Result<NodeDrainReport, String> {
+    let event_name: String = format!("k8s://{}/nodes/{}/drain", name, node_name);
+    let cancel: CancellationToken = state.start(&event_name).await?;
+    let result: Result<NodeDrainReport, String> = K8sNodes::drain(
+        app_handle,
+        name,
+        node_name,
+        options.unwrap_or_default(),
+        event_name.clone(),
+        cancel,
+    )
+    .await;
+    state.finish(&event_name).await;
+    result
+}
+
+// Stops retrying blocked evictions; drain_node then returns its report.
+#[tauri::command]
+pub async fn cancel_drain(
+    name: String,
+    node_name: String,
+    state: tauri::State<'_, DrainManager>,
+) -> Result<(), String> {
+    state.cancel(&format!("k8s://{}/nodes/{}/drain", name, node_name)).await
+}
+
+#[tauri::command]
+pub async fn update_node_labels(
+    name: String,
+    resource_names: Vec<String>,
+    set: Option<BTreeMap<String, String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Vec<Result<String, String>>, String> {
+    K8sNodes::update_labels(
+        name,
+        resource_names,
+        set.unwrap_or_default(),
+        remove.unwrap_or_default(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn update_node_taints(
+    name: String,
+    resource_names: Vec<String>,
+    set: Option<Vec<NodeTaintSpec>>,
+    remove: Option<Vec<NodeTaintRemoval>>,
+) -> Result<Vec<Result<String, String>>, String> {
+    K8sNodes::update_taints(
+        name,
+        resource_names,
+        set.unwrap_or_default(),
+        remove.unwrap_or_default(),
+    )
+    .await
 }
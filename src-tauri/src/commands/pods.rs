@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
+use crate::manager::k8s::eviction::K8sEviction;
+use crate::types::delete::DeleteOptions;
 use crate::{
     commands::common::watch,
-    manager::k8s::{pod_resources::PodResources, resources::K8sResources},
+    manager::k8s::{pod_files::PodFiles, pod_resources::PodResources, resources::K8sResources},
+    types::pod_files::{PodFileEntry, PodFileListing, PodFilePreview},
     utils::watcher::WatchManager,
 };
 use k8s_openapi::api::core::v1::Pod;
@@ -59,8 +62,21 @@ pub async fn delete_pods(
     name: String,
     namespace: Option<String>,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sResources::<Pod>::delete(name, namespace, resource_names).await?)
+    Ok(K8sResources::<Pod>::delete(name, namespace, resource_names, options.unwrap_or_default())
+        .await?)
+}
+
+// Evictions go through the apiserver's disruption checks, unlike deletes.
+#[tauri::command]
+pub async fn evict_pods(
+    name: String,
+    namespace: String,
+    resource_names: Vec<String>,
+    grace_period_seconds: Option<u32>,
+) -> Result<Vec<Result<String, String>>, String> {
+    K8sEviction::evict_pods(name, namespace, resource_names, grace_period_seconds).await
 }
 
 #[tauri::command]
@@ -123,6 +139,40 @@ pub async fn exec_pod(
         .await
 }
 
+#[tauri::command]
+pub async fn list_pod_files(
+    context: String,
+    namespace: String,
+    pod_name: String,
+    container_name: Option<String>,
+    path: String,
+) -> Result<PodFileListing, String> {
+    PodFiles::list_dir(context, namespace, pod_name, container_name, path).await
+}
+
+#[tauri::command]
+pub async fn stat_pod_file(
+    context: String,
+    namespace: String,
+    pod_name: String,
+    container_name: Option<String>,
+    path: String,
+) -> Result<PodFileEntry, String> {
+    PodFiles::stat(context, namespace, pod_name, container_name, path).await
+}
+
+#[tauri::command]
+pub async fn read_pod_file(
+    context: String,
+    namespace: String,
+    pod_name: String,
+    container_name: Option<String>,
+    path: String,
+    max_kb: Option<u32>,
+) -> Result<PodFilePreview, String> {
+    PodFiles::read_preview(context, namespace, pod_name, container_name, path, max_kb).await
+}
+
 #[derive(Serialize)]
 pub struct ExecStartResult {
     pub event_name: String,
@@ -1,5 +1,8 @@
 use crate::manager::k8s::port_forward::PortForwarder;
-use crate::utils::port_forward::{PortForwardItem, PortForwardManager};
+use crate::utils::port_forward::{
+    PortForwardItem, PortForwardManager, PortForwardMeta, PortForwardMode, PortForwardOptions,
+    PortForwardPortSpec, RemotePort, ServiceProxyOptions,
+};
 use tauri::AppHandle;
 
 #[tauri::command]
@@ -10,11 +13,72 @@ pub async fn start_port_forward(
     namespace: String,
     resource_kind: String,
     resource_name: String,
-    local_port: u16,
-    remote_port: u16,
+    local_port: Option<u16>,
+    remote_port: Option<u16>,
+    ports: Option<Vec<PortForwardPortSpec>>,
+    bind_address: Option<String>,
+    display_name: Option<String>,
+    group: Option<String>,
+    auto_start: Option<bool>,
 ) -> Result<serde_json::Value, String> {
+    // `ports` supersedes the single local/remote pair; a local port of 0 picks a free one
+    let specs: Vec<PortForwardPortSpec> = match ports {
+        Some(p) if !p.is_empty() => p,
+        _ => {
+            let remote: u16 = remote_port.ok_or_else(|| "remote port is required".to_string())?;
+            vec![PortForwardPortSpec {
+                local_port: local_port.unwrap_or(0),
+                remote_port: RemotePort::Number(remote),
+            }]
+        }
+    };
+
+    let options = PortForwardOptions {
+        bind_address,
+        meta: PortForwardMeta {
+            name: display_name.filter(|n| !n.trim().is_empty()),
+            group: group.filter(|g| !g.trim().is_empty()),
+            auto_start: auto_start.unwrap_or(false),
+            ..Default::default()
+        },
+    };
+
     let pf = PortForwarder::new(app_handle, &state);
-    pf.start(context, namespace, resource_kind, resource_name, local_port, remote_port).await
+    pf.start(context, namespace, resource_kind, resource_name, specs, options).await
+}
+
+#[tauri::command]
+pub async fn start_service_proxy(
+    app_handle: AppHandle,
+    state: tauri::State<'_, PortForwardManager>,
+    context: String,
+    namespace: String,
+    service_name: String,
+    port: RemotePort,
+    options: Option<ServiceProxyOptions>,
+) -> Result<serde_json::Value, String> {
+    let options: ServiceProxyOptions = options.unwrap_or_default();
+    let mode: PortForwardMode = match options.scheme.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("") | Some("http") => PortForwardMode::Http,
+        Some("https") => PortForwardMode::Https,
+        Some(other) => return Err(format!("unsupported proxy scheme: {}", other)),
+    };
+    let spec = PortForwardPortSpec {
+        local_port: options.local_port.unwrap_or(0),
+        remote_port: port,
+    };
+    let pf_options = PortForwardOptions {
+        bind_address: options.bind_address,
+        meta: PortForwardMeta {
+            name: options.display_name.filter(|n| !n.trim().is_empty()),
+            group: options.group.filter(|g| !g.trim().is_empty()),
+            auto_start: options.auto_start,
+            ..Default::default()
+        },
+    };
+
+    let pf = PortForwarder::new(app_handle, &state);
+    pf.start_service_proxy(context, namespace, service_name, spec, mode, pf_options).await
 }
 
 #[tauri::command]
@@ -44,8 +108,63 @@ pub async fn resume_port_forward(
 
 #[tauri::command]
 pub async fn delete_port_forward(
+    app_handle: AppHandle,
     state: tauri::State<'_, PortForwardManager>,
     session_id: String,
 ) -> Result<(), String> {
-    PortForwarder::delete(state, session_id).await
+    let pf = PortForwarder::new(app_handle, &state);
+    pf.delete(session_id).await
+}
+
+#[tauri::command]
+pub async fn rename_port_forward(
+    app_handle: AppHandle,
+    state: tauri::State<'_, PortForwardManager>,
+    session_id: String,
+    display_name: String,
+) -> Result<(), String> {
+    let pf = PortForwarder::new(app_handle, &state);
+    pf.rename(session_id, display_name).await
+}
+
+#[tauri::command]
+pub async fn set_port_forward_group(
+    app_handle: AppHandle,
+    state: tauri::State<'_, PortForwardManager>,
+    session_ids: Vec<String>,
+    group: Option<String>,
+) -> Result<(), String> {
+    let pf = PortForwarder::new(app_handle, &state);
+    pf.set_group(session_ids, group).await
+}
+
+#[tauri::command]
+pub async fn set_port_forward_auto_start(
+    app_handle: AppHandle,
+    state: tauri::State<'_, PortForwardManager>,
+    session_id: String,
+    auto_start: bool,
+) -> Result<(), String> {
+    let pf = PortForwarder::new(app_handle, &state);
+    pf.set_auto_start(session_id, auto_start).await
+}
+
+#[tauri::command]
+pub async fn start_port_forward_group(
+    app_handle: AppHandle,
+    state: tauri::State<'_, PortForwardManager>,
+    group: String,
+) -> Result<Vec<Result<String, String>>, String> {
+    let pf = PortForwarder::new(app_handle, &state);
+    Ok(pf.start_group(group).await)
+}
+
+#[tauri::command]
+pub async fn stop_port_forward_group(
+    app_handle: AppHandle,
+    state: tauri::State<'_, PortForwardManager>,
+    group: String,
+) -> Result<Vec<Result<String, String>>, String> {
+    let pf = PortForwarder::new(app_handle, &state);
+    Ok(pf.stop_group(group).await)
 }
@@ -0,0 +1,84 @@
+use crate::manager::k8s::prometheus::K8sPrometheus;
+use crate::state::AppState;
+use crate::types::prometheus::{
+    PrometheusCandidate, PrometheusConfig, PrometheusQueryResult, PrometheusQueryTemplate,
+    PrometheusRange,
+};
+use std::collections::HashMap;
+
+#[tauri::command]
+pub async fn get_prometheus_config(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<PrometheusConfig>, String> {
+    K8sPrometheus::config(&state, &name)
+}
+
+#[tauri::command]
+pub async fn set_prometheus_config(
+    name: String,
+    config: PrometheusConfig,
+    state: tauri::State<'_, AppState>,
+) -> Result<PrometheusConfig, String> {
+    K8sPrometheus::save_config(&state, &name, config)
+}
+
+#[tauri::command]
+pub async fn delete_prometheus_config(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    K8sPrometheus::delete_config(&state, &name)
+}
+
+#[tauri::command]
+pub async fn discover_prometheus(name: String) -> Result<Vec<PrometheusCandidate>, String> {
+    K8sPrometheus::discover(&name).await
+}
+
+#[tauri::command]
+pub async fn check_prometheus(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    K8sPrometheus::check(&state, &name).await
+}
+
+#[tauri::command]
+pub async fn list_prometheus_queries(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<PrometheusQueryTemplate>, String> {
+    K8sPrometheus::queries(&state, &name)
+}
+
+#[tauri::command]
+pub async fn prometheus_query(
+    name: String,
+    query: String,
+    time: Option<f64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<PrometheusQueryResult, String> {
+    K8sPrometheus::query(&state, &name, query, time).await
+}
+
+#[tauri::command]
+pub async fn prometheus_query_range(
+    name: String,
+    query: String,
+    range: PrometheusRange,
+    state: tauri::State<'_, AppState>,
+) -> Result<PrometheusQueryResult, String> {
+    K8sPrometheus::query_range(&state, &name, query, range).await
+}
+
+#[tauri::command]
+pub async fn run_prometheus_query(
+    name: String,
+    query_id: String,
+    variables: Option<HashMap<String, String>>,
+    range: Option<PrometheusRange>,
+    state: tauri::State<'_, AppState>,
+) -> Result<PrometheusQueryResult, String> {
+    K8sPrometheus::run_query(&state, &name, &query_id, variables.unwrap_or_default(), range).await
+}
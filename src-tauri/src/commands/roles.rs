@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::types::delete::DeleteOptions;
 use crate::{
     commands::common::watch, manager::k8s::resources::K8sResources, utils::watcher::WatchManager,
 };
@@ -56,6 +57,8 @@ pub async fn delete_roles(
     name: String,
     namespace: Option<String>,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sResources::<Role>::delete(name, namespace, resource_names).await?)
+    Ok(K8sResources::<Role>::delete(name, namespace, resource_names, options.unwrap_or_default())
+        .await?)
 }
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::commands::common::watch;
+use crate::manager::k8s::rollouts::K8sRollouts;
+use crate::types::rollouts::{RolloutKind, RolloutRevision, RolloutStatus};
+use crate::utils::watcher::WatchManager;
+use serde_json::Value;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn get_rollout_status(
+    name: String,
+    kind: RolloutKind,
+    namespace: String,
+    resource_name: String,
+) -> Result<RolloutStatus, String> {
+    K8sRollouts::status(name, kind, namespace, resource_name).await
+}
+
+#[tauri::command]
+pub async fn get_rollout_history(
+    name: String,
+    kind: RolloutKind,
+    namespace: String,
+    resource_name: String,
+) -> Result<Vec<RolloutRevision>, String> {
+    K8sRollouts::history(name, kind, namespace, resource_name).await
+}
+
+#[tauri::command]
+pub async fn pause_rollout(
+    name: String,
+    kind: RolloutKind,
+    namespace: String,
+    resource_name: String,
+) -> Result<Value, String> {
+    K8sRollouts::set_paused(name, kind, namespace, resource_name, true).await
+}
+
+#[tauri::command]
+pub async fn resume_rollout(
+    name: String,
+    kind: RolloutKind,
+    namespace: String,
+    resource_name: String,
+) -> Result<Value, String> {
+    K8sRollouts::set_paused(name, kind, namespace, resource_name, false).await
+}
+
+// Without a revision (or with 0) this rolls back to the previous one, like kubectl.
+#[tauri::command]
+pub async fn undo_rollout(
+    name: String,
+    kind: RolloutKind,
+    namespace: String,
+    resource_name: String,
+    to_revision: Option<i64>,
+) -> Result<Value, String> {
+    K8sRollouts::undo(name, kind, namespace, resource_name, to_revision).await
+}
+
+// Snapshots are emitted on k8s://{name}/rollouts/{kind}/{namespace}/{resource}.
+#[tauri::command]
+pub async fn watch_rollout(
+    app_handle: AppHandle,
+    name: String,
+    kind: RolloutKind,
+    namespace: String,
+    resource_name: String,
+    timeout_seconds: Option<u64>,
+    state: tauri::State<'_, WatchManager>,
+) -> Result<String, String> {
+    let resource: String = format!("rollouts/{:?}/{}/{}", kind, namespace, resource_name);
+    // A finished rollout watch stays registered; restart it for the new rollout
+    state.unwatch(&format!("k8s://{}/{}", name, resource)).await?;
+    watch(
+        app_handle,
+        name,
+        resource,
+        None,
+        state,
+        Arc::new(move |app_handle, ctx_name, _namespaces, event_name| {
+            K8sRollouts::watch(
+                app_handle,
+                ctx_name,
+                kind,
+                namespace.clone(),
+                resource_name.clone(),
+                timeout_seconds,
+                event_name,
+            )
+        }),
+    )
+    .await
+}
@@ -0,0 +1,26 @@
+use crate::manager::k8s::scaling::K8sScaling;
+use crate::types::scaling::ScaleResult;
+
+// Any kind with a /scale subresource, custom resources included. When an HPA manages
+// the target nothing changes unless `force` is set; the result names the autoscaler.
+#[tauri::command]
+pub async fn scale_resource(
+    name: String,
+    api_version: String,
+    kind: String,
+    namespace: Option<String>,
+    resource_name: String,
+    replicas: i32,
+    force: Option<bool>,
+) -> Result<ScaleResult, String> {
+    K8sScaling::scale(
+        name,
+        api_version,
+        kind,
+        namespace,
+        resource_name,
+        replicas,
+        force.unwrap_or(false),
+    )
+    .await
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
+use crate::manager::k8s::scaling::K8sScaling;
+use crate::types::delete::DeleteOptions;
 use crate::{
-    commands::common::{restart_patch, scale_patch, watch},
+    commands::common::{restart_patch, watch},
     manager::k8s::resources::K8sResources,
     utils::watcher::WatchManager,
 };
@@ -58,8 +60,15 @@ pub async fn delete_stateful_sets(
     name: String,
     namespace: Option<String>,
     resource_names: Vec<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<Vec<Result<String, String>>, String> {
-    Ok(K8sResources::<StatefulSet>::delete(name, namespace, resource_names).await?)
+    Ok(K8sResources::<StatefulSet>::delete(
+        name,
+        namespace,
+        resource_names,
+        options.unwrap_or_default(),
+    )
+    .await?)
 }
 
 #[tauri::command]
@@ -78,19 +87,23 @@ pub async fn restart_stateful_set(
     .await
 }
 
+// Fails with a warning when an HPA manages the StatefulSet unless `force` is set.
 #[tauri::command]
 pub async fn scale_stateful_set(
     name: String,
     namespace: Option<String>,
     resource_name: String,
     replicas: i32,
+    force: Option<bool>,
 ) -> Result<Value, String> {
-    K8sResources::<StatefulSet>::patch(
+    K8sScaling::scale_object(
         name,
+        "apps/v1",
+        "StatefulSet",
         namespace,
         resource_name,
-        scale_patch(replicas),
-        "merge".into(),
+        replicas,
+        force.unwrap_or(false),
     )
     .await
 }
@@ -0,0 +1,36 @@
+use crate::manager::k8s::termination::K8sTermination;
+use crate::types::termination::TerminationDiagnostics;
+
+#[tauri::command]
+pub async fn diagnose_termination(
+    name: String,
+    api_version: String,
+    kind: String,
+    namespace: Option<String>,
+    resource_name: String,
+) -> Result<TerminationDiagnostics, String> {
+    K8sTermination::diagnose(name, api_version, kind, namespace, resource_name).await
+}
+
+// Skips whatever cleanup the finalizers stand for; `confirm` must repeat the resource name.
+#[tauri::command]
+pub async fn remove_finalizers(
+    name: String,
+    api_version: String,
+    kind: String,
+    namespace: Option<String>,
+    resource_name: String,
+    finalizers: Option<Vec<String>>,
+    confirm: String,
+) -> Result<Vec<String>, String> {
+    K8sTermination::remove_finalizers(
+        name,
+        api_version,
+        kind,
+        namespace,
+        resource_name,
+        finalizers,
+        confirm,
+    )
+    .await
+}
@@ -2,6 +2,8 @@ use serde_json::Value;
 use tauri::AppHandle;
 
 use crate::commands::{deployments, namespaces, nodes, pods, services};
+use crate::manager::k8s::metrics_history::K8sMetricsHistory;
+use crate::manager::k8s::port_forward::spawn_auto_start;
 use crate::utils::watcher::WatchManager;
 
 fn value_to_ns_name(v: &Value) -> Option<String> {
@@ -47,6 +49,9 @@ pub async fn warmup_context(
         selected_ns.push(n);
     }
 
+    spawn_auto_start(app_handle.clone(), name.clone());
+    K8sMetricsHistory::start_recording(app_handle.clone(), name.clone()).await;
+
     let _ = namespaces::watch_namespaces(app_handle.clone(), name.clone(), state.clone()).await;
     let _ = nodes::watch_nodes(app_handle.clone(), name.clone(), state.clone()).await;
     if !selected_ns.is_empty() {
@@ -0,0 +1,16 @@
+use std::time::Duration;
+
+// metrics-server scrapes every 15s by default; polling faster only repeats samples
+pub const METRICS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+// Retry interval while the metrics API is not installed
+pub const METRICS_UNAVAILABLE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// Usage history sampling interval per connected context
+pub const METRICS_HISTORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+// Downsampling tiers as (bucket size, retention) in seconds, finest first
+pub const METRICS_HISTORY_TIERS: [(u64, u64); 3] =
+    [(60, 6 * 3600), (600, 3 * 24 * 3600), (3600, 30 * 24 * 3600)];
+// Old buckets are removed at most this often
+pub const METRICS_HISTORY_PRUNE_INTERVAL: Duration = Duration::from_secs(600);
+// Upper bound of points returned per series by a history query
+pub const METRICS_HISTORY_MAX_POINTS: u64 = 720;
@@ -1,2 +1,6 @@
 pub mod app;
+pub mod metrics;
+pub mod prometheus;
+pub mod rollouts;
+pub mod termination;
 pub mod watch;
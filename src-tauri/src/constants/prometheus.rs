@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+pub const PROMETHEUS_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+// Used for `$rate` when a run does not set it
+pub const PROMETHEUS_DEFAULT_RATE: &str = "5m";
+// Prometheus rejects range queries above 11000 points per series
+pub const PROMETHEUS_MAX_POINTS: f64 = 11000.0;
+
+/// Built-in queries as (id, title, scope, unit, expression). Expressions use the cAdvisor
+/// metrics scraped from the kubelet and kube-state-metrics for restarts. Workload pods
+/// are matched by name with `$workload_pods`, an exact pattern for the workload's kind.
+// Pod name pattern per workload kind, after the escaped workload name
+pub const PROMETHEUS_WORKLOAD_POD_PATTERNS: [(&str, &str); 4] = [
+    ("Deployment", "-[a-z0-9]+-[a-z0-9]{5}"),
+    ("StatefulSet", "-[0-9]+"),
+    ("DaemonSet", "-[a-z0-9]{5}"),
+    ("Job", "-[a-z0-9]{5}"),
+];
+
+pub const PROMETHEUS_BUILTIN_QUERIES: [(&str, &str, &str, &str, &str); 14] = [
+    (
+        "pod_cpu",
+        "Pod CPU",
+        "pod",
+        "cores",
+        r#"sum by (pod) (rate(container_cpu_usage_seconds_total{namespace="$namespace", pod="$pod", container!="", container!="POD"}[$rate]))"#,
+    ),
+    (
+        "pod_memory",
+        "Pod memory",
+        "pod",
+        "bytes",
+        r#"sum by (pod) (container_memory_working_set_bytes{namespace="$namespace", pod="$pod", container!="", container!="POD"})"#,
+    ),
+    (
+        "pod_restarts",
+        "Pod restarts",
+        "pod",
+        "count",
+        r#"sum by (pod) (kube_pod_container_status_restarts_total{namespace="$namespace", pod="$pod"})"#,
+    ),
+    (
+        "pod_network_receive",
+        "Pod network received",
+        "pod",
+        "bytesPerSecond",
+        r#"sum by (pod) (rate(container_network_receive_bytes_total{namespace="$namespace", pod="$pod"}[$rate]))"#,
+    ),
+    (
+        "pod_network_transmit",
+        "Pod network transmitted",
+        "pod",
+        "bytesPerSecond",
+        r#"sum by (pod) (rate(container_network_transmit_bytes_total{namespace="$namespace", pod="$pod"}[$rate]))"#,
+    ),
+    (
+        "workload_cpu",
+        "Workload CPU by pod",
+        "workload",
+        "cores",
+        r#"sum by (pod) (rate(container_cpu_usage_seconds_total{namespace="$namespace", pod=~"$workload_pods", container!="", container!="POD"}[$rate]))"#,
+    ),
+    (
+        "workload_memory",
+        "Workload memory by pod",
+        "workload",
+        "bytes",
+        r#"sum by (pod) (container_memory_working_set_bytes{namespace="$namespace", pod=~"$workload_pods", container!="", container!="POD"})"#,
+    ),
+    (
+        "workload_restarts",
+        "Workload restarts by pod",
+        "workload",
+        "count",
+        r#"sum by (pod) (kube_pod_container_status_restarts_total{namespace="$namespace", pod=~"$workload_pods"})"#,
+    ),
+    (
+        "workload_network_receive",
+        "Workload network received",
+        "workload",
+        "bytesPerSecond",
+        r#"sum(rate(container_network_receive_bytes_total{namespace="$namespace", pod=~"$workload_pods"}[$rate]))"#,
+    ),
+    (
+        "workload_network_transmit",
+        "Workload network transmitted",
+        "workload",
+        "bytesPerSecond",
+        r#"sum(rate(container_network_transmit_bytes_total{namespace="$namespace", pod=~"$workload_pods"}[$rate]))"#,
+    ),
+    (
+        "namespace_cpu",
+        "Namespace CPU",
+        "namespace",
+        "cores",
+        r#"sum(rate(container_cpu_usage_seconds_total{namespace="$namespace", container!="", container!="POD"}[$rate]))"#,
+    ),
+    (
+        "namespace_memory",
+        "Namespace memory",
+        "namespace",
+        "bytes",
+        r#"sum(container_memory_working_set_bytes{namespace="$namespace", container!="", container!="POD"})"#,
+    ),
+    (
+        "node_cpu",
+        "Node CPU",
+        "node",
+        "cores",
+        r#"sum(rate(container_cpu_usage_seconds_total{node="$node", id="/"}[$rate]))"#,
+    ),
+    (
+        "node_memory",
+        "Node memory",
+        "node",
+        "bytes",
+        r#"sum(container_memory_working_set_bytes{node="$node", id="/"})"#,
+    ),
+];
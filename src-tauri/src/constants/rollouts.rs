@@ -0,0 +1,7 @@
+use std::time::Duration;
+
+// How often `watch_rollout` checks the workload and its pods
+pub const ROLLOUT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+// Timeout for StatefulSets and DaemonSets, which have no progress deadline; the
+// Deployment default of progressDeadlineSeconds
+pub const ROLLOUT_DEFAULT_TIMEOUT_SECONDS: u64 = 600;
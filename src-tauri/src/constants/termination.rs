@@ -0,0 +1,4 @@
+// Names listed per kind in the remaining resources of a terminating namespace
+pub const TERMINATION_REMAINING_NAMES: usize = 20;
+// Kinds listed at the same time when looking for remaining resources
+pub const TERMINATION_LIST_CONCURRENCY: usize = 8;
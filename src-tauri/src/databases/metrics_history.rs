@@ -0,0 +1,273 @@
+use crate::types::metrics::{MetricsHistoryPoint, MetricsHistorySettings, MetricsSeriesKind};
+use anyhow::Result;
+use bincode::config;
+use bincode::serde::{decode_from_slice, encode_to_vec};
+use serde::{Deserialize, Serialize};
+use sled::{Batch, Db, Tree};
+
+#[derive(Clone)]
+pub struct MetricsHistoryRepo {
+    // {context}\0{resolution}\0{kind}\0{series}\0 + big-endian bucket start (unix seconds)
+    samples: Tree,
+    // {context}\0{kind}\0{series} -> big-endian unix seconds of the last sample
+    series: Tree,
+    // {context} -> MetricsHistorySettings
+    settings: Tree,
+}
+
+/// A usage sample to record; CPU in cores and memory in bytes.
+pub struct HistorySample {
+    pub kind: MetricsSeriesKind,
+    pub key: String,
+    pub cpu: Option<f64>,
+    pub memory: Option<f64>,
+}
+
+// One bucket on disk. Sums and counts are stored instead of averages so later samples
+// can be folded into the same bucket.
+#[derive(Serialize, Deserialize, Default)]
+struct BucketRecord {
+    cpu_sum: f64,
+    cpu_max: f64,
+    cpu_count: u32,
+    memory_sum: f64,
+    memory_max: f64,
+    memory_count: u32,
+}
+
+impl BucketRecord {
+    fn add(&mut self, cpu: Option<f64>, memory: Option<f64>) {
+        if let Some(v) = cpu {
+            self.cpu_max = if self.cpu_count == 0 { v } else { self.cpu_max.max(v) };
+            self.cpu_sum += v;
+            self.cpu_count += 1;
+        }
+        if let Some(v) = memory {
+            self.memory_max = if self.memory_count == 0 { v } else { self.memory_max.max(v) };
+            self.memory_sum += v;
+            self.memory_count += 1;
+        }
+    }
+
+    fn point(&self, bucket_start: u64) -> MetricsHistoryPoint {
+        let avg = |sum: f64, count: u32| (count > 0).then(|| sum / count as f64);
+        MetricsHistoryPoint {
+            timestamp: bucket_start as i64 * 1000,
+            cpu: avg(self.cpu_sum, self.cpu_count),
+            cpu_max: (self.cpu_count > 0).then_some(self.cpu_max),
+            memory: avg(self.memory_sum, self.memory_count),
+            memory_max: (self.memory_count > 0).then_some(self.memory_max),
+        }
+    }
+}
+
+impl MetricsHistoryRepo {
+    pub fn new(db: &Db) -> Result<Self> {
+        Ok(Self {
+            samples: db.open_tree("metrics_history")?,
+            series: db.open_tree("metrics_history_series")?,
+            settings: db.open_tree("metrics_history_settings")?,
+        })
+    }
+
+    pub fn settings(&self, context: &str) -> Result<MetricsHistorySettings> {
+        match self.settings.get(context.as_bytes())? {
+            Some(value) => {
+                let (settings, _len): (MetricsHistorySettings, usize) =
+                    decode_from_slice(&value, config::standard())?;
+                Ok(settings)
+            }
+            None => Ok(MetricsHistorySettings::default()),
+        }
+    }
+
+    pub fn save_settings(&self, context: &str, settings: &MetricsHistorySettings) -> Result<()> {
+        let value: Vec<u8> = encode_to_vec(settings, config::standard())?;
+        self.settings.insert(context.as_bytes(), value)?;
+        let _ = self.settings.flush();
+        Ok(())
+    }
+
+    /// Fold samples taken at `at` (unix seconds) into the bucket of every tier, given as
+    /// resolutions in seconds. Returns how many samples were recorded; samples of new
+    /// series are dropped once the context reaches `max_series`.
+    pub fn record(
+        &self,
+        context: &str,
+        at: u64,
+        samples: &[HistorySample],
+        resolutions: &[u64],
+        max_series: u32,
+    ) -> Result<usize> {
+        let mut series_count: usize =
+            self.series.scan_prefix(Self::context_prefix(context)).count();
+        let mut series_batch: Batch = Batch::default();
+        let mut samples_batch: Batch = Batch::default();
+        let mut recorded: usize = 0;
+
+        for sample in samples {
+            let series_key: Vec<u8> = Self::series_key(context, sample.kind, &sample.key);
+            if !self.series.contains_key(&series_key)? {
+                if series_count >= max_series as usize {
+                    continue;
+                }
+                series_count += 1;
+            }
+            series_batch.insert(series_key, at.to_be_bytes().to_vec());
+
+            for resolution in resolutions {
+                let bucket_start: u64 = at - at % resolution;
+                let mut key: Vec<u8> =
+                    Self::sample_prefix(context, *resolution, sample.kind, &sample.key);
+                key.extend_from_slice(&bucket_start.to_be_bytes());
+                let mut record: BucketRecord = match self.samples.get(&key)? {
+                    Some(value) => decode_from_slice(&value, config::standard())?.0,
+                    None => BucketRecord::default(),
+                };
+                record.add(sample.cpu, sample.memory);
+                samples_batch.insert(key, encode_to_vec(&record, config::standard())?);
+            }
+            recorded += 1;
+        }
+
+        self.samples.apply_batch(samples_batch)?;
+        self.series.apply_batch(series_batch)?;
+        Ok(recorded)
+    }
+
+    /// Buckets of one series at the given resolution whose start lies in `from..=to`.
+    pub fn query(
+        &self,
+        context: &str,
+        resolution: u64,
+        kind: MetricsSeriesKind,
+        key: &str,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<MetricsHistoryPoint>> {
+        let prefix: Vec<u8> = Self::sample_prefix(context, resolution, kind, key);
+        let mut start: Vec<u8> = prefix.clone();
+        start.extend_from_slice(&(from - from % resolution).to_be_bytes());
+        let mut end: Vec<u8> = prefix;
+        end.extend_from_slice(&to.to_be_bytes());
+
+        let mut points: Vec<MetricsHistoryPoint> = Vec::new();
+        for result in self.samples.range(start..=end) {
+            let (k, value) = result?;
+            let (record, _len): (BucketRecord, usize) =
+                decode_from_slice(&value, config::standard())?;
+            points.push(record.point(Self::bucket_start(&k)));
+        }
+        Ok(points)
+    }
+
+    /// Series keys of a kind that have been sampled since `since` (unix seconds).
+    pub fn series_keys(
+        &self,
+        context: &str,
+        kind: MetricsSeriesKind,
+        since: u64,
+    ) -> Result<Vec<String>> {
+        let prefix: Vec<u8> = Self::series_key(context, kind, "");
+        let mut keys: Vec<String> = Vec::new();
+        for result in self.series.scan_prefix(&prefix) {
+            let (k, value) = result?;
+            if Self::bucket_start(&value) >= since {
+                keys.push(String::from_utf8_lossy(&k[prefix.len()..]).to_string());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Drop buckets that are older than their tier's retention, given as
+    /// (resolution, retention) pairs in seconds, and series not sampled within the
+    /// longest retention.
+    pub fn prune(&self, context: &str, now: u64, tiers: &[(u64, u64)]) -> Result<usize> {
+        let mut removed: usize = 0;
+        for (resolution, retention) in tiers {
+            let cutoff: u64 = now.saturating_sub(*retention);
+            let mut prefix: Vec<u8> = Self::context_prefix(context);
+            prefix.extend_from_slice(resolution.to_string().as_bytes());
+            prefix.push(0);
+            let mut batch: Batch = Batch::default();
+            for result in self.samples.scan_prefix(&prefix) {
+                let (k, _value) = result?;
+                if Self::bucket_start(&k) < cutoff {
+                    batch.remove(k);
+                    removed += 1;
+                }
+            }
+            self.samples.apply_batch(batch)?;
+        }
+
+        let longest: u64 = tiers.iter().map(|(_, retention)| *retention).max().unwrap_or(0);
+        let cutoff: u64 = now.saturating_sub(longest);
+        let mut batch: Batch = Batch::default();
+        for result in self.series.scan_prefix(Self::context_prefix(context)) {
+            let (k, value) = result?;
+            if Self::bucket_start(&value) < cutoff {
+                batch.remove(k);
+            }
+        }
+        self.series.apply_batch(batch)?;
+        Ok(removed)
+    }
+
+    pub fn clear(&self, context: &str) -> Result<()> {
+        for tree in [&self.samples, &self.series] {
+            let mut batch: Batch = Batch::default();
+            for result in tree.scan_prefix(Self::context_prefix(context)) {
+                let (k, _value) = result?;
+                batch.remove(k);
+            }
+            tree.apply_batch(batch)?;
+        }
+        let _ = self.samples.flush();
+        Ok(())
+    }
+
+    fn context_prefix(context: &str) -> Vec<u8> {
+        let mut prefix: Vec<u8> = context.as_bytes().to_vec();
+        prefix.push(0);
+        prefix
+    }
+
+    fn sample_prefix(
+        context: &str,
+        resolution: u64,
+        kind: MetricsSeriesKind,
+        key: &str,
+    ) -> Vec<u8> {
+        let mut prefix: Vec<u8> = Self::context_prefix(context);
+        for part in [resolution.to_string().as_str(), Self::kind_name(kind), key] {
+            prefix.extend_from_slice(part.as_bytes());
+            prefix.push(0);
+        }
+        prefix
+    }
+
+    fn series_key(context: &str, kind: MetricsSeriesKind, key: &str) -> Vec<u8> {
+        let mut k: Vec<u8> = Self::context_prefix(context);
+        k.extend_from_slice(Self::kind_name(kind).as_bytes());
+        k.push(0);
+        k.extend_from_slice(key.as_bytes());
+        k
+    }
+
+    // Timestamps are the trailing 8 big-endian bytes of keys and values.
+    fn bucket_start(bytes: &[u8]) -> u64 {
+        let mut ts: [u8; 8] = [0; 8];
+        if bytes.len() >= 8 {
+            ts.copy_from_slice(&bytes[bytes.len() - 8..]);
+        }
+        u64::from_be_bytes(ts)
+    }
+
+    fn kind_name(kind: MetricsSeriesKind) -> &'static str {
+        match kind {
+            MetricsSeriesKind::Pod => "pod",
+            MetricsSeriesKind::Container => "container",
+            MetricsSeriesKind::Node => "node",
+        }
+    }
+}
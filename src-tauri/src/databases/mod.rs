@@ -4,6 +4,9 @@ use std::path::PathBuf;
 use sled::Db;
 
 pub mod k8s_contexts;
+pub mod metrics_history;
+pub mod port_forwards;
+pub mod prometheus;
 
 #[derive(Clone)]
 pub struct Database {
@@ -0,0 +1,160 @@
+use crate::types::port_forward::PortForwardDefinition;
+use crate::utils::port_forward::{PortForwardMode, PortForwardPort, RemotePort};
+use anyhow::{bail, Result};
+use bincode::config;
+use bincode::serde::{decode_from_slice, encode_to_vec};
+use serde::{Deserialize, Serialize};
+use sled::{Db, Tree};
+
+// Records start with this marker byte and the format version
+const RECORD_MARKER: u8 = 0xFF;
+const RECORD_VERSION: u8 = 1;
+
+#[derive(Clone)]
+pub struct PortForwardsRepo {
+    tree: Tree,
+}
+
+// On-disk shape. bincode cannot decode the untagged `RemotePort`, so the requested
+// port is stored as explicit number/name fields.
+#[derive(Serialize, Deserialize)]
+struct PortForwardRecord {
+    id: String,
+    name: Option<String>,
+    group: Option<String>,
+    context: String,
+    namespace: String,
+    resource_kind: String,
+    resource_name: String,
+    bind_address: String,
+    ports: Vec<PortRecord>,
+    mode: PortForwardMode,
+    auto_start: bool,
+    created_at: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PortRecord {
+    local_port: u16,
+    remote_port: u16,
+    requested_number: Option<u16>,
+    requested_name: Option<String>,
+    name: Option<String>,
+    protocol: String,
+}
+
+impl PortForwardsRepo {
+    pub fn new(db: &Db) -> Result<Self> {
+        let tree: Tree = db.open_tree("port_forwards")?;
+        Ok(Self { tree })
+    }
+
+    pub fn save_definition(&self, def: &PortForwardDefinition) -> Result<()> {
+        let mut value: Vec<u8> = vec![RECORD_MARKER, RECORD_VERSION];
+        value.extend(encode_to_vec(Self::to_record(def), config::standard())?);
+        self.tree.insert(def.id.as_bytes(), value)?;
+        let _ = self.tree.flush();
+        Ok(())
+    }
+
+    pub fn list_definitions(&self) -> Result<Vec<PortForwardDefinition>> {
+        let mut defs: Vec<PortForwardDefinition> = Vec::new();
+        for result in self.tree.iter() {
+            let (key, value) = result?;
+            // One unreadable record must not cost the user every other saved forward
+            match Self::decode_record(&value) {
+                Ok(record) => defs.push(Self::from_record(record)),
+                Err(e) => eprintln!(
+                    "skipping unreadable port forward {}: {}",
+                    String::from_utf8_lossy(&key),
+                    e
+                ),
+            }
+        }
+        defs.sort_by_key(|d| d.created_at);
+        Ok(defs)
+    }
+
+    pub fn delete_definition(&self, id: &str) -> Result<()> {
+        self.tree.remove(id.as_bytes())?;
+        let _ = self.tree.flush();
+        Ok(())
+    }
+
+    fn decode_record(value: &[u8]) -> Result<PortForwardRecord> {
+        if let [RECORD_MARKER, version, body @ ..] = value {
+            if *version != RECORD_VERSION {
+                bail!("unsupported record version {}", version);
+            }
+            let (record, _len): (PortForwardRecord, usize) =
+                decode_from_slice(body, config::standard())?;
+            return Ok(record);
+        }
+        bail!("record has no version marker")
+    }
+
+    fn to_record(def: &PortForwardDefinition) -> PortForwardRecord {
+        PortForwardRecord {
+            id: def.id.clone(),
+            name: def.name.clone(),
+            group: def.group.clone(),
+            context: def.context.clone(),
+            namespace: def.namespace.clone(),
+            resource_kind: def.resource_kind.clone(),
+            resource_name: def.resource_name.clone(),
+            bind_address: def.bind_address.clone(),
+            ports: def
+                .ports
+                .iter()
+                .map(|p| {
+                    let (requested_number, requested_name) = match &p.requested_port {
+                        RemotePort::Number(n) => (Some(*n), None),
+                        RemotePort::Name(n) => (None, Some(n.clone())),
+                    };
+                    PortRecord {
+                        local_port: p.local_port,
+                        remote_port: p.remote_port,
+                        requested_number,
+                        requested_name,
+                        name: p.name.clone(),
+                        protocol: p.protocol.clone(),
+                    }
+                })
+                .collect(),
+            mode: def.mode,
+            auto_start: def.auto_start,
+            created_at: def.created_at,
+        }
+    }
+
+    fn from_record(record: PortForwardRecord) -> PortForwardDefinition {
+        PortForwardDefinition {
+            id: record.id,
+            name: record.name,
+            group: record.group,
+            context: record.context,
+            namespace: record.namespace,
+            resource_kind: record.resource_kind,
+            resource_name: record.resource_name,
+            bind_address: record.bind_address,
+            ports: record
+                .ports
+                .into_iter()
+                .map(|p| PortForwardPort {
+                    local_port: p.local_port,
+                    remote_port: p.remote_port,
+                    requested_port: match (p.requested_name, p.requested_number) {
+                        (Some(name), _) => RemotePort::Name(name),
+                        (None, Some(n)) => RemotePort::Number(n),
+                        (None, None) => RemotePort::Number(p.remote_port),
+                    },
+                    name: p.name,
+                    protocol: p.protocol,
+                })
+                .collect(),
+            mode: record.mode,
+            auto_start: record.auto_start,
+            created_at: record.created_at,
+        }
+    }
+}
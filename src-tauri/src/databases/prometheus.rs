@@ -0,0 +1,41 @@
+use crate::types::prometheus::PrometheusConfig;
+use anyhow::Result;
+use bincode::config;
+use bincode::serde::{decode_from_slice, encode_to_vec};
+use sled::{Db, Tree};
+
+#[derive(Clone)]
+pub struct PrometheusConfigsRepo {
+    tree: Tree,
+}
+
+impl PrometheusConfigsRepo {
+    pub fn new(db: &Db) -> Result<Self> {
+        let tree: Tree = db.open_tree("prometheus_configs")?;
+        Ok(Self { tree })
+    }
+
+    pub fn get_config(&self, context: &str) -> Result<Option<PrometheusConfig>> {
+        match self.tree.get(context.as_bytes())? {
+            Some(value) => {
+                let (config, _len): (PrometheusConfig, usize) =
+                    decode_from_slice(&value, config::standard())?;
+                Ok(Some(config))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn save_config(&self, context: &str, prometheus: &PrometheusConfig) -> Result<()> {
+        let value: Vec<u8> = encode_to_vec(prometheus, config::standard())?;
+        self.tree.insert(context.as_bytes(), value)?;
+        let _ = self.tree.flush();
+        Ok(())
+    }
+
+    pub fn delete_config(&self, context: &str) -> Result<()> {
+        self.tree.remove(context.as_bytes())?;
+        let _ = self.tree.flush();
+        Ok(())
+    }
+}
@@ -35,6 +35,7 @@ use crate::commands::ingresses;
 use crate::commands::jobs;
 use crate::commands::leases;
 use crate::commands::limit_ranges;
+use crate::commands::metrics;
 use crate::commands::mutating_webhooks;
 use crate::commands::namespaces;
 use crate::commands::network_policies;
@@ -45,17 +46,21 @@ use crate::commands::pod_disruption_budgets;
 use crate::commands::pods;
 use crate::commands::port_forward;
 use crate::commands::priority_classes;
+use crate::commands::prometheus;
 use crate::commands::replica_sets;
 use crate::commands::replication_controllers;
 use crate::commands::resource_quotas;
 use crate::commands::role_bindings;
 use crate::commands::roles;
+use crate::commands::rollouts;
 use crate::commands::runtime_classes;
+use crate::commands::scaling;
 use crate::commands::secrets;
 use crate::commands::service_accounts;
 use crate::commands::services;
 use crate::commands::stateful_sets;
 use crate::commands::storage_classes;
+use crate::commands::termination;
 use crate::commands::validating_webhooks;
 use crate::commands::warmup;
 use crate::utils::connections::ConnectionsManager;
@@ -86,6 +91,18 @@ pub fn run() {
 
         tauri::async_runtime::block_on(async move {
             let st: state::AppState = state::AppState::init(data_dir).await.expect("init db");
+
+            // Saved port forwards come back stopped; flagged ones start when their context connects.
+            match st.port_forwards.list_definitions() {
+                Ok(definitions) => {
+                    app_handle
+                        .state::<crate::utils::port_forward::PortForwardManager>()
+                        .restore(definitions)
+                        .await
+                }
+                Err(e) => eprintln!("failed to restore port forwards: {}", e),
+            }
+
             app_handle.manage(st);
         });
 
@@ -97,6 +114,9 @@ pub fn run() {
         .manage(ConnectionsManager::default())
         .manage(crate::utils::exec::ExecManager::default())
         .manage(crate::utils::port_forward::PortForwardManager::default())
+        .manage(crate::utils::helm_jobs::HelmJobManager::default())
+        .manage(crate::utils::drains::DrainManager::default())
+        .manage(crate::utils::metrics_history::MetricsRecorderManager::default())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
@@ -117,6 +137,30 @@ pub fn run() {
             nodes::list_nodes,
             nodes::watch_nodes,
             nodes::delete_nodes,
+            nodes::cordon_nodes,
+            nodes::uncordon_nodes,
+            nodes::drain_node,
+            nodes::cancel_drain,
+            nodes::update_node_labels,
+            nodes::update_node_taints,
+            metrics::get_pod_metrics,
+            metrics::get_node_metrics,
+            metrics::watch_pod_metrics,
+            metrics::watch_node_metrics,
+            metrics::query_metrics_history,
+            metrics::list_metrics_history_series,
+            metrics::get_metrics_history_settings,
+            metrics::set_metrics_history_settings,
+            metrics::clear_metrics_history,
+            prometheus::get_prometheus_config,
+            prometheus::set_prometheus_config,
+            prometheus::delete_prometheus_config,
+            prometheus::discover_prometheus,
+            prometheus::check_prometheus,
+            prometheus::list_prometheus_queries,
+            prometheus::prometheus_query,
+            prometheus::prometheus_query_range,
+            prometheus::run_prometheus_query,
             namespaces::list_namespaces,
             namespaces::watch_namespaces,
             namespaces::delete_namespaces,
@@ -125,12 +169,16 @@ pub fn run() {
             pods::list_pods,
             pods::watch_pods,
             pods::delete_pods,
+            pods::evict_pods,
             pods::get_pod_logs,
             pods::watch_pod_logs,
             pods::exec_pod,
             pods::start_exec_pod,
             pods::send_exec_input,
             pods::stop_exec_pod,
+            pods::list_pod_files,
+            pods::stat_pod_file,
+            pods::read_pod_file,
             priority_classes::create_priority_class,
             priority_classes::update_priority_class,
             priority_classes::list_priority_classes,
@@ -141,11 +189,14 @@ pub fn run() {
             runtime_classes::list_runtime_classes,
             runtime_classes::watch_runtime_classes,
             runtime_classes::delete_runtime_classes,
+            scaling::scale_resource,
             storage_classes::create_storage_class,
             storage_classes::update_storage_class,
             storage_classes::list_storage_classes,
             storage_classes::watch_storage_classes,
             storage_classes::delete_storage_classes,
+            termination::diagnose_termination,
+            termination::remove_finalizers,
             service_accounts::create_service_account,
             service_accounts::update_service_account,
             service_accounts::list_service_accounts,
@@ -156,6 +207,12 @@ pub fn run() {
             roles::list_roles,
             roles::watch_roles,
             roles::delete_roles,
+            rollouts::get_rollout_status,
+            rollouts::get_rollout_history,
+            rollouts::pause_rollout,
+            rollouts::resume_rollout,
+            rollouts::undo_rollout,
+            rollouts::watch_rollout,
             cluster_roles::create_cluster_role,
             cluster_roles::update_cluster_role,
             cluster_roles::list_cluster_roles,
@@ -240,6 +297,7 @@ pub fn run() {
             horizontal_pod_autoscalers::list_horizontal_pod_autoscalers,
             horizontal_pod_autoscalers::watch_horizontal_pod_autoscalers,
             horizontal_pod_autoscalers::delete_horizontal_pod_autoscalers,
+            horizontal_pod_autoscalers::update_horizontal_pod_autoscaler_replicas,
             pod_disruption_budgets::create_pod_disruption_budget,
             pod_disruption_budgets::update_pod_disruption_budget,
             pod_disruption_budgets::list_pod_disruption_budgets,
@@ -301,8 +359,25 @@ pub fn run() {
             helm::watch_helm_releases,
             helm::helm_get_values,
             helm::helm_get_history,
+            helm::helm_get_manifest,
+            helm::helm_get_release,
+            helm::helm_install_release,
             helm::helm_upgrade_release,
             helm::helm_rollback_release,
+            helm::helm_diff_revisions,
+            helm::helm_diff_upgrade,
+            helm::helm_list_repos,
+            helm::helm_add_repo,
+            helm::helm_remove_repos,
+            helm::helm_update_repos,
+            helm::helm_registry_login,
+            helm::helm_registry_logout,
+            helm::helm_show_chart,
+            helm::helm_release_resources,
+            helm::helm_resolve_upgrade_chart,
+            helm::helm_list_jobs,
+            helm::cancel_helm_job,
+            helm::helm_template,
             custom_resources::create_custom_resource,
             custom_resources::update_custom_resource,
             custom_resources::list_custom_resources,
@@ -314,6 +389,12 @@ pub fn run() {
             port_forward::list_port_forwards,
             port_forward::resume_port_forward,
             port_forward::delete_port_forward,
+            port_forward::rename_port_forward,
+            port_forward::set_port_forward_group,
+            port_forward::set_port_forward_auto_start,
+            port_forward::start_port_forward_group,
+            port_forward::stop_port_forward_group,
+            port_forward::start_service_proxy,
         ]);
 
     builder.run(tauri::generate_context!()).expect("error while running tauri application");
@@ -71,6 +71,13 @@ impl K8sClient {
     }
 
     pub async fn for_context(name: &str) -> Result<Client, String> {
+        let config: Config = Self::config_for_context(name).await?;
+        Client::try_from(config)
+            .map_err(|e| format!("client_from_context: Client::try_from error: {}", e))
+    }
+
+    /// Resolved configuration of a context, for callers that build their own HTTP stack.
+    pub async fn config_for_context(name: &str) -> Result<Config, String> {
         // Check connection gating first; if disconnected, block all requests to this cluster
         let cm = ConnectionsManager::global();
         if !cm.is_connected(name).await {
@@ -86,11 +93,11 @@ impl K8sClient {
         let sanitized: String = Self::sanitize_yaml(&kubeconfig);
 
         let mut errs: Vec<String> = Vec::new();
-        if let Some(client) = Self::try_client_from_custom(&sanitized, name, &mut errs).await? {
-            return Ok(client);
+        if let Some(config) = Self::try_config_from_custom(&sanitized, name, &mut errs).await? {
+            return Ok(config);
         }
 
-        match Self::client_from_default_with_temp(name, &sanitized).await {
+        match Self::config_from_default_with_temp(name, &sanitized).await {
             Ok(c) => Ok(c),
             Err(msg) => {
                 if errs.is_empty() {
@@ -111,11 +118,11 @@ impl K8sClient {
             .collect()
     }
 
-    async fn try_client_from_custom(
+    async fn try_config_from_custom(
         sanitized: &str,
         name: &str,
         errs: &mut Vec<String>,
-    ) -> Result<Option<Client>, String> {
+    ) -> Result<Option<Config>, String> {
         match serde_yaml::from_str::<Kubeconfig>(sanitized) {
             Ok(kcfg) => {
                 let opts = KubeConfigOptions {
@@ -123,15 +130,7 @@ impl K8sClient {
                     ..Default::default()
                 };
                 match Config::from_custom_kubeconfig(kcfg, &opts).await {
-                    Ok(cfg) => match Client::try_from(cfg) {
-                        Ok(c) => Ok(Some(c)),
-                        Err(e) => {
-                            let msg = format!("client_from_context: Client::try_from error: {}", e);
-                            println!("{}", msg);
-                            errs.push(msg);
-                            Ok(None)
-                        }
-                    },
+                    Ok(cfg) => Ok(Some(cfg)),
                     Err(e) => {
                         let msg =
                             format!("client_from_context: from_custom_kubeconfig error: {}", e);
@@ -150,14 +149,14 @@ impl K8sClient {
         }
     }
 
-    async fn client_from_default_with_temp(name: &str, sanitized: &str) -> Result<Client, String> {
+    async fn config_from_default_with_temp(name: &str, sanitized: &str) -> Result<Config, String> {
         let tmp_path: PathBuf = std::env::temp_dir().join(format!("kumate_ctx_{}.yaml", name));
         tokio::fs::write(&tmp_path, sanitized).await.map_err(|e| e.to_string())?;
 
         let old_kubeconfig: Option<String> = std::env::var("KUBECONFIG").ok();
         std::env::set_var("KUBECONFIG", &tmp_path);
 
-        let result: Result<Client, String> = Client::try_default().await.map_err(|e| e.to_string());
+        let result: Result<Config, String> = Config::infer().await.map_err(|e| e.to_string());
 
         match old_kubeconfig {
             Some(v) => std::env::set_var("KUBECONFIG", v),
@@ -5,14 +5,17 @@ use k8s_openapi::{
     apimachinery::pkg::apis::meta::v1::ObjectMeta, Metadata, Resource as K8sResource,
 };
 use kube::{
-    api::{Api, DeleteParams, ObjectList, PostParams, WatchEvent, WatchParams},
+    api::{Api, DeleteParams, ObjectList, Patch, PatchParams, PostParams, WatchEvent, WatchParams},
     Resource,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 
-use crate::{manager::k8s::client::K8sClient, types::event::EventType};
+use crate::{
+    manager::k8s::client::K8sClient,
+    types::{delete::DeleteOptions, event::EventType},
+};
 
 pub struct K8sClusterResources<T> {
     _marker: std::marker::PhantomData<T>,
@@ -55,6 +58,25 @@ where
         Ok(serde_json::to_value(updated).unwrap_or(Value::Null))
     }
 
+    pub async fn patch(
+        context_name: String,
+        resource_name: String,
+        patch: Value,
+        patch_type: String,
+    ) -> Result<Value, String> {
+        let client: kube::Client = K8sClient::for_context(&context_name).await?;
+        let api: Api<T> = Api::all(client);
+        let params: PatchParams = PatchParams::default();
+
+        let result: T = match patch_type.as_str() {
+            "strategic" => api.patch(&resource_name, &params, &Patch::Strategic(patch)).await,
+            _ => api.patch(&resource_name, &params, &Patch::Merge(patch)).await,
+        }
+        .map_err(|e| Self::extract_error(&e, &resource_name))?;
+
+        serde_json::to_value(&result).map_err(|e| e.to_string())
+    }
+
     pub async fn list(context_name: String) -> Result<Vec<Value>, String> {
         let client: kube::Client = K8sClient::for_context(&context_name).await?;
         let api: Api<T> = Api::all(client);
@@ -70,14 +92,16 @@ where
     pub async fn delete(
         context_name: String,
         names: Vec<String>,
+        options: DeleteOptions,
     ) -> Result<Vec<Result<String, String>>, String> {
+        options.validate()?;
         let client: kube::Client = K8sClient::for_context(&context_name).await?;
         let api: Api<T> = Api::all(client);
 
         let futures = names.into_iter().map(|name| {
             let api = api.clone();
+            let dp: DeleteParams = options.params_for(&name);
             async move {
-                let dp: DeleteParams = DeleteParams::default();
                 match api.delete(&name, &dp).await {
                     Ok(_) => Ok(name),
                     Err(e) => Err(e.to_string()),
@@ -171,4 +195,17 @@ where
 
         let _ = app_handle.emit(event_name, event);
     }
+
+    fn extract_error(e: &kube::Error, name: &str) -> String {
+        match e {
+            kube::Error::Api(ae) => {
+                if ae.message.is_empty() {
+                    format!("{}: resource {}", ae.reason, name)
+                } else {
+                    ae.message.clone()
+                }
+            }
+            other => format!("{}: {}", name, other),
+        }
+    }
 }
@@ -1,19 +1,31 @@
 use std::pin::Pin;
 
 use futures_util::{Stream, StreamExt};
-use kube::api::{Api, ApiResource, DeleteParams, ObjectList, PostParams, WatchEvent, WatchParams};
+use kube::api::{
+    Api, ApiResource, DeleteParams, DynamicObject, ObjectList, Patch, PatchParams, PostParams,
+    WatchEvent, WatchParams,
+};
 use kube::core::gvk::GroupVersionKind;
+use kube::discovery::{self, ApiCapabilities, Scope};
 use kube::Client;
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 
-use crate::{manager::k8s::client::K8sClient, types::event::EventType};
+use crate::{
+    manager::k8s::client::K8sClient,
+    types::{delete::DeleteOptions, event::EventType},
+};
 
 /// Generic dynamic manager to operate on Custom Resources (CRDs) without fixed types.
 pub struct DynamicK8sResources;
 
 impl DynamicK8sResources {
-    fn api_resource(group: &str, version: &str, kind: &str, plural: &str) -> ApiResource {
+    pub(crate) fn api_resource(
+        group: &str,
+        version: &str,
+        kind: &str,
+        plural: &str,
+    ) -> ApiResource {
         let gvk: GroupVersionKind = GroupVersionKind::gvk(group, version, kind);
         let mut ar = ApiResource::from_gvk(&gvk);
         // Plural is required to construct the correct endpoint path
@@ -21,6 +33,87 @@ impl DynamicK8sResources {
         ar
     }
 
+    /// Resolve an `apiVersion`/`kind` pair through API discovery. Returns the API resource
+    /// and whether it is namespaced.
+    pub async fn discover(
+        client: &Client,
+        api_version: &str,
+        kind: &str,
+    ) -> Result<(ApiResource, bool), String> {
+        let (ar, caps): (ApiResource, ApiCapabilities) =
+            Self::discover_capabilities(client, api_version, kind).await?;
+        Ok((ar, caps.scope == Scope::Namespaced))
+    }
+
+    /// Like `discover`, with the scope, subresources and verbs of the kind.
+    pub async fn discover_capabilities(
+        client: &Client,
+        api_version: &str,
+        kind: &str,
+    ) -> Result<(ApiResource, ApiCapabilities), String> {
+        let (group, version): (&str, &str) =
+            api_version.split_once('/').unwrap_or(("", api_version));
+        let gvk: GroupVersionKind = GroupVersionKind::gvk(group, version, kind);
+        discovery::pinned_kind(client, &gvk)
+            .await
+            .map_err(|e| format!("{} {} is not served by the cluster: {}", api_version, kind, e))
+    }
+
+    /// Api for an `apiVersion`/`kind` resolved through discovery, scoped to `namespace` when
+    /// the kind is namespaced.
+    pub async fn discovered_api(
+        client: Client,
+        api_version: &str,
+        kind: &str,
+        namespace: Option<&str>,
+    ) -> Result<(Api<DynamicObject>, ApiResource), String> {
+        let (ar, namespaced): (ApiResource, bool) =
+            Self::discover(&client, api_version, kind).await?;
+        let api: Api<DynamicObject> = match (namespaced, namespace) {
+            (true, Some(ns)) => Api::namespaced_with(client, ns, &ar),
+            (true, None) => return Err(format!("{} is namespaced, a namespace is required", kind)),
+            (false, _) => Api::all_with(client, &ar),
+        };
+        Ok((api, ar))
+    }
+
+    /// Remove finalizers from `metadata.finalizers` of any object; `None` removes all of
+    /// them. The patch carries the resourceVersion that was read, so a concurrent change
+    /// fails with a conflict instead of being overwritten. Returns the finalizers left.
+    pub async fn remove_finalizers(
+        context_name: String,
+        api_version: String,
+        kind: String,
+        namespace: Option<String>,
+        resource_name: String,
+        finalizers: Option<Vec<String>>,
+    ) -> Result<Vec<String>, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let (api, _): (Api<DynamicObject>, ApiResource) =
+            Self::discovered_api(client, &api_version, &kind, namespace.as_deref()).await?;
+        let obj: DynamicObject =
+            api.get(&resource_name).await.map_err(|e| Self::extract_error(&e, &resource_name))?;
+        let current: Vec<String> = obj.metadata.finalizers.clone().unwrap_or_default();
+        let remaining: Vec<String> = match finalizers.as_ref() {
+            Some(remove) => current.iter().filter(|f| !remove.contains(f)).cloned().collect(),
+            None => vec![],
+        };
+        if remaining.len() == current.len() {
+            return Ok(remaining);
+        }
+
+        let patch: Value = serde_json::json!({
+            "metadata": {
+                "finalizers": if remaining.is_empty() { Value::Null } else { serde_json::json!(remaining) },
+                "resourceVersion": obj.metadata.resource_version,
+            }
+        });
+        api.patch(&resource_name, &PatchParams::default(), &Patch::Merge(patch))
+            .await
+            .map_err(|e| Self::extract_error(&e, &resource_name))?;
+        Ok(remaining)
+    }
+
     async fn make_api(
         client: Client,
         namespace: Option<String>,
@@ -155,15 +248,17 @@ impl DynamicK8sResources {
         plural: String,
         is_namespaced: bool,
         resource_names: Vec<String>,
+        options: DeleteOptions,
     ) -> Result<Vec<Result<String, String>>, String> {
+        options.validate()?;
         let client: kube::Client = K8sClient::for_context(&context_name).await?;
         let api =
             Self::make_api(client, namespace, &group, &version, &kind, &plural, is_namespaced)
                 .await;
 
-        let dp: DeleteParams = DeleteParams::default();
         let mut results: Vec<Result<String, String>> = Vec::new();
         for name in resource_names {
+            let dp: DeleteParams = options.params_for(&name);
             match api.delete(&name, &dp).await {
                 Ok(_) => results.push(Ok(name)),
                 Err(e) => results.push(Err(Self::extract_error(&e, "delete"))),
@@ -0,0 +1,206 @@
+use crate::manager::k8s::client::K8sClient;
+use futures_util::future::join_all;
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use kube::api::{Api, DeleteParams, EvictParams, ListParams, PostParams};
+use kube::Client;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+// How often blocked evictions are retried and terminating pods are checked
+const EVICTION_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// When retries give up: at the deadline, or as soon as the token is cancelled.
+#[derive(Clone)]
+pub struct EvictionStop {
+    pub deadline: Option<Instant>,
+    pub cancel: CancellationToken,
+}
+
+impl EvictionStop {
+    // Sleep until the next attempt; false when retrying should stop instead
+    async fn wait_retry(&self) -> bool {
+        if self.deadline.is_some_and(|d| Instant::now() + EVICTION_RETRY_INTERVAL > d) {
+            return false;
+        }
+        tokio::select! {
+            _ = self.cancel.cancelled() => false,
+            _ = tokio::time::sleep(EVICTION_RETRY_INTERVAL) => true,
+        }
+    }
+}
+
+pub enum EvictionOutcome {
+    Accepted,
+    // The pod no longer exists
+    Gone,
+    // 429: evicting now would violate a PodDisruptionBudget
+    Blocked(String),
+    Failed(String),
+}
+
+/// Pod eviction through the `pods/eviction` subresource, which lets the apiserver
+/// enforce PodDisruptionBudgets.
+pub struct K8sEviction;
+
+impl K8sEviction {
+    /// Evict pods once each. A refusal because of a PodDisruptionBudget is reported with
+    /// the budgets selecting the pod instead of being retried.
+    pub async fn evict_pods(
+        context_name: String,
+        namespace: String,
+        names: Vec<String>,
+        grace_period: Option<u32>,
+    ) -> Result<Vec<Result<String, String>>, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let api: Api<Pod> = K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
+        let futures = names.into_iter().map(|name| {
+            let (api, client, namespace) = (api.clone(), client.clone(), namespace.clone());
+            async move {
+                match Self::evict(&api, &name, grace_period).await {
+                    EvictionOutcome::Accepted => Ok(name),
+                    EvictionOutcome::Gone => Err(format!("{}: pod not found", name)),
+                    EvictionOutcome::Blocked(message) => {
+                        let budgets: Vec<String> =
+                            Self::budgets_for(client, &namespace, &api, &name).await;
+                        if budgets.is_empty() {
+                            Err(format!("{}: {}", name, message))
+                        } else {
+                            Err(format!(
+                                "{}: blocked by PodDisruptionBudget {}: {}",
+                                name,
+                                budgets.join(", "),
+                                message
+                            ))
+                        }
+                    }
+                    EvictionOutcome::Failed(message) => Err(format!("{}: {}", name, message)),
+                }
+            }
+        });
+        Ok(join_all(futures).await)
+    }
+
+    pub async fn evict(api: &Api<Pod>, name: &str, grace_period: Option<u32>) -> EvictionOutcome {
+        let ep: EvictParams = EvictParams {
+            delete_options: grace_period.map(|g| DeleteParams::default().grace_period(g)),
+            post_options: PostParams::default(),
+        };
+        match api.evict(name, &ep).await {
+            Ok(_) => EvictionOutcome::Accepted,
+            Err(kube::Error::Api(ae)) if ae.code == 404 => EvictionOutcome::Gone,
+            Err(kube::Error::Api(ae)) if ae.code == 429 => EvictionOutcome::Blocked(ae.message),
+            Err(kube::Error::Api(ae)) => EvictionOutcome::Failed(ae.message),
+            Err(e) => EvictionOutcome::Failed(e.to_string()),
+        }
+    }
+
+    /// Retry a blocked eviction until it is accepted or `stop` says to give up.
+    /// `on_blocked` runs once, when the first attempt is refused.
+    pub async fn evict_until(
+        api: &Api<Pod>,
+        name: &str,
+        grace_period: Option<u32>,
+        stop: &EvictionStop,
+        mut on_blocked: impl FnMut(&str),
+    ) -> EvictionOutcome {
+        let mut reported: bool = false;
+        loop {
+            match Self::evict(api, name, grace_period).await {
+                EvictionOutcome::Blocked(message) => {
+                    if !reported {
+                        on_blocked(&message);
+                        reported = true;
+                    }
+                    if !stop.wait_retry().await {
+                        return EvictionOutcome::Blocked(message);
+                    }
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
+    /// Wait until the pod with this uid is gone. A pod recreated under the same name
+    /// (StatefulSets) has a new uid and counts as gone.
+    pub async fn wait_deleted(
+        api: &Api<Pod>,
+        name: &str,
+        uid: Option<&str>,
+        stop: &EvictionStop,
+    ) -> Result<(), String> {
+        loop {
+            match api.get_opt(name).await {
+                Ok(None) => return Ok(()),
+                Ok(Some(pod)) if pod.metadata.uid.as_deref() != uid => return Ok(()),
+                Ok(Some(_)) => {}
+                Err(e) => return Err(format!("failed to check pod {}: {}", name, e)),
+            }
+            if !stop.wait_retry().await {
+                return Err(if stop.cancel.is_cancelled() {
+                    "drain cancelled while the pod was terminating".to_string()
+                } else {
+                    "timed out waiting for the pod to terminate".to_string()
+                });
+            }
+        }
+    }
+
+    // Best effort: only used to explain a refusal.
+    async fn budgets_for(
+        client: Client,
+        namespace: &str,
+        api: &Api<Pod>,
+        name: &str,
+    ) -> Vec<String> {
+        let pod: Pod = match api.get(name).await {
+            Ok(p) => p,
+            Err(_) => return vec![],
+        };
+        let pdb_api: Api<PodDisruptionBudget> =
+            K8sClient::api::<PodDisruptionBudget>(client, Some(namespace.to_string())).await;
+        match pdb_api.list(&ListParams::default()).await {
+            Ok(list) => Self::matching_budgets(&pod, &list.items),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Names of the budgets in the pod's namespace whose selector matches it.
+    pub fn matching_budgets(pod: &Pod, budgets: &[PodDisruptionBudget]) -> Vec<String> {
+        let empty: BTreeMap<String, String> = BTreeMap::new();
+        let labels: &BTreeMap<String, String> = pod.metadata.labels.as_ref().unwrap_or(&empty);
+        budgets
+            .iter()
+            .filter(|b| b.metadata.namespace == pod.metadata.namespace)
+            .filter(|b| {
+                b.spec
+                    .as_ref()
+                    .and_then(|s| s.selector.as_ref())
+                    .is_some_and(|sel| Self::selector_matches(sel, labels))
+            })
+            .filter_map(|b| b.metadata.name.clone())
+            .collect()
+    }
+
+    // An empty selector matches every pod (policy/v1 semantics).
+    fn selector_matches(selector: &LabelSelector, labels: &BTreeMap<String, String>) -> bool {
+        let labels_match: bool = selector
+            .match_labels
+            .as_ref()
+            .is_none_or(|ml| ml.iter().all(|(k, v)| labels.get(k) == Some(v)));
+        let expressions_match: bool = selector.match_expressions.iter().flatten().all(|expr| {
+            let values: &[String] = expr.values.as_deref().unwrap_or(&[]);
+            let value: Option<&String> = labels.get(&expr.key);
+            match expr.operator.as_str() {
+                "In" => value.is_some_and(|v| values.contains(v)),
+                "NotIn" => value.is_none_or(|v| !values.contains(v)),
+                "Exists" => value.is_some(),
+                "DoesNotExist" => value.is_none(),
+                _ => false,
+            }
+        });
+        labels_match && expressions_match
+    }
+}
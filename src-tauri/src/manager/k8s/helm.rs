@@ -1,16 +1,51 @@
 use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::helm_chart::{ChartFiles, HelmCharts};
+use crate::manager::k8s::helm_diff::HelmDiff;
+use crate::manager::k8s::helm_resources::HelmReleaseResources;
+use crate::manager::k8s::helm_storage::HelmStorage;
 use crate::types::event::EventType;
+use crate::types::helm::{
+    HelmChartCandidate, HelmChartInfo, HelmChartMetadata, HelmChartResolution, HelmInstallOptions,
+    HelmRelease, HelmReleaseDetails, HelmReleaseDiff, HelmReleaseResource, HelmRenderedObject,
+    HelmRepoAddOptions, HelmTemplateOptions, HelmTemplateResult,
+};
+use crate::utils::helm_jobs::{HelmJob, HelmJobItem, HelmJobManager, HelmJobState};
 use futures_util::{Stream, StreamExt};
 use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+use k8s_openapi::chrono::Utc;
 use kube::api::{Api, ListParams, WatchEvent, WatchParams};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use tauri::{AppHandle, Emitter};
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
 
 pub struct HelmManager;
 
+// Arguments shared by `helm upgrade` and the dry-run used to diff an upgrade.
+pub struct UpgradeArgs {
+    pub context_name: String,
+    pub namespace: Option<String>,
+    pub release_name: String,
+    pub chart: Option<String>,
+    pub values: Option<Value>,
+    pub reuse_values: bool,
+    pub version: Option<String>,
+}
+
+// A `helm upgrade` command with the chart and namespace it resolved to.
+struct PreparedUpgrade {
+    cmd: Command,
+    // Temp values file for the caller to remove afterwards
+    values_file: Option<PathBuf>,
+    chart: String,
+    namespace: Option<String>,
+}
+
 impl HelmManager {
     pub async fn list_releases(
         context_name: String,
@@ -72,11 +107,13 @@ impl HelmManager {
         Ok(items)
     }
 
+    // One tracked job per release.
     pub async fn uninstall_releases(
+        app_handle: AppHandle,
         context_name: String,
         namespace: Option<String>,
         release_names: Vec<String>,
-    ) -> Result<Vec<Result<String, String>>, String> {
+    ) -> Result<Vec<HelmJobItem>, String> {
         let helm_bin = Self::resolve_helm_bin()
             .await
             .ok_or_else(|| "Helm CLI is not available; uninstall requires Helm".to_string())?;
@@ -105,7 +142,7 @@ impl HelmManager {
             }
         }
 
-        let mut results: Vec<Result<String, String>> = Vec::new();
+        let mut jobs: Vec<HelmJobItem> = Vec::new();
         for rel in release_names.into_iter() {
             // Determine namespace: provided hint or resolved per release
             let ns_for_rel =
@@ -121,29 +158,44 @@ impl HelmManager {
                 cmd.arg("--kube-context").arg(&context_name);
             }
 
-            match cmd.output().await {
-                Ok(out) if out.status.success() => results.push(Ok(rel)),
-                Ok(out) => {
-                    let stderr = String::from_utf8_lossy(&out.stderr).to_string();
-                    let stdout = String::from_utf8_lossy(&out.stdout).to_string();
-                    let msg = if !stderr.trim().is_empty() { stderr } else { stdout };
-                    results.push(Err(format!("helm uninstall failed: {}", msg.trim())));
-                }
-                Err(e) => results.push(Err(format!("failed to run helm uninstall: {}", e))),
-            }
+            cmd.arg("--debug");
+
+            let job: HelmJobItem = Self::start_job(
+                &app_handle,
+                &context_name,
+                ns_for_rel.as_deref().unwrap_or_default(),
+                &rel,
+                "uninstall",
+                cmd,
+                None,
+            )
+            .await;
+            jobs.push(job);
         }
 
-        Ok(results)
+        Ok(jobs)
     }
 
     pub async fn get_values(
         context_name: String,
         namespace: Option<String>,
         release_name: String,
+        all: bool,
     ) -> Result<String, String> {
-        let helm_bin = Self::resolve_helm_bin()
-            .await
-            .ok_or_else(|| "Helm CLI is not available; get values requires Helm".to_string())?;
+        // Read the release storage directly; the CLI is only a fallback (e.g. SQL storage driver)
+        let native_err: String =
+            match Self::native_release(&context_name, namespace.as_deref(), &release_name, None)
+                .await
+            {
+                Ok(release) => {
+                    let values: Value =
+                        if all { HelmStorage::computed_values(&release) } else { release.config };
+                    return serde_yaml::to_string(&values)
+                        .map_err(|e| format!("Failed to encode values to YAML: {}", e));
+                }
+                Err(e) => e,
+            };
+        let helm_bin = Self::resolve_helm_bin().await.ok_or(native_err)?;
 
         // Resolve namespace if not provided
         let ns_hint = namespace.as_ref().map(|s| s.trim()).and_then(|s| {
@@ -174,7 +226,10 @@ impl HelmManager {
         };
 
         let mut cmd = Command::new(&helm_bin);
-        cmd.arg("get").arg("values").arg(&release_name).arg("--all").arg("-o").arg("yaml");
+        cmd.arg("get").arg("values").arg(&release_name).arg("-o").arg("yaml");
+        if all {
+            cmd.arg("--all");
+        }
         if let Some(ns) = ns_for_rel.as_ref() {
             cmd.arg("--namespace").arg(ns);
         }
@@ -197,9 +252,18 @@ impl HelmManager {
         namespace: Option<String>,
         release_name: String,
     ) -> Result<Vec<Value>, String> {
-        let helm_bin = Self::resolve_helm_bin()
-            .await
-            .ok_or_else(|| "Helm CLI is not available; history requires Helm".to_string())?;
+        let client = K8sClient::for_context(&context_name).await?;
+        let native_err: String = match HelmStorage::revisions(
+            client,
+            Self::namespace_hint(namespace.as_deref()),
+            &release_name,
+        )
+        .await
+        {
+            Ok(releases) => return Ok(releases.iter().map(HelmStorage::history_entry).collect()),
+            Err(e) => e,
+        };
+        let helm_bin = Self::resolve_helm_bin().await.ok_or(native_err)?;
 
         // Resolve namespace similarly
         let ns_hint = namespace.as_ref().map(|s| s.trim()).and_then(|s| {
@@ -250,150 +314,841 @@ impl HelmManager {
         }
     }
 
-    pub async fn upgrade_release(
+    pub async fn get_manifest(
         context_name: String,
         namespace: Option<String>,
         release_name: String,
-        chart: Option<String>,
-        values: Option<Value>,
-        reuse_values: bool,
-        version: Option<String>,
+        revision: Option<i32>,
     ) -> Result<String, String> {
-        use tokio::fs::File;
-        use tokio::io::AsyncWriteExt;
+        let native_err: String = match Self::native_release(
+            &context_name,
+            namespace.as_deref(),
+            &release_name,
+            revision,
+        )
+        .await
+        {
+            Ok(release) => return Ok(release.manifest),
+            Err(e) => e,
+        };
+        let helm_bin = Self::resolve_helm_bin().await.ok_or(native_err)?;
+
+        let mut cmd = Command::new(&helm_bin);
+        cmd.arg("get").arg("manifest").arg(&release_name);
+        if let Some(rev) = revision {
+            cmd.arg("--revision").arg(rev.to_string());
+        }
+        if let Some(ns) = Self::namespace_hint(namespace.as_deref()) {
+            cmd.arg("--namespace").arg(ns);
+        }
+        if !context_name.trim().is_empty() {
+            cmd.arg("--kube-context").arg(&context_name);
+        }
+
+        match cmd.output().await {
+            Ok(out) if out.status.success() => Ok(String::from_utf8_lossy(&out.stdout).to_string()),
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                Err(format!("helm get manifest failed: {}", stderr.trim()))
+            }
+            Err(e) => Err(format!("failed to run helm get manifest: {}", e)),
+        }
+    }
+
+    pub async fn get_release(
+        context_name: String,
+        namespace: Option<String>,
+        release_name: String,
+        revision: Option<i32>,
+    ) -> Result<HelmReleaseDetails, String> {
+        let release: HelmRelease =
+            Self::native_release(&context_name, namespace.as_deref(), &release_name, revision)
+                .await?;
+        Ok(HelmStorage::details(release))
+    }
 
+    /// Every object the release's manifest renders, resolved in the cluster with its
+    /// readiness and any drift from the rendered spec.
+    pub async fn release_resources(
+        context_name: String,
+        namespace: Option<String>,
+        release_name: String,
+    ) -> Result<Vec<HelmReleaseResource>, String> {
+        let client = K8sClient::for_context(&context_name).await?;
+        let release: HelmRelease = HelmStorage::release(
+            client.clone(),
+            Self::namespace_hint(namespace.as_deref()),
+            &release_name,
+            None,
+        )
+        .await?;
+        Ok(HelmReleaseResources::inventory(client, &release).await)
+    }
+
+    /// Start `helm upgrade` as a tracked job once the chart is resolved and the values
+    /// pass the chart schema.
+    pub async fn upgrade_release(
+        app_handle: AppHandle,
+        args: UpgradeArgs,
+    ) -> Result<HelmJobItem, String> {
         let helm_bin = Self::resolve_helm_bin()
             .await
             .ok_or_else(|| "Helm CLI is not available; upgrade requires Helm".to_string())?;
-
-        // Resolve namespace and current chart
-        let ns_hint = namespace.as_ref().map(|s| s.trim()).and_then(|s| {
-            let lower = s.to_lowercase();
-            let invalid = ["*", "all", "all namespaces", "all_namespaces"];
-            if s.is_empty() || invalid.contains(&lower.as_str()) {
-                None
-            } else {
-                Some(s)
-            }
-        });
-        let mut target_ns: Option<String> = ns_hint.map(|s| s.to_string());
-        let mut current_chart_name: Option<String> = None;
-        if target_ns.is_none() || chart.is_none() {
-            if let Ok(items) = Self::helm_cli_list_releases(&context_name, None).await {
-                for item in items.into_iter() {
-                    let n = item.get("name").and_then(|x| x.as_str()).unwrap_or("");
-                    if n == release_name {
-                        if target_ns.is_none() {
-                            target_ns = item
-                                .get("namespace")
-                                .and_then(|x| x.as_str())
-                                .map(|s| s.to_string());
-                        }
-                        if current_chart_name.is_none() {
-                            if let Some(c) = item.get("chart").and_then(|x| x.as_str()) {
-                                // chart like "fluent-bit-0.21.6" => name before last dash
-                                let parts: Vec<&str> = c.split('-').collect();
-                                if parts.len() > 1 {
-                                    current_chart_name = Some(parts[..parts.len() - 1].join("-"));
-                                } else {
-                                    current_chart_name = Some(c.to_string());
-                                }
-                            }
-                        }
-                        break;
+        let PreparedUpgrade {
+            mut cmd,
+            values_file: tmp_path,
+            chart,
+            namespace,
+        } = Self::build_upgrade_command(&helm_bin, &args).await?;
+
+        // Check values against the chart schema first so the UI gets every violation at once
+        let warnings: Vec<String> =
+            match Self::validate_upgrade_values(&helm_bin, &args, &chart, namespace.as_deref())
+                .await
+            {
+                Ok(warnings) => warnings,
+                Err(e) => {
+                    if let Some(p) = tmp_path.as_ref() {
+                        let _ = tokio::fs::remove_file(p).await;
                     }
+                    return Err(e);
                 }
-            }
+            };
+        cmd.arg("--debug");
+
+        let mut job: HelmJobItem = Self::start_job(
+            &app_handle,
+            &args.context_name,
+            namespace.as_deref().unwrap_or_default(),
+            &args.release_name,
+            "upgrade",
+            cmd,
+            tmp_path,
+        )
+        .await;
+        if !warnings.is_empty() {
+            app_handle.state::<HelmJobManager>().add_warnings(&job.job_id, &warnings).await;
+            job.warnings = warnings;
         }
+        Ok(job)
+    }
 
-        let chart_ref = chart.or(current_chart_name).ok_or_else(|| {
-            "Missing chart reference; please provide chart (e.g., repo/chart) for upgrade"
-                .to_string()
-        })?;
+    pub async fn diff_revisions(
+        context_name: String,
+        namespace: Option<String>,
+        release_name: String,
+        from_revision: i32,
+        to_revision: Option<i32>,
+    ) -> Result<HelmReleaseDiff, String> {
+        let client = K8sClient::for_context(&context_name).await?;
+        let releases: Vec<HelmRelease> = HelmStorage::revisions(
+            client,
+            Self::namespace_hint(namespace.as_deref()),
+            &release_name,
+        )
+        .await?;
+        let find = |rev: i32| -> Result<&HelmRelease, String> {
+            releases
+                .iter()
+                .find(|r| r.version == rev)
+                .ok_or_else(|| format!("revision {} of release {} not found", rev, release_name))
+        };
+        let from: &HelmRelease = find(from_revision)?;
+        let to: &HelmRelease = match to_revision {
+            Some(rev) => find(rev)?,
+            None => releases.last().ok_or_else(|| format!("release {} not found", release_name))?,
+        };
 
-        let mut chart_arg = chart_ref.clone();
-        let needs_resolution = !chart_arg.starts_with("http://")
-            && !chart_arg.starts_with("https://")
-            && !chart_arg.starts_with("oci://")
-            && !chart_arg.contains('/');
-        if needs_resolution {
-            // Resolve bare chart name via user's Helm repos only; do not use any hard-coded repositories.
-            match Self::helm_search_repo_chart(&helm_bin, &chart_arg, version.as_ref()).await {
-                Ok(Some(repo_chart)) => {
-                    chart_arg = repo_chart;
-                }
-                Ok(None) => {
-                    return Err(format!(
-                        "Unable to resolve chart '{}'. Please specify repo/chart (e.g., metrics-server/metrics-server) or add the repo via 'helm repo add'.",
-                        chart_arg
-                    ));
-                }
-                Err(e) => return Err(e),
-            }
+        Ok(HelmDiff::diff(
+            format!("revision {}", from.version),
+            format!("revision {}", to.version),
+            &from.manifest,
+            &from.config,
+            &to.manifest,
+            &to.config,
+        ))
+    }
+
+    // Render the upgrade with `helm upgrade --dry-run` and diff it against the deployed revision.
+    pub async fn diff_upgrade(
+        context_name: String,
+        namespace: Option<String>,
+        release_name: String,
+        chart: Option<String>,
+        values: Option<Value>,
+        reuse_values: bool,
+        version: Option<String>,
+    ) -> Result<HelmReleaseDiff, String> {
+        let helm_bin = Self::resolve_helm_bin()
+            .await
+            .ok_or_else(|| "Helm CLI is not available; upgrade diff requires Helm".to_string())?;
+        let current: HelmRelease =
+            Self::native_release(&context_name, namespace.as_deref(), &release_name, None).await?;
+        let args = UpgradeArgs {
+            context_name,
+            // The live release tells us where it is installed
+            namespace: Some(current.namespace.clone()),
+            release_name,
+            chart,
+            values,
+            reuse_values,
+            version,
+        };
+        let PreparedUpgrade {
+            mut cmd,
+            values_file: tmp_path,
+            ..
+        } = Self::build_upgrade_command(&helm_bin, &args).await?;
+        cmd.arg("--dry-run").arg("-o").arg("json");
+
+        let out = cmd.output().await;
+        if let Some(p) = tmp_path.as_ref() {
+            let _ = tokio::fs::remove_file(p).await;
         }
+        let out = out.map_err(|e| format!("Failed to run helm upgrade --dry-run: {}", e))?;
+        if !out.status.success() {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            return Err(format!("helm upgrade --dry-run failed: {}", stderr.trim()));
+        }
+        let proposed: HelmRelease = serde_json::from_slice(&out.stdout)
+            .map_err(|e| format!("Failed to parse helm upgrade --dry-run json: {}", e))?;
+
+        Ok(HelmDiff::diff(
+            format!("revision {}", current.version),
+            "proposed upgrade".to_string(),
+            &current.manifest,
+            &current.config,
+            &proposed.manifest,
+            &proposed.config,
+        ))
+    }
+
+    // Resolve namespace and chart for an upgrade and build the `helm upgrade` command.
+    async fn build_upgrade_command(
+        helm_bin: &str,
+        args: &UpgradeArgs,
+    ) -> Result<PreparedUpgrade, String> {
+        let ns_hint: Option<&str> = Self::namespace_hint(args.namespace.as_deref());
+        let explicit_chart: Option<&str> =
+            args.chart.as_deref().map(|c| c.trim()).filter(|c| !c.is_empty());
+        let (target_ns, chart_arg): (Option<String>, String) = match explicit_chart {
+            Some(chart) => {
+                let is_reference: bool = chart.starts_with("http://")
+                    || chart.starts_with("https://")
+                    || chart.starts_with("oci://")
+                    || chart.contains('/');
+                let chart_arg: String = if is_reference {
+                    chart.to_string()
+                } else {
+                    Self::resolve_chart_name(helm_bin, chart, args.version.as_deref()).await?
+                };
+                let target_ns: Option<String> = match ns_hint {
+                    Some(ns) => Some(ns.to_string()),
+                    None => Self::deployed_chart(&args.context_name, None, &args.release_name)
+                        .await
+                        .ok()
+                        .map(|(ns, _)| ns),
+                };
+                (target_ns, chart_arg)
+            }
+            // Upgrade from the chart the release was installed from
+            None => {
+                let resolution: HelmChartResolution = Self::upgrade_chart_resolution(
+                    helm_bin,
+                    &args.context_name,
+                    ns_hint,
+                    &args.release_name,
+                )
+                .await?;
+                let chart_arg: String = resolution.chart.clone().ok_or_else(|| {
+                    Self::unresolved_chart_error(&resolution.chart_name, &resolution.candidates)
+                })?;
+                (Some(resolution.namespace), chart_arg)
+            }
+        };
 
         // Prepare command
-        let mut cmd = Command::new(&helm_bin);
+        let mut cmd = Command::new(helm_bin);
         cmd.arg("upgrade");
-        cmd.arg(&release_name);
+        cmd.arg(&args.release_name);
         cmd.arg(&chart_arg);
         if let Some(ns) = target_ns.as_ref() {
             cmd.arg("--namespace").arg(ns);
         }
-        if !context_name.trim().is_empty() {
-            cmd.arg("--kube-context").arg(&context_name);
+        if !args.context_name.trim().is_empty() {
+            cmd.arg("--kube-context").arg(&args.context_name);
         }
-        if reuse_values {
+        if args.reuse_values {
             cmd.arg("--reuse-values");
         }
-        if let Some(ver) = version.as_ref() {
+        if let Some(ver) = args.version.as_ref() {
             if !ver.trim().is_empty() {
                 cmd.arg("--version").arg(ver);
             }
         }
 
         // If values provided, write to a temp file and use -f
-        let mut tmp_path: Option<std::path::PathBuf> = None;
-        if let Some(v) = values.as_ref() {
-            let sanitized_v = Self::expand_dotted_keys(v);
-            let yaml_text = serde_yaml::to_string(&sanitized_v)
-                .map_err(|e| format!("Failed to encode values to YAML: {}", e))?;
-            let dir = std::env::temp_dir();
-            let file_path = dir.join(format!("kumate-helm-values-{}.yaml", release_name));
-            let mut file = File::create(&file_path)
-                .await
-                .map_err(|e| format!("Failed to create temp values file: {}", e))?;
-            file.write_all(yaml_text.as_bytes())
-                .await
-                .map_err(|e| format!("Failed to write temp values file: {}", e))?;
-            tmp_path = Some(file_path.clone());
-            cmd.arg("-f").arg(file_path);
+        let mut tmp_path: Option<PathBuf> = None;
+        if let Some(v) = args.values.as_ref() {
+            let file_path: PathBuf = Self::write_values_file(&args.release_name, v).await?;
+            cmd.arg("-f").arg(&file_path);
+            tmp_path = Some(file_path);
         }
 
-        println!("Running helm upgrade command: {:?}", cmd);
+        Ok(PreparedUpgrade {
+            cmd,
+            values_file: tmp_path,
+            chart: chart_arg,
+            namespace: target_ns,
+        })
+    }
 
-        let out = cmd.output().await.map_err(|e| format!("Failed to run helm upgrade: {}", e))?;
-        if out.status.success() {
-            // Cleanup temp file
-            if let Some(p) = tmp_path.as_ref() {
-                let _ = tokio::fs::remove_file(p).await;
+    // Validate the values the upgraded release would end up with against the target
+    // chart's values.schema.json. Charts without a schema are not checked; a chart that
+    // cannot be fetched here is left to Helm and returned as a warning.
+    async fn validate_upgrade_values(
+        helm_bin: &str,
+        args: &UpgradeArgs,
+        chart: &str,
+        namespace: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        let Some(values) = args.values.as_ref() else {
+            return Ok(vec![]);
+        };
+        let files: ChartFiles =
+            match Self::fetch_chart_files(helm_bin, chart, args.version.as_deref()).await {
+                Ok(files) => files,
+                Err(e) => {
+                    return Ok(vec![format!(
+                        "Values were not checked against the chart schema: {}",
+                        e
+                    )])
+                }
+            };
+        let Some(schema) = files.schema()? else {
+            return Ok(vec![]);
+        };
+
+        let mut effective: Value = files.values()?;
+        if args.reuse_values {
+            let current: Result<HelmRelease, String> =
+                Self::native_release(&args.context_name, namespace, &args.release_name, None).await;
+            if let Ok(current) = current {
+                HelmStorage::coalesce(&mut effective, &current.config);
             }
-            Ok("Upgrade successful".to_string())
+        }
+        HelmStorage::coalesce(&mut effective, &Self::expand_dotted_keys(values));
+
+        let errors: Vec<String> = HelmCharts::validate_values(&schema, &effective)?;
+        if errors.is_empty() {
+            Ok(vec![])
+        } else {
+            Err(format!("values do not match the chart schema:\n{}", errors.join("\n")))
+        }
+    }
+
+    /// Chart.yaml, default values, values schema, README and available versions of a
+    /// repo chart, `oci://` reference or local chart.
+    pub async fn show_chart(
+        chart: String,
+        version: Option<String>,
+    ) -> Result<HelmChartInfo, String> {
+        let helm_bin = Self::resolve_helm_bin().await.ok_or_else(|| {
+            "Helm CLI is not available; showing a chart requires Helm".to_string()
+        })?;
+        let (chart_arg, is_local): (String, bool) =
+            Self::resolve_install_chart(&helm_bin, chart.trim(), version.as_ref()).await?;
+        let files: ChartFiles = Self::fetch_chart_files(
+            &helm_bin,
+            &chart_arg,
+            if is_local { None } else { version.as_deref() },
+        )
+        .await?;
+        let metadata: HelmChartMetadata = files.metadata()?;
+
+        let mut warnings: Vec<String> = Vec::new();
+        let mut versions: Vec<String> = if is_local {
+            vec![]
+        } else if chart_arg.starts_with("oci://") {
+            let registry_config: Option<PathBuf> = Self::registry_config_path(&helm_bin).await;
+            HelmCharts::oci_versions(&chart_arg, registry_config.as_deref()).await.unwrap_or_else(
+                |e| {
+                    warnings.push(format!("Failed to list versions of {}: {}", chart_arg, e));
+                    vec![]
+                },
+            )
         } else {
+            Self::repo_chart_versions(&helm_bin, &chart_arg).await
+        };
+        if versions.is_empty() {
+            versions.push(metadata.version.clone());
+        }
+        HelmCharts::sort_versions(&mut versions);
+
+        Ok(HelmChartInfo {
+            chart: chart_arg,
+            values: files.values()?,
+            schema: files.schema()?,
+            values_yaml: files.values_yaml.unwrap_or_default(),
+            readme: files.readme,
+            metadata,
+            versions,
+            warnings,
+        })
+    }
+
+    // Read a local chart directly; anything else is downloaded with `helm pull` first.
+    async fn fetch_chart_files(
+        helm_bin: &str,
+        chart: &str,
+        version: Option<&str>,
+    ) -> Result<ChartFiles, String> {
+        let path: &Path = Path::new(chart);
+        if path.is_dir() {
+            return HelmCharts::read_dir(path);
+        }
+        if path.is_file() {
+            return HelmCharts::read_archive(path);
+        }
+
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("kumate-helm-chart-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| format!("Failed to create temp chart directory: {}", e))?;
+        let mut cmd = Command::new(helm_bin);
+        cmd.arg("pull").arg(chart).arg("--destination").arg(&dir);
+        if let Some(ver) = version.filter(|v| !v.trim().is_empty()) {
+            cmd.arg("--version").arg(ver);
+        }
+        let result: Result<ChartFiles, String> = match cmd.output().await {
+            Ok(out) if out.status.success() => std::fs::read_dir(&dir)
+                .map_err(|e| format!("Failed to read pulled chart: {}", e))
+                .and_then(|entries| {
+                    entries
+                        .flatten()
+                        .map(|e| e.path())
+                        .find(|p| p.extension().is_some_and(|ext| ext == "tgz"))
+                        .ok_or_else(|| {
+                            format!("helm pull did not produce an archive for {}", chart)
+                        })
+                })
+                .and_then(|archive| HelmCharts::read_archive(&archive)),
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                Err(format!("helm pull failed: {}", stderr.trim()))
+            }
+            Err(e) => Err(format!("Failed to run helm pull: {}", e)),
+        };
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        result
+    }
+
+    // Versions of a "repo/chart" from the local repository index.
+    async fn repo_chart_versions(helm_bin: &str, chart: &str) -> Vec<String> {
+        let out = Command::new(helm_bin)
+            .arg("search")
+            .arg("repo")
+            .arg(chart)
+            .arg("--versions")
+            .arg("-o")
+            .arg("json")
+            .output()
+            .await;
+        let Ok(out) = out else {
+            return vec![];
+        };
+        if !out.status.success() {
+            return vec![];
+        }
+        let results: Value = serde_json::from_slice(&out.stdout).unwrap_or(Value::Null);
+        results
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|i| i.get("name").and_then(|n| n.as_str()) == Some(chart))
+                    .filter_map(|i| i.get("version").and_then(|v| v.as_str()))
+                    .map(|v| v.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Where `helm registry login` stores credentials, as reported by `helm env`.
+    async fn registry_config_path(helm_bin: &str) -> Option<PathBuf> {
+        let out = Command::new(helm_bin).arg("env").output().await.ok()?;
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        stdout
+            .lines()
+            .find_map(|l| l.strip_prefix("HELM_REGISTRY_CONFIG="))
+            .map(|v| PathBuf::from(v.trim().trim_matches('"')))
+            .filter(|p| p.is_file())
+    }
+
+    /// Install a new release from a repo chart, an `oci://` reference or a local chart
+    /// directory/archive, as a tracked job.
+    pub async fn install_release(
+        app_handle: AppHandle,
+        context_name: String,
+        namespace: String,
+        release_name: String,
+        chart: String,
+        options: HelmInstallOptions,
+    ) -> Result<HelmJobItem, String> {
+        let helm_bin = Self::resolve_helm_bin()
+            .await
+            .ok_or_else(|| "Helm CLI is not available; install requires Helm".to_string())?;
+        let namespace: String = Self::namespace_hint(Some(&namespace))
+            .ok_or_else(|| "A target namespace is required for install".to_string())?
+            .to_string();
+
+        let (chart_arg, is_local): (String, bool) =
+            Self::resolve_install_chart(&helm_bin, chart.trim(), options.version.as_ref()).await?;
+
+        let mut cmd = Command::new(&helm_bin);
+        cmd.arg("install").arg(&release_name).arg(&chart_arg);
+        cmd.arg("--namespace").arg(&namespace);
+        if !context_name.trim().is_empty() {
+            cmd.arg("--kube-context").arg(&context_name);
+        }
+        // --debug makes Helm report what it is waiting on, which is the progress we stream
+        cmd.arg("--debug");
+        if let Some(ver) = options.version.as_ref() {
+            // Local charts carry their own version
+            if !ver.trim().is_empty() && !is_local {
+                cmd.arg("--version").arg(ver);
+            }
+        }
+        if options.create_namespace {
+            cmd.arg("--create-namespace");
+        }
+        if options.atomic {
+            cmd.arg("--atomic");
+        }
+        if options.wait {
+            cmd.arg("--wait");
+        }
+        if let Some(secs) = options.timeout_seconds {
+            cmd.arg("--timeout").arg(format!("{}s", secs));
+        }
+        if let Some(desc) = options.description.as_ref() {
+            if !desc.trim().is_empty() {
+                cmd.arg("--description").arg(desc);
+            }
+        }
+
+        let mut tmp_path: Option<PathBuf> = None;
+        if let Some(v) = options.values.as_ref() {
+            let file_path: PathBuf = Self::write_values_file(&release_name, v).await?;
+            cmd.arg("-f").arg(&file_path);
+            tmp_path = Some(file_path);
+        }
+
+        Ok(Self::start_job(
+            &app_handle,
+            &context_name,
+            &namespace,
+            &release_name,
+            "install",
+            cmd,
+            tmp_path,
+        )
+        .await)
+    }
+
+    /// Render a chart locally with `helm template`; nothing is sent to the cluster.
+    pub async fn template(
+        release_name: String,
+        chart: String,
+        namespace: Option<String>,
+        options: HelmTemplateOptions,
+    ) -> Result<HelmTemplateResult, String> {
+        let helm_bin = Self::resolve_helm_bin()
+            .await
+            .ok_or_else(|| "Helm CLI is not available; rendering requires Helm".to_string())?;
+        let namespace: String =
+            Self::namespace_hint(namespace.as_deref()).unwrap_or("default").to_string();
+        let (chart_arg, is_local): (String, bool) =
+            Self::resolve_install_chart(&helm_bin, chart.trim(), options.version.as_ref()).await?;
+
+        let mut cmd = Command::new(&helm_bin);
+        cmd.arg("template").arg(&release_name).arg(&chart_arg);
+        cmd.arg("--namespace").arg(&namespace);
+        if let Some(ver) = options.version.as_ref() {
+            if !ver.trim().is_empty() && !is_local {
+                cmd.arg("--version").arg(ver);
+            }
+        }
+        if options.include_crds {
+            cmd.arg("--include-crds");
+        }
+        if let Some(kube_version) = options.kube_version.as_ref() {
+            if !kube_version.trim().is_empty() {
+                cmd.arg("--kube-version").arg(kube_version);
+            }
+        }
+        for api_version in options.api_versions.iter() {
+            cmd.arg("--api-versions").arg(api_version);
+        }
+        let mut tmp_path: Option<PathBuf> = None;
+        if let Some(v) = options.values.as_ref() {
+            let file_path: PathBuf = Self::write_values_file(&release_name, v).await?;
+            cmd.arg("-f").arg(&file_path);
+            tmp_path = Some(file_path);
+        }
+
+        let out = cmd.output().await;
+        if let Some(p) = tmp_path.as_ref() {
+            let _ = tokio::fs::remove_file(p).await;
+        }
+        let out = out.map_err(|e| format!("Failed to run helm template: {}", e))?;
+        if !out.status.success() {
             let stderr = String::from_utf8_lossy(&out.stderr);
-            if let Some(p) = tmp_path.as_ref() {
+            return Err(format!("helm template failed: {}", stderr.trim()));
+        }
+        let manifest: String = String::from_utf8_lossy(&out.stdout).to_string();
+
+        let mut objects: Vec<HelmRenderedObject> = Vec::new();
+        for (source, doc) in HelmDiff::sourced_documents(&manifest) {
+            let object: Value = serde_yaml::from_str(&doc).map_err(|e| {
+                format!("{} rendered invalid YAML: {}", source.as_deref().unwrap_or("chart"), e)
+            })?;
+            let field = |path: &[&str]| -> Option<String> {
+                let mut cur: &Value = &object;
+                for p in path {
+                    cur = cur.get(*p)?;
+                }
+                cur.as_str().map(|s| s.to_string())
+            };
+            // Templates that render to comments or empty lists
+            let (Some(kind), Some(name)) = (field(&["kind"]), field(&["metadata", "name"])) else {
+                continue;
+            };
+            objects.push(HelmRenderedObject {
+                api_version: field(&["apiVersion"]).unwrap_or_default(),
+                namespace: field(&["metadata", "namespace"]),
+                hook: field(&["metadata", "annotations", "helm.sh/hook"]),
+                kind,
+                name,
+                source,
+                yaml: doc,
+                object,
+            });
+        }
+
+        Ok(HelmTemplateResult {
+            chart: chart_arg,
+            release_name,
+            namespace,
+            manifest,
+            objects,
+        })
+    }
+
+    // Local paths and oci:// or http(s) references are passed to Helm as-is, "repo/chart"
+    // is used directly and bare chart names are looked up in the configured repositories.
+    // Returns the chart argument and whether it is a local chart.
+    async fn resolve_install_chart(
+        helm_bin: &str,
+        chart: &str,
+        version: Option<&String>,
+    ) -> Result<(String, bool), String> {
+        if chart.is_empty() {
+            return Err("Missing chart reference".to_string());
+        }
+        let expanded: PathBuf = match chart.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|h| h.join(rest)).unwrap_or_else(|| chart.into()),
+            None => PathBuf::from(chart),
+        };
+        if expanded.exists() {
+            let is_chart: bool = expanded.join("Chart.yaml").is_file()
+                || expanded.extension().is_some_and(|ext| ext == "tgz" || ext == "gz");
+            if !is_chart {
+                return Err(format!(
+                    "{} is neither a chart directory (with Chart.yaml) nor a packaged .tgz chart",
+                    expanded.display()
+                ));
+            }
+            return Ok((expanded.to_string_lossy().to_string(), true));
+        }
+        if chart.starts_with("oci://")
+            || chart.starts_with("http://")
+            || chart.starts_with("https://")
+        {
+            return Ok((chart.to_string(), false));
+        }
+        if chart.contains('/') {
+            return Ok((chart.to_string(), false));
+        }
+        let repo_chart: String =
+            Self::resolve_chart_name(helm_bin, chart, version.map(|v| v.as_str())).await?;
+        Ok((repo_chart, false))
+    }
+
+    async fn write_values_file(release_name: &str, values: &Value) -> Result<PathBuf, String> {
+        use tokio::fs::File;
+
+        let sanitized_v = Self::expand_dotted_keys(values);
+        let yaml_text = serde_yaml::to_string(&sanitized_v)
+            .map_err(|e| format!("Failed to encode values to YAML: {}", e))?;
+        let dir = std::env::temp_dir();
+        // Unique per call: jobs outlive their command, and each removes its own file when done
+        let file_path =
+            dir.join(format!("kumate-helm-values-{}-{}.yaml", release_name, uuid::Uuid::new_v4()));
+        let mut file = File::create(&file_path)
+            .await
+            .map_err(|e| format!("Failed to create temp values file: {}", e))?;
+        file.write_all(yaml_text.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write temp values file: {}", e))?;
+        Ok(file_path)
+    }
+
+    // Run a Helm command as a tracked job. Output is streamed on
+    // `k8s://{context}/helm/{operation}/{namespace}/{release}` and the temp values file,
+    // if any, is removed once Helm exits.
+    async fn start_job(
+        app_handle: &AppHandle,
+        context_name: &str,
+        namespace: &str,
+        release_name: &str,
+        operation: &str,
+        cmd: Command,
+        values_file: Option<PathBuf>,
+    ) -> HelmJobItem {
+        let id: String = uuid::Uuid::new_v4().to_string();
+        let event_name: String =
+            format!("k8s://{}/helm/{}/{}/{}", context_name, operation, namespace, release_name);
+        let cancel_token: CancellationToken = CancellationToken::new();
+        let item: HelmJobItem = app_handle
+            .state::<HelmJobManager>()
+            .insert(
+                id.clone(),
+                HelmJob {
+                    cancel_token: Some(cancel_token.clone()),
+                    context: context_name.to_string(),
+                    namespace: namespace.to_string(),
+                    release: release_name.to_string(),
+                    operation: operation.to_string(),
+                    event_name: event_name.clone(),
+                    started_at: Utc::now().to_rfc3339(),
+                    finished_at: None,
+                    state: HelmJobState::Running,
+                    error: None,
+                    warnings: vec![],
+                },
+            )
+            .await;
+
+        let app: AppHandle = app_handle.clone();
+        let operation: String = operation.to_string();
+        tokio::spawn(async move {
+            let result: Result<String, String> =
+                Self::run_streaming(&app, &event_name, cmd, cancel_token.clone()).await;
+            if let Some(p) = values_file.as_ref() {
                 let _ = tokio::fs::remove_file(p).await;
             }
-            Err(format!("helm upgrade failed: {}", stderr.trim()))
+            let jobs = app.state::<HelmJobManager>();
+            match result {
+                Ok(_) => jobs.finish(&id, HelmJobState::Succeeded, None).await,
+                Err(_) if cancel_token.is_cancelled() => {
+                    jobs.finish(&id, HelmJobState::Cancelled, None).await
+                }
+                Err(e) => {
+                    let error: String = format!("helm {} failed: {}", operation, e);
+                    jobs.finish(&id, HelmJobState::Failed, Some(error)).await
+                }
+            }
+        });
+        item
+    }
+
+    // Run a Helm command and emit its output line by line. Returns stdout on success;
+    // on failure the error is Helm's "Error:" line, or the last lines of stderr.
+    // Cancelling kills the process.
+    async fn run_streaming(
+        app_handle: &AppHandle,
+        event_name: &str,
+        mut cmd: Command,
+        cancel: CancellationToken,
+    ) -> Result<String, String> {
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+        let mut child = cmd.spawn().map_err(|e| format!("Failed to run helm: {}", e))?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let read_stdout = async {
+            let mut collected: String = String::new();
+            if let Some(out) = stdout {
+                let mut lines = BufReader::new(out).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    Self::emit_helm_output(app_handle, event_name, "HELM_STDOUT", &line);
+                    collected.push_str(&line);
+                    collected.push('\n');
+                }
+            }
+            collected
+        };
+        let read_stderr = async {
+            let mut tail: Vec<String> = Vec::new();
+            if let Some(err) = stderr {
+                let mut lines = BufReader::new(err).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    Self::emit_helm_output(app_handle, event_name, "HELM_STDERR", &line);
+                    tail.push(line);
+                    if tail.len() > 20 {
+                        tail.remove(0);
+                    }
+                }
+            }
+            tail
+        };
+        // Owns the child so dropping it on cancel kills Helm
+        let run = async move {
+            let (stdout_text, stderr_tail) = tokio::join!(read_stdout, read_stderr);
+            let status = child.wait().await;
+            (stdout_text, stderr_tail, status)
+        };
+        let (stdout_text, stderr_tail, status) = tokio::select! {
+            _ = cancel.cancelled() => {
+                let message: String = "cancelled; the release may be left pending until it is rolled back or upgraded again".to_string();
+                Self::emit_helm_output(app_handle, event_name, "HELM_CANCELLED", &message);
+                return Err(message);
+            }
+            finished = run => finished,
+        };
+
+        let status = status.map_err(|e| format!("Failed to wait for helm: {}", e))?;
+        if status.success() {
+            Self::emit_helm_output(app_handle, event_name, "HELM_COMPLETED", "");
+            return Ok(stdout_text);
         }
+        let message: String = stderr_tail
+            .iter()
+            .find(|l| l.starts_with("Error:"))
+            .map(|l| l.trim_start_matches("Error:").trim().to_string())
+            .unwrap_or_else(|| stderr_tail.join("\n").trim().to_string());
+        Self::emit_helm_output(app_handle, event_name, "HELM_FAILED", &message);
+        Err(message)
+    }
+
+    fn emit_helm_output(app_handle: &AppHandle, event_name: &str, kind: &str, line: &str) {
+        let event: Value = serde_json::json!({
+            "type": kind,
+            "line": line,
+            "timestamp": Utc::now().to_rfc3339(),
+        });
+        let _ = app_handle.emit(event_name, event);
     }
 
     pub async fn rollback_release(
+        app_handle: AppHandle,
         context_name: String,
         namespace: Option<String>,
         release_name: String,
         revision: i32,
-    ) -> Result<String, String> {
+    ) -> Result<HelmJobItem, String> {
         let helm_bin = Self::resolve_helm_bin()
             .await
             .ok_or_else(|| "Helm CLI is not available; rollback requires Helm".to_string())?;
@@ -431,13 +1186,18 @@ impl HelmManager {
             cmd.arg("--kube-context").arg(&context_name);
         }
 
-        let out = cmd.output().await.map_err(|e| format!("Failed to run helm rollback: {}", e))?;
-        if out.status.success() {
-            Ok("Rollback successful".to_string())
-        } else {
-            let stderr = String::from_utf8_lossy(&out.stderr);
-            Err(format!("helm rollback failed: {}", stderr.trim()))
-        }
+        cmd.arg("--debug");
+
+        Ok(Self::start_job(
+            &app_handle,
+            &context_name,
+            target_ns.as_deref().unwrap_or_default(),
+            &release_name,
+            "rollback",
+            cmd,
+            None,
+        )
+        .await)
     }
 
     pub async fn list_charts(_context_name: String) -> Result<Vec<Value>, String> {
@@ -483,80 +1243,376 @@ impl HelmManager {
         Ok(results)
     }
 
-    // Try to resolve a chart reference using the user's local Helm repositories.
-    // Returns a repo/chart string like "metrics-server/metrics-server" when found.
-    async fn helm_search_repo_chart(
+    pub async fn list_repositories() -> Result<Vec<Value>, String> {
+        let helm_bin = Self::resolve_helm_bin()
+            .await
+            .ok_or_else(|| "Helm CLI is not available; repositories require Helm".to_string())?;
+
+        let out = Command::new(&helm_bin)
+            .arg("repo")
+            .arg("list")
+            .arg("-o")
+            .arg("json")
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run 'helm repo list': {}", e))?;
+        if !out.status.success() {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            // Helm treats an empty repository list as an error
+            if stderr.contains("no repositories") {
+                return Ok(vec![]);
+            }
+            return Err(format!("helm repo list failed: {}", stderr.trim()));
+        }
+        let v: Value = serde_json::from_slice(&out.stdout)
+            .map_err(|e| format!("Failed to parse 'helm repo list' JSON: {}", e))?;
+        Ok(v.as_array().cloned().unwrap_or_default())
+    }
+
+    pub async fn add_repository(
+        repo_name: String,
+        url: String,
+        options: HelmRepoAddOptions,
+    ) -> Result<String, String> {
+        let helm_bin = Self::resolve_helm_bin().await.ok_or_else(|| {
+            "Helm CLI is not available; adding a repository requires Helm".to_string()
+        })?;
+
+        let mut cmd = Command::new(&helm_bin);
+        cmd.arg("repo").arg("add").arg(repo_name.trim()).arg(url.trim());
+        if let Some(user) = options.username.as_ref().filter(|u| !u.trim().is_empty()) {
+            cmd.arg("--username").arg(user);
+        }
+        // The password goes through stdin so it never shows up in the process list
+        let password: Option<&String> = options.password.as_ref().filter(|p| !p.is_empty());
+        if password.is_some() {
+            cmd.arg("--password-stdin");
+        }
+        for (flag, path) in [
+            ("--ca-file", &options.ca_file),
+            ("--cert-file", &options.cert_file),
+            ("--key-file", &options.key_file),
+        ] {
+            if let Some(p) = path.as_ref().filter(|p| !p.trim().is_empty()) {
+                cmd.arg(flag).arg(p);
+            }
+        }
+        if options.insecure_skip_tls_verify {
+            cmd.arg("--insecure-skip-tls-verify");
+        }
+        if options.pass_credentials {
+            cmd.arg("--pass-credentials");
+        }
+        if options.force_update {
+            cmd.arg("--force-update");
+        }
+
+        let out = Self::output_with_stdin(cmd, password.map(|p| p.as_str())).await?;
+        if out.status.success() {
+            Ok(format!("Repository {} added", repo_name.trim()))
+        } else {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            Err(format!("helm repo add failed: {}", stderr.trim()))
+        }
+    }
+
+    pub async fn remove_repositories(
+        repo_names: Vec<String>,
+    ) -> Result<Vec<Result<String, String>>, String> {
+        let helm_bin = Self::resolve_helm_bin().await.ok_or_else(|| {
+            "Helm CLI is not available; removing a repository requires Helm".to_string()
+        })?;
+
+        let mut results: Vec<Result<String, String>> = Vec::new();
+        for repo in repo_names.into_iter() {
+            match Command::new(&helm_bin).arg("repo").arg("remove").arg(&repo).output().await {
+                Ok(out) if out.status.success() => results.push(Ok(repo)),
+                Ok(out) => {
+                    let stderr = String::from_utf8_lossy(&out.stderr);
+                    results.push(Err(format!("helm repo remove failed: {}", stderr.trim())));
+                }
+                Err(e) => results.push(Err(format!("failed to run helm repo remove: {}", e))),
+            }
+        }
+        Ok(results)
+    }
+
+    // Refresh the index of the given repositories, or of all of them.
+    pub async fn update_repositories(repo_names: Option<Vec<String>>) -> Result<String, String> {
+        let helm_bin = Self::resolve_helm_bin().await.ok_or_else(|| {
+            "Helm CLI is not available; updating repositories requires Helm".to_string()
+        })?;
+
+        let mut cmd = Command::new(&helm_bin);
+        cmd.arg("repo").arg("update");
+        for repo in repo_names.unwrap_or_default() {
+            cmd.arg(repo);
+        }
+        let out =
+            cmd.output().await.map_err(|e| format!("Failed to run helm repo update: {}", e))?;
+        if out.status.success() {
+            Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+        } else {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            Err(format!("helm repo update failed: {}", stderr.trim()))
+        }
+    }
+
+    pub async fn registry_login(
+        registry: String,
+        username: String,
+        password: String,
+        insecure: bool,
+    ) -> Result<String, String> {
+        let helm_bin = Self::resolve_helm_bin()
+            .await
+            .ok_or_else(|| "Helm CLI is not available; registry login requires Helm".to_string())?;
+        // `helm registry login` expects a host, not an oci:// reference
+        let host: &str = registry.trim().trim_start_matches("oci://").trim_end_matches('/');
+
+        let mut cmd = Command::new(&helm_bin);
+        cmd.arg("registry").arg("login").arg(host);
+        cmd.arg("--username").arg(&username).arg("--password-stdin");
+        if insecure {
+            cmd.arg("--insecure");
+        }
+        let out = Self::output_with_stdin(cmd, Some(&password)).await?;
+        if out.status.success() {
+            Ok(format!("Logged in to {}", host))
+        } else {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            Err(format!("helm registry login failed: {}", stderr.trim()))
+        }
+    }
+
+    pub async fn registry_logout(registry: String) -> Result<String, String> {
+        let helm_bin = Self::resolve_helm_bin().await.ok_or_else(|| {
+            "Helm CLI is not available; registry logout requires Helm".to_string()
+        })?;
+        let host: &str = registry.trim().trim_start_matches("oci://").trim_end_matches('/');
+
+        let out = Command::new(&helm_bin)
+            .arg("registry")
+            .arg("logout")
+            .arg(host)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run helm registry logout: {}", e))?;
+        if out.status.success() {
+            Ok(format!("Logged out of {}", host))
+        } else {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            Err(format!("helm registry logout failed: {}", stderr.trim()))
+        }
+    }
+
+    async fn output_with_stdin(
+        mut cmd: Command,
+        input: Option<&str>,
+    ) -> Result<std::process::Output, String> {
+        cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+        let mut child = cmd.spawn().map_err(|e| format!("Failed to run helm: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            if let Some(input) = input {
+                stdin
+                    .write_all(input.as_bytes())
+                    .await
+                    .map_err(|e| format!("Failed to write to helm: {}", e))?;
+            }
+            // Dropping stdin closes it so Helm stops reading
+        }
+        child.wait_with_output().await.map_err(|e| format!("Failed to wait for helm: {}", e))
+    }
+
+    // Charts named exactly `chart_name` in the configured repositories, one entry per
+    // repository. `version` marks the repositories that serve that version.
+    async fn chart_candidates(
         helm_bin: &str,
         chart_name: &str,
-        version: Option<&String>,
-    ) -> Result<Option<String>, String> {
-        // Query local Helm repos for chart candidates
-        let mut cmd = Command::new(helm_bin);
-        cmd.arg("search").arg("repo").arg(chart_name).arg("-o").arg("json").arg("--versions");
-        let out =
-            cmd.output().await.map_err(|e| format!("Failed to run 'helm search repo': {}", e))?;
+        version: Option<&str>,
+    ) -> Result<Vec<HelmChartCandidate>, String> {
+        let out = Command::new(helm_bin)
+            .arg("search")
+            .arg("repo")
+            .arg(chart_name)
+            .arg("--versions")
+            .arg("-o")
+            .arg("json")
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run 'helm search repo': {}", e))?;
         if !out.status.success() {
-            // If search fails (e.g., no repos), just return None
-            return Ok(None);
+            // No repositories configured yet
+            return Ok(vec![]);
         }
-
-        let stdout = String::from_utf8_lossy(&out.stdout);
-        let results: Value = serde_json::from_str(stdout.trim())
+        let results: Value = serde_json::from_slice(&out.stdout)
             .map_err(|e| format!("Failed to parse 'helm search repo' JSON: {}", e))?;
-        let arr = match results.as_array() {
-            Some(a) => a,
-            None => return Ok(None),
-        };
 
-        // Choose best candidate based on scoring:
-        //  - Highest: exact pattern "<chart_name>/<chart_name>"
-        //  - Medium: name ends with "/<chart_name>"
-        //  - Low: name contains chart_name
-        // Within the best score tier, prefer matching version if provided, otherwise pick the first.
-        #[derive(Clone)]
-        struct Candidate {
-            name: String,
-            version: String,
-            score: i32,
-        }
-        let mut candidates: Vec<Candidate> = Vec::new();
-        for item in arr.iter() {
-            let name = item.get("name").and_then(|x| x.as_str()).unwrap_or("");
-            let ver = item.get("version").and_then(|x| x.as_str()).unwrap_or("");
-            let exact = name == format!("{}/{}", chart_name, chart_name);
-            let ends = name.ends_with(&format!("/{chart_name}"));
-            let contains = name.contains(chart_name);
-            let score = if exact {
-                3
-            } else if ends {
-                2
-            } else if contains {
-                1
-            } else {
-                0
-            };
-            if score > 0 {
-                candidates.push(Candidate {
-                    name: name.to_string(),
-                    version: ver.to_string(),
-                    score,
-                });
+        // Results are grouped per chart with the newest version first
+        let mut candidates: Vec<HelmChartCandidate> = Vec::new();
+        for item in results.as_array().cloned().unwrap_or_default().iter() {
+            let name: &str = item.get("name").and_then(|x| x.as_str()).unwrap_or("");
+            if name.rsplit('/').next() != Some(chart_name) {
+                continue;
+            }
+            let item_version: &str = item.get("version").and_then(|x| x.as_str()).unwrap_or("");
+            let matches_version: bool = version.is_some_and(|v| v == item_version);
+            match candidates.iter_mut().find(|c| c.chart == name) {
+                Some(existing) => existing.has_current_version |= matches_version,
+                None => candidates.push(HelmChartCandidate {
+                    chart: name.to_string(),
+                    latest_version: item_version.to_string(),
+                    app_version: item
+                        .get("app_version")
+                        .and_then(|x| x.as_str())
+                        .map(|x| x.to_string()),
+                    description: item
+                        .get("description")
+                        .and_then(|x| x.as_str())
+                        .map(|x| x.to_string()),
+                    has_current_version: matches_version,
+                }),
+            }
+        }
+        Ok(candidates)
+    }
+
+    // The single candidate left after preferring repositories that serve the wanted
+    // version and, when known, carry the same chart description. None when ambiguous.
+    fn pick_candidate(
+        candidates: &[HelmChartCandidate],
+        description: Option<&str>,
+    ) -> Option<String> {
+        let mut best: Vec<&HelmChartCandidate> = candidates.iter().collect();
+        if best.iter().any(|c| c.has_current_version) {
+            best.retain(|c| c.has_current_version);
+        }
+        if let Some(desc) = description.filter(|d| !d.trim().is_empty()) {
+            if best.len() > 1 && best.iter().any(|c| c.description.as_deref() == Some(desc)) {
+                best.retain(|c| c.description.as_deref() == Some(desc));
             }
         }
+        match best.as_slice() {
+            [only] => Some(only.chart.clone()),
+            _ => None,
+        }
+    }
+
+    // Resolve a bare chart name to "repo/chart" without guessing between repositories.
+    async fn resolve_chart_name(
+        helm_bin: &str,
+        chart_name: &str,
+        version: Option<&str>,
+    ) -> Result<String, String> {
+        let candidates: Vec<HelmChartCandidate> =
+            Self::chart_candidates(helm_bin, chart_name, version).await?;
+        Self::pick_candidate(&candidates, None)
+            .ok_or_else(|| Self::unresolved_chart_error(chart_name, &candidates))
+    }
+
+    fn unresolved_chart_error(chart_name: &str, candidates: &[HelmChartCandidate]) -> String {
         if candidates.is_empty() {
-            return Ok(None);
+            format!(
+                "Unable to resolve chart '{}'. Please specify repo/chart (e.g., metrics-server/metrics-server), an oci:// reference or a local chart path, or add its repository under Helm repositories.",
+                chart_name
+            )
+        } else {
+            let names: Vec<&str> = candidates.iter().map(|c| c.chart.as_str()).collect();
+            format!(
+                "Chart '{}' is available from several repositories ({}); please choose one",
+                chart_name,
+                names.join(", ")
+            )
         }
-        // Determine the highest score tier
-        let max_score = candidates.iter().map(|c| c.score).max().unwrap_or(1);
-        let best: Vec<Candidate> =
-            candidates.into_iter().filter(|c| c.score == max_score).collect();
-        if let Some(want_ver) = version {
-            if let Some(found) = best.iter().find(|c| &c.version == want_ver) {
-                return Ok(Some(found.name.clone()));
+    }
+
+    /// Work out which chart a release should be upgraded from, using the chart name,
+    /// version and description stored with the release.
+    pub async fn resolve_upgrade_chart(
+        context_name: String,
+        namespace: Option<String>,
+        release_name: String,
+    ) -> Result<HelmChartResolution, String> {
+        let helm_bin = Self::resolve_helm_bin()
+            .await
+            .ok_or_else(|| "Helm CLI is not available; upgrade requires Helm".to_string())?;
+        Self::upgrade_chart_resolution(
+            &helm_bin,
+            &context_name,
+            namespace.as_deref(),
+            &release_name,
+        )
+        .await
+    }
+
+    async fn upgrade_chart_resolution(
+        helm_bin: &str,
+        context_name: &str,
+        namespace: Option<&str>,
+        release_name: &str,
+    ) -> Result<HelmChartResolution, String> {
+        let (release_ns, metadata): (String, HelmChartMetadata) =
+            Self::deployed_chart(context_name, namespace, release_name).await?;
+        let candidates: Vec<HelmChartCandidate> =
+            Self::chart_candidates(helm_bin, &metadata.name, Some(&metadata.version)).await?;
+        let chart: Option<String> =
+            Self::pick_candidate(&candidates, metadata.description.as_deref());
+        Ok(HelmChartResolution {
+            namespace: release_ns,
+            chart_name: metadata.name,
+            current_version: metadata.version,
+            chart,
+            candidates,
+        })
+    }
+
+    // Namespace and chart metadata of the deployed revision. Releases the app can't decode
+    // (e.g. the SQL storage driver) fall back to the chart string printed by `helm list`.
+    async fn deployed_chart(
+        context_name: &str,
+        namespace: Option<&str>,
+        release_name: &str,
+    ) -> Result<(String, HelmChartMetadata), String> {
+        let native_err: String =
+            match Self::native_release(context_name, namespace, release_name, None).await {
+                Ok(release) => return Ok((release.namespace, release.chart.metadata)),
+                Err(e) => e,
+            };
+        let ns_hint: Option<&str> = Self::namespace_hint(namespace);
+        let items: Vec<Value> =
+            Self::helm_cli_list_releases(context_name, None).await.unwrap_or_default();
+        let item: &Value = items
+            .iter()
+            .find(|item| {
+                item.get("name").and_then(|x| x.as_str()) == Some(release_name)
+                    && ns_hint
+                        .is_none_or(|ns| item.get("namespace").and_then(|x| x.as_str()) == Some(ns))
+            })
+            .ok_or(native_err)?;
+        let release_ns: String =
+            item.get("namespace").and_then(|x| x.as_str()).unwrap_or_default().to_string();
+        let chart: &str = item.get("chart").and_then(|x| x.as_str()).unwrap_or_default();
+        let (name, version): (String, String) = Self::split_chart_version(chart);
+        Ok((
+            release_ns,
+            HelmChartMetadata {
+                name,
+                version,
+                ..Default::default()
+            },
+        ))
+    }
+
+    // "fluent-bit-0.21.6" => ("fluent-bit", "0.21.6"); the version starts at the first
+    // dash followed by valid semver, so prereleases like "app-1.2.3-rc.1" split correctly.
+    fn split_chart_version(chart: &str) -> (String, String) {
+        for (idx, _) in chart.match_indices('-') {
+            let rest: &str = &chart[idx + 1..];
+            if semver::Version::parse(rest).is_ok() {
+                return (chart[..idx].to_string(), rest.to_string());
             }
         }
-        // Fallback: pick the first from the best tier
-        Ok(best.first().map(|c| c.name.clone()))
+        (chart.to_string(), String::new())
     }
 
     pub async fn watch_releases(
@@ -668,6 +1724,34 @@ impl HelmManager {
     }
 
     // Internal helpers
+    fn namespace_hint(namespace: Option<&str>) -> Option<&str> {
+        namespace.map(|s| s.trim()).filter(|s| {
+            let lower = s.to_lowercase();
+            let invalid = ["*", "all", "all namespaces", "all_namespaces"];
+            !s.is_empty() && !invalid.contains(&lower.as_str())
+        })
+    }
+
+    async fn native_release(
+        context_name: &str,
+        namespace: Option<&str>,
+        release_name: &str,
+        revision: Option<i32>,
+    ) -> Result<HelmRelease, String> {
+        let client = K8sClient::for_context(context_name).await?;
+        HelmStorage::release(client, Self::namespace_hint(namespace), release_name, revision).await
+    }
+
+    // Chart details are only available in the encoded release payload, not in labels
+    fn add_release_details(obj: &mut Value, release: Result<HelmRelease, String>) {
+        if let Ok(release) = release {
+            let metadata = &release.chart.metadata;
+            obj["chart"] = Value::String(format!("{}-{}", metadata.name, metadata.version));
+            obj["app_version"] = Value::String(metadata.app_version.clone().unwrap_or_default());
+            obj["updated"] = Value::String(release.info.last_deployed.unwrap_or_default());
+        }
+    }
+
     fn is_helm_secret(s: &Secret) -> bool {
         let labels = s.metadata.labels.clone().unwrap_or_default();
         let owner = labels.get("owner").cloned().unwrap_or_default();
@@ -707,6 +1791,7 @@ impl HelmManager {
             "name": release_name,
             "namespace": namespace,
         });
+        Self::add_release_details(&mut obj, HelmStorage::decode_secret(s));
         obj
     }
 
@@ -733,6 +1818,7 @@ impl HelmManager {
             "name": release_name,
             "namespace": namespace,
         });
+        Self::add_release_details(&mut obj, HelmStorage::decode_config_map(c));
         obj
     }
 
@@ -0,0 +1,254 @@
+use crate::types::helm::HelmChartMetadata;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// The top-level files of a chart the app shows before install or upgrade.
+#[derive(Default)]
+pub struct ChartFiles {
+    pub chart_yaml: Option<String>,
+    pub values_yaml: Option<String>,
+    pub schema_json: Option<String>,
+    pub readme: Option<String>,
+}
+
+impl ChartFiles {
+    fn set(&mut self, file_name: &str, content: String) {
+        match file_name {
+            "Chart.yaml" => self.chart_yaml = Some(content),
+            "values.yaml" => self.values_yaml = Some(content),
+            "values.schema.json" => self.schema_json = Some(content),
+            // README.md is the common spelling but Helm accepts any case and extension
+            name if name.to_lowercase().starts_with("readme") && self.readme.is_none() => {
+                self.readme = Some(content)
+            }
+            _ => {}
+        }
+    }
+
+    pub fn metadata(&self) -> Result<HelmChartMetadata, String> {
+        let text: &String =
+            self.chart_yaml.as_ref().ok_or_else(|| "chart has no Chart.yaml".to_string())?;
+        serde_yaml::from_str(text).map_err(|e| format!("invalid Chart.yaml: {}", e))
+    }
+
+    pub fn values(&self) -> Result<Value, String> {
+        match self.values_yaml.as_ref() {
+            Some(text) if !text.trim().is_empty() => {
+                let v: Value = serde_yaml::from_str(text)
+                    .map_err(|e| format!("invalid values.yaml: {}", e))?;
+                Ok(if v.is_object() { v } else { Value::Object(serde_json::Map::new()) })
+            }
+            _ => Ok(Value::Object(serde_json::Map::new())),
+        }
+    }
+
+    pub fn schema(&self) -> Result<Option<Value>, String> {
+        match self.schema_json.as_ref() {
+            Some(text) => serde_json::from_str(text)
+                .map(Some)
+                .map_err(|e| format!("invalid values.schema.json: {}", e)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Reads packaged or unpacked charts and checks values against their schema.
+pub struct HelmCharts;
+
+impl HelmCharts {
+    /// Top-level files of a packaged chart (`<chart>/Chart.yaml`, ...); subcharts are skipped.
+    pub fn read_archive(path: &Path) -> Result<ChartFiles, String> {
+        let file: std::fs::File = std::fs::File::open(path)
+            .map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        let entries = archive
+            .entries()
+            .map_err(|e| format!("failed to read chart archive {}: {}", path.display(), e))?;
+
+        let mut files: ChartFiles = ChartFiles::default();
+        for entry in entries {
+            let mut entry = entry.map_err(|e| format!("failed to read chart archive: {}", e))?;
+            let entry_path: String = match entry.path() {
+                Ok(p) => p.to_string_lossy().to_string(),
+                Err(_) => continue,
+            };
+            let parts: Vec<&str> = entry_path.trim_start_matches("./").split('/').collect();
+            if parts.len() != 2 {
+                continue;
+            }
+            let file_name: String = parts[1].to_string();
+            let mut content: String = String::new();
+            if entry.read_to_string(&mut content).is_ok() {
+                files.set(&file_name, content);
+            }
+        }
+        Ok(files)
+    }
+
+    pub fn read_dir(path: &Path) -> Result<ChartFiles, String> {
+        let entries = std::fs::read_dir(path)
+            .map_err(|e| format!("failed to read chart directory {}: {}", path.display(), e))?;
+        let mut files: ChartFiles = ChartFiles::default();
+        for entry in entries.flatten() {
+            if !entry.path().is_file() {
+                continue;
+            }
+            let file_name: String = entry.file_name().to_string_lossy().to_string();
+            if let Ok(content) = std::fs::read_to_string(entry.path()) {
+                files.set(&file_name, content);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Validate values against a chart's JSON schema. Returns one message per violation,
+    /// prefixed with the JSON pointer of the offending value.
+    pub fn validate_values(schema: &Value, values: &Value) -> Result<Vec<String>, String> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| format!("invalid values.schema.json: {}", e))?;
+        let errors: Vec<String> = validator
+            .iter_errors(values)
+            .map(|e| {
+                let path: String = e.instance_path.to_string();
+                format!("{}: {}", if path.is_empty() { "/" } else { path.as_str() }, e)
+            })
+            .collect();
+        Ok(errors)
+    }
+
+    /// Sort chart versions newest first; tags that are not semver go last.
+    pub fn sort_versions(versions: &mut Vec<String>) {
+        versions.sort_by(|a, b| {
+            let parse = |v: &str| semver::Version::parse(v.trim_start_matches('v')).ok();
+            match (parse(a), parse(b)) {
+                (Some(x), Some(y)) => y.cmp(&x),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.cmp(b),
+            }
+        });
+        versions.dedup();
+    }
+
+    /// List the tags of an `oci://` chart through the registry API, using the credentials
+    /// stored by `helm registry login` when there are any.
+    pub async fn oci_versions(
+        reference: &str,
+        registry_config: Option<&Path>,
+    ) -> Result<Vec<String>, String> {
+        let without_scheme: &str = reference.trim_start_matches("oci://").trim_end_matches('/');
+        let (host, repository) = without_scheme
+            .split_once('/')
+            .ok_or_else(|| format!("invalid OCI reference {}", reference))?;
+        // A tag in the reference is not part of the repository name
+        let repository: &str = match repository.rfind(':') {
+            Some(idx) if !repository[idx..].contains('/') => &repository[..idx],
+            _ => repository,
+        };
+        let basic_auth: Option<String> = registry_config.and_then(|p| Self::registry_auth(p, host));
+
+        let http: reqwest::Client = reqwest::Client::new();
+        let url: String = format!("https://{}/v2/{}/tags/list", host, repository);
+        let mut res =
+            Self::registry_get(&http, &url, basic_auth.as_ref().map(|a| format!("Basic {}", a)))
+                .await?;
+        if res.status() == reqwest::StatusCode::UNAUTHORIZED {
+            // Registries hand out scoped bearer tokens; anonymous pulls get one too
+            let challenge: String = res
+                .headers()
+                .get(reqwest::header::WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let token: String =
+                Self::registry_token(&http, &challenge, basic_auth.as_ref()).await?;
+            res = Self::registry_get(&http, &url, Some(format!("Bearer {}", token))).await?;
+        }
+        if !res.status().is_success() {
+            return Err(format!("registry returned {} for {}", res.status(), url));
+        }
+        let body: Value =
+            res.json().await.map_err(|e| format!("invalid tag list from {}: {}", host, e))?;
+        // OCI tags can't contain '+', Helm stores semver build metadata with '_'
+        let versions: Vec<String> = body
+            .get("tags")
+            .and_then(|t| t.as_array())
+            .map(|tags| {
+                tags.iter().filter_map(|t| t.as_str()).map(|t| t.replace('_', "+")).collect()
+            })
+            .unwrap_or_default();
+        Ok(versions)
+    }
+
+    async fn registry_get(
+        http: &reqwest::Client,
+        url: &str,
+        authorization: Option<String>,
+    ) -> Result<reqwest::Response, String> {
+        let mut req = http.get(url);
+        if let Some(auth) = authorization {
+            req = req.header(reqwest::header::AUTHORIZATION, auth);
+        }
+        req.send().await.map_err(|e| format!("registry request failed: {}", e))
+    }
+
+    // Exchange a `Bearer realm=...,service=...,scope=...` challenge for a token.
+    async fn registry_token(
+        http: &reqwest::Client,
+        challenge: &str,
+        basic_auth: Option<&String>,
+    ) -> Result<String, String> {
+        let params: &str = challenge.strip_prefix("Bearer ").ok_or_else(|| {
+            "registry requires authentication; log in to the registry first".to_string()
+        })?;
+        let mut fields: HashMap<String, String> = HashMap::new();
+        for part in params.split(',') {
+            if let Some((k, v)) = part.split_once('=') {
+                fields.insert(k.trim().to_string(), v.trim().trim_matches('"').to_string());
+            }
+        }
+        let realm: &String = fields
+            .get("realm")
+            .ok_or_else(|| "registry auth challenge has no realm".to_string())?;
+
+        let query: Vec<(&str, &String)> = ["service", "scope"]
+            .into_iter()
+            .filter_map(|k| fields.get(k).map(|v| (k, v)))
+            .collect();
+        let mut req = http.get(realm.as_str()).query(&query);
+        if let Some(auth) = basic_auth {
+            req = req.header(reqwest::header::AUTHORIZATION, format!("Basic {}", auth));
+        }
+        let res = req.send().await.map_err(|e| format!("registry token request failed: {}", e))?;
+        if !res.status().is_success() {
+            return Err(format!("registry token request returned {}", res.status()));
+        }
+        let body: Value =
+            res.json().await.map_err(|e| format!("invalid registry token response: {}", e))?;
+        body.get("token")
+            .or_else(|| body.get("access_token"))
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+            .ok_or_else(|| "registry token response has no token".to_string())
+    }
+
+    // Base64 "user:password" for a host from Helm's registry config (Docker config format).
+    fn registry_auth(config_path: &Path, host: &str) -> Option<String> {
+        let text: String = std::fs::read_to_string(config_path).ok()?;
+        let config: Value = serde_json::from_str(&text).ok()?;
+        let auths = config.get("auths")?.as_object()?;
+        let entry: &Value = auths.get(host).or_else(|| {
+            auths.iter().find(|(k, _)| k.trim_start_matches("https://") == host).map(|(_, v)| v)
+        })?;
+        if let Some(auth) = entry.get("auth").and_then(|a| a.as_str()) {
+            return Some(auth.to_string());
+        }
+        let user: &str = entry.get("username")?.as_str()?;
+        let password: &str = entry.get("password")?.as_str()?;
+        Some(STANDARD.encode(format!("{}:{}", user, password)))
+    }
+}
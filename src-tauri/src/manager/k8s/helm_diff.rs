@@ -0,0 +1,234 @@
+use crate::types::helm::{HelmReleaseDiff, HelmResourceDiff, HelmValueChange};
+use crate::utils::diff::unified_diff;
+use ring::hmac;
+use ring::rand::SystemRandom;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A rendered manifest document identified the way helm-diff does: apiVersion,
+/// kind, namespace and name.
+struct ManifestResource {
+    api_version: String,
+    kind: String,
+    namespace: Option<String>,
+    name: String,
+    yaml: String,
+}
+
+pub struct HelmDiff;
+
+impl HelmDiff {
+    pub fn diff(
+        from: String,
+        to: String,
+        old_manifest: &str,
+        old_values: &Value,
+        new_manifest: &str,
+        new_values: &Value,
+    ) -> HelmReleaseDiff {
+        let (resources, unchanged) = Self::diff_manifests(old_manifest, new_manifest);
+        HelmReleaseDiff {
+            from,
+            to,
+            resources,
+            unchanged,
+            values: Self::diff_values(old_values, new_values),
+        }
+    }
+
+    /// Per-resource diff of two rendered manifests plus the number of unchanged resources.
+    /// Secret values are replaced by a keyed hash, so changed keys still show up.
+    pub fn diff_manifests(old: &str, new: &str) -> (Vec<HelmResourceDiff>, usize) {
+        // A fresh key per diff: hashes compare within it but can't be looked up elsewhere
+        let secret_key: Option<hmac::Key> =
+            hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new()).ok();
+        let old_resources: BTreeMap<String, ManifestResource> =
+            Self::parse_manifest(old, secret_key.as_ref());
+        let mut new_resources: BTreeMap<String, ManifestResource> =
+            Self::parse_manifest(new, secret_key.as_ref());
+
+        let mut diffs: Vec<HelmResourceDiff> = Vec::new();
+        let mut unchanged: usize = 0;
+        for (key, old_res) in old_resources.into_iter() {
+            match new_resources.remove(&key) {
+                Some(new_res) if new_res.yaml == old_res.yaml => unchanged += 1,
+                Some(new_res) => {
+                    let diff: String = unified_diff(&old_res.yaml, &new_res.yaml);
+                    diffs.push(Self::resource_diff(new_res, "modified", diff));
+                }
+                None => {
+                    let diff: String = unified_diff(&old_res.yaml, "");
+                    diffs.push(Self::resource_diff(old_res, "removed", diff));
+                }
+            }
+        }
+        for (_, new_res) in new_resources.into_iter() {
+            let diff: String = unified_diff("", &new_res.yaml);
+            diffs.push(Self::resource_diff(new_res, "added", diff));
+        }
+
+        diffs.sort_by(|a, b| {
+            (&a.namespace, &a.kind, &a.name).cmp(&(&b.namespace, &b.kind, &b.name))
+        });
+        (diffs, unchanged)
+    }
+
+    /// Leaf-level changes between two values trees, keyed by dotted path.
+    pub fn diff_values(old: &Value, new: &Value) -> Vec<HelmValueChange> {
+        let mut old_leaves: BTreeMap<String, Value> = BTreeMap::new();
+        let mut new_leaves: BTreeMap<String, Value> = BTreeMap::new();
+        Self::flatten("", old, &mut old_leaves);
+        Self::flatten("", new, &mut new_leaves);
+
+        let mut changes: Vec<HelmValueChange> = Vec::new();
+        for (path, old_value) in old_leaves.iter() {
+            match new_leaves.get(path) {
+                Some(new_value) if new_value == old_value => {}
+                Some(new_value) => changes.push(HelmValueChange {
+                    path: path.clone(),
+                    change: "changed".to_string(),
+                    old: Some(old_value.clone()),
+                    new: Some(new_value.clone()),
+                }),
+                None => changes.push(HelmValueChange {
+                    path: path.clone(),
+                    change: "removed".to_string(),
+                    old: Some(old_value.clone()),
+                    new: None,
+                }),
+            }
+        }
+        for (path, new_value) in new_leaves.into_iter() {
+            if !old_leaves.contains_key(&path) {
+                changes.push(HelmValueChange {
+                    path,
+                    change: "added".to_string(),
+                    old: None,
+                    new: Some(new_value),
+                });
+            }
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        changes
+    }
+
+    fn resource_diff(res: ManifestResource, change: &str, diff: String) -> HelmResourceDiff {
+        HelmResourceDiff {
+            api_version: res.api_version,
+            kind: res.kind,
+            namespace: res.namespace,
+            name: res.name,
+            change: change.to_string(),
+            diff,
+        }
+    }
+
+    // Objects are walked; arrays and scalars are compared as a whole.
+    fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (k, v) in map.iter() {
+                    let path: String =
+                        if prefix.is_empty() { k.clone() } else { format!("{}.{}", prefix, k) };
+                    Self::flatten(&path, v, out);
+                }
+            }
+            Value::Object(_) | Value::Null if prefix.is_empty() => {}
+            _ => {
+                out.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+
+    fn parse_manifest(
+        manifest: &str,
+        secret_key: Option<&hmac::Key>,
+    ) -> BTreeMap<String, ManifestResource> {
+        let mut resources: BTreeMap<String, ManifestResource> = BTreeMap::new();
+        for doc in Self::split_documents(manifest) {
+            let mut parsed: serde_yaml::Value = match serde_yaml::from_str(&doc) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            let field = |path: &[&str]| -> Option<String> {
+                let mut cur: &serde_yaml::Value = &parsed;
+                for p in path {
+                    cur = cur.get(*p)?;
+                }
+                cur.as_str().map(|s| s.to_string())
+            };
+            let (Some(kind), Some(name)) = (field(&["kind"]), field(&["metadata", "name"])) else {
+                continue;
+            };
+            let api_version: String = field(&["apiVersion"]).unwrap_or_default();
+            let namespace: Option<String> = field(&["metadata", "namespace"]);
+            if kind == "Secret" {
+                Self::mask_secret(&mut parsed, secret_key);
+            }
+            // Re-serialize so formatting-only differences don't show up as changes
+            let yaml: String = serde_yaml::to_string(&parsed).unwrap_or(doc.clone());
+            let key: String =
+                format!("{}/{}/{}/{}", namespace.as_deref().unwrap_or(""), api_version, kind, name);
+            resources.insert(
+                key,
+                ManifestResource {
+                    api_version,
+                    kind,
+                    namespace,
+                    name,
+                    yaml,
+                },
+            );
+        }
+        resources
+    }
+
+    // Without a key (no system randomness) values are hidden without a hash.
+    fn mask_secret(secret: &mut serde_yaml::Value, key: Option<&hmac::Key>) {
+        for field in ["data", "stringData"] {
+            let Some(serde_yaml::Value::Mapping(values)) = secret.get_mut(field) else {
+                continue;
+            };
+            for value in values.values_mut() {
+                let raw: String = match &*value {
+                    serde_yaml::Value::String(s) => s.clone(),
+                    other => serde_yaml::to_string(other).unwrap_or_default(),
+                };
+                let masked: String = match key {
+                    Some(key) => {
+                        let tag: hmac::Tag = hmac::sign(key, raw.as_bytes());
+                        let hash: String =
+                            tag.as_ref()[..6].iter().map(|b| format!("{:02x}", b)).collect();
+                        format!("(hidden, hash {})", hash)
+                    }
+                    None => "(hidden)".to_string(),
+                };
+                *value = serde_yaml::Value::String(masked);
+            }
+        }
+    }
+
+    // Split on `---` separators and drop the `# Source:` comments Helm adds.
+    pub(crate) fn split_documents(manifest: &str) -> Vec<String> {
+        Self::sourced_documents(manifest).into_iter().map(|(_, doc)| doc).collect()
+    }
+
+    /// Documents of a rendered manifest with the template path from their `# Source:` comment.
+    pub(crate) fn sourced_documents(manifest: &str) -> Vec<(Option<String>, String)> {
+        let mut docs: Vec<(Option<String>, String)> = Vec::new();
+        let mut source: Option<String> = None;
+        let mut current: Vec<&str> = Vec::new();
+        for line in manifest.lines() {
+            if line.trim_end() == "---" || line.starts_with("--- ") {
+                docs.push((source.take(), current.join("\n")));
+                current.clear();
+            } else if let Some(path) = line.strip_prefix("# Source:") {
+                source = Some(path.trim().to_string());
+            } else {
+                current.push(line);
+            }
+        }
+        docs.push((source, current.join("\n")));
+        docs.into_iter().filter(|(_, d)| !d.trim().is_empty()).collect()
+    }
+}
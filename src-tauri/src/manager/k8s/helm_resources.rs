@@ -0,0 +1,452 @@
+use crate::manager::k8s::dynamic_resources::DynamicK8sResources;
+use crate::manager::k8s::helm_diff::HelmDiff;
+use crate::types::helm::{HelmDriftField, HelmRelease, HelmReleaseResource};
+use crate::utils::quantity::parse_quantity;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use kube::api::{Api, ApiResource, DynamicObject, ListParams};
+use kube::Client;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+const RELEASE_NAME_ANNOTATION: &str = "meta.helm.sh/release-name";
+const RELEASE_NAMESPACE_ANNOTATION: &str = "meta.helm.sh/release-namespace";
+// Drifted fields reported per resource
+const MAX_DRIFT_FIELDS: usize = 50;
+// Maps whose values are resource quantities, besides `resources.requests/limits`
+const QUANTITY_PARENTS: [&str; 6] = [
+    "requests",
+    "limits",
+    "overhead",
+    "capacity",
+    "allocatable",
+    "hard",
+];
+// IntOrString fields, which the manifest may write either way
+const INT_OR_STRING_FIELDS: [&str; 5] = [
+    "port",
+    "targetPort",
+    "maxSurge",
+    "maxUnavailable",
+    "minAvailable",
+];
+
+/// Resolves the objects a release rendered against the live cluster and reports
+/// existence, readiness and drift for each of them.
+pub struct HelmReleaseResources;
+
+impl HelmReleaseResources {
+    pub async fn inventory(client: Client, release: &HelmRelease) -> Vec<HelmReleaseResource> {
+        let rendered: Vec<Value> = HelmDiff::split_documents(&release.manifest)
+            .iter()
+            .filter_map(|doc| serde_yaml::from_str::<Value>(doc).ok())
+            .filter(|obj| Self::str_at(obj, &["kind"]).is_some())
+            .filter(|obj| Self::str_at(obj, &["metadata", "name"]).is_some())
+            .collect();
+
+        // Names the manifest claims per kind, so annotation lookups don't pick those up
+        let mut claimed: HashMap<(String, String), HashSet<String>> = HashMap::new();
+        for obj in rendered.iter() {
+            claimed
+                .entry(Self::type_key(obj))
+                .or_default()
+                .insert(Self::str_at(obj, &["metadata", "name"]).unwrap_or_default());
+        }
+
+        let mut discovered: HashMap<(String, String), Result<(ApiResource, bool), String>> =
+            HashMap::new();
+        let mut resources: Vec<HelmReleaseResource> = Vec::new();
+        for obj in rendered.iter() {
+            let key: (String, String) = Self::type_key(obj);
+            if !discovered.contains_key(&key) {
+                let found = DynamicK8sResources::discover(&client, &key.0, &key.1).await;
+                discovered.insert(key.clone(), found);
+            }
+            let mut resource: HelmReleaseResource = HelmReleaseResource {
+                api_version: key.0.clone(),
+                kind: key.1.clone(),
+                namespace: Self::str_at(obj, &["metadata", "namespace"]),
+                name: Self::str_at(obj, &["metadata", "name"]).unwrap_or_default(),
+                exists: false,
+                matched_by: None,
+                managed: false,
+                ready: None,
+                message: None,
+                drifted: false,
+                drift: vec![],
+                error: None,
+            };
+            let (ar, namespaced): (ApiResource, bool) = match discovered.get(&key) {
+                Some(Ok(found)) => found.clone(),
+                Some(Err(e)) => {
+                    resource.error = Some(e.clone());
+                    resources.push(resource);
+                    continue;
+                }
+                None => continue,
+            };
+            // Namespaced objects without an explicit namespace go to the release namespace
+            resource.namespace = if namespaced {
+                Some(resource.namespace.unwrap_or_else(|| release.namespace.clone()))
+            } else {
+                None
+            };
+            let api: Api<DynamicObject> = match resource.namespace.as_ref() {
+                Some(ns) => Api::namespaced_with(client.clone(), ns, &ar),
+                None => Api::all_with(client.clone(), &ar),
+            };
+
+            let live: Option<DynamicObject> = match api.get_opt(&resource.name).await {
+                Ok(Some(o)) => {
+                    resource.matched_by = Some("name".to_string());
+                    Some(o)
+                }
+                Ok(None) => {
+                    let taken: HashSet<String> = claimed.get(&key).cloned().unwrap_or_default();
+                    let found: Option<DynamicObject> =
+                        Self::find_by_annotation(&api, release, &taken).await;
+                    if found.is_some() {
+                        resource.matched_by = Some("annotation".to_string());
+                    }
+                    found
+                }
+                Err(e) => {
+                    resource.error =
+                        Some(format!("failed to get {} {}: {}", resource.kind, resource.name, e));
+                    resources.push(resource);
+                    continue;
+                }
+            };
+            let Some(live) = live else {
+                resource.message = Some("not found in the cluster".to_string());
+                resources.push(resource);
+                continue;
+            };
+
+            resource.exists = true;
+            resource.managed = Self::owned_by(&live, release);
+            let live: Value = serde_json::to_value(&live).unwrap_or(Value::Null);
+            let (ready, message) = Self::readiness(&resource.kind, &live);
+            resource.ready = ready;
+            resource.message = match (resource.matched_by.as_deref(), message) {
+                (Some("annotation"), msg) => {
+                    let live_name: String =
+                        Self::str_at(&live, &["metadata", "name"]).unwrap_or_default();
+                    Some(match msg {
+                        Some(m) => format!("found as {}; {}", live_name, m),
+                        None => format!("found as {}", live_name),
+                    })
+                }
+                (_, msg) => msg,
+            };
+            resource.drift = Self::drift(&resource.kind, obj, &live);
+            resource.drifted = !resource.drift.is_empty();
+            resources.push(resource);
+        }
+        resources
+    }
+
+    // A live object of the same kind annotated as belonging to the release, when exactly
+    // one exists that the manifest doesn't already account for by name.
+    async fn find_by_annotation(
+        api: &Api<DynamicObject>,
+        release: &HelmRelease,
+        claimed: &HashSet<String>,
+    ) -> Option<DynamicObject> {
+        let list = api.list(&ListParams::default()).await.ok()?;
+        let mut candidates: Vec<DynamicObject> = list
+            .items
+            .into_iter()
+            .filter(|o| Self::owned_by(o, release))
+            .filter(|o| !o.metadata.name.as_ref().is_some_and(|n| claimed.contains(n)))
+            .collect();
+        if candidates.len() == 1 {
+            candidates.pop()
+        } else {
+            None
+        }
+    }
+
+    fn owned_by(obj: &DynamicObject, release: &HelmRelease) -> bool {
+        let Some(annotations) = obj.metadata.annotations.as_ref() else {
+            return false;
+        };
+        annotations.get(RELEASE_NAME_ANNOTATION) == Some(&release.name)
+            && annotations.get(RELEASE_NAMESPACE_ANNOTATION) == Some(&release.namespace)
+    }
+
+    // Readiness roughly as `helm --wait` judges it; None for kinds without a notion of ready.
+    fn readiness(kind: &str, obj: &Value) -> (Option<bool>, Option<String>) {
+        let int = |path: &[&str]| -> i64 {
+            Self::value_at(obj, path).and_then(|v| v.as_i64()).unwrap_or(0)
+        };
+        match kind {
+            "Deployment" => {
+                let desired: i64 = Self::value_at(obj, &["spec", "replicas"])
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(1);
+                if int(&["status", "observedGeneration"]) < int(&["metadata", "generation"]) {
+                    return (Some(false), Some("rollout not yet observed".to_string()));
+                }
+                let updated: i64 = int(&["status", "updatedReplicas"]);
+                let available: i64 = int(&["status", "availableReplicas"]);
+                if updated < desired {
+                    return (
+                        Some(false),
+                        Some(format!("{}/{} replicas updated", updated, desired)),
+                    );
+                }
+                let msg: String = format!("{}/{} replicas available", available, desired);
+                (Some(available >= desired), Some(msg))
+            }
+            "StatefulSet" => {
+                let desired: i64 = Self::value_at(obj, &["spec", "replicas"])
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(1);
+                let ready: i64 = int(&["status", "readyReplicas"]);
+                let on_delete: bool = Self::str_at(obj, &["spec", "updateStrategy", "type"])
+                    .as_deref()
+                    == Some("OnDelete");
+                let current = Self::str_at(obj, &["status", "currentRevision"]);
+                let update = Self::str_at(obj, &["status", "updateRevision"]);
+                if !on_delete && update.is_some() && current != update {
+                    return (Some(false), Some("rolling update in progress".to_string()));
+                }
+                (Some(ready >= desired), Some(format!("{}/{} replicas ready", ready, desired)))
+            }
+            "DaemonSet" => {
+                let desired: i64 = int(&["status", "desiredNumberScheduled"]);
+                let updated: i64 = int(&["status", "updatedNumberScheduled"]);
+                let ready: i64 = int(&["status", "numberReady"]);
+                if updated < desired {
+                    return (Some(false), Some(format!("{}/{} pods updated", updated, desired)));
+                }
+                (Some(ready >= desired), Some(format!("{}/{} pods ready", ready, desired)))
+            }
+            "ReplicaSet" | "ReplicationController" => {
+                let desired: i64 = Self::value_at(obj, &["spec", "replicas"])
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(1);
+                let ready: i64 = int(&["status", "readyReplicas"]);
+                (Some(ready >= desired), Some(format!("{}/{} replicas ready", ready, desired)))
+            }
+            "Pod" => {
+                let phase: String = Self::str_at(obj, &["status", "phase"]).unwrap_or_default();
+                if phase == "Succeeded" {
+                    return (Some(true), Some(phase));
+                }
+                match Self::condition(obj, "Ready") {
+                    Some((ready, _)) => (Some(ready), Some(phase)),
+                    None => (Some(false), Some(phase)),
+                }
+            }
+            "Job" => {
+                if let Some((true, _)) = Self::condition(obj, "Complete") {
+                    return (Some(true), Some("completed".to_string()));
+                }
+                if let Some((true, msg)) = Self::condition(obj, "Failed") {
+                    return (Some(false), Some(msg.unwrap_or_else(|| "failed".to_string())));
+                }
+                (Some(false), Some("running".to_string()))
+            }
+            "PersistentVolumeClaim" => {
+                let phase: String = Self::str_at(obj, &["status", "phase"]).unwrap_or_default();
+                (Some(phase == "Bound"), Some(phase))
+            }
+            "Service" => {
+                if Self::str_at(obj, &["spec", "type"]).as_deref() != Some("LoadBalancer") {
+                    return (Some(true), None);
+                }
+                let assigned: bool = Self::value_at(obj, &["status", "loadBalancer", "ingress"])
+                    .and_then(|v| v.as_array())
+                    .is_some_and(|a| !a.is_empty());
+                let msg: Option<String> =
+                    (!assigned).then(|| "waiting for load balancer address".to_string());
+                (Some(assigned), msg)
+            }
+            "CustomResourceDefinition" => match Self::condition(obj, "Established") {
+                Some((ok, msg)) => (Some(ok), msg),
+                None => (Some(false), Some("not established".to_string())),
+            },
+            "APIService" => match Self::condition(obj, "Available") {
+                Some((ok, msg)) => (Some(ok), msg),
+                None => (None, None),
+            },
+            // Custom resources commonly report a Ready condition
+            _ => match Self::condition(obj, "Ready") {
+                Some((ok, msg)) => (Some(ok), msg),
+                None => (None, None),
+            },
+        }
+    }
+
+    // (status == "True", message) of a status condition.
+    fn condition(obj: &Value, condition_type: &str) -> Option<(bool, Option<String>)> {
+        let conditions: &Vec<Value> = Self::value_at(obj, &["status", "conditions"])?.as_array()?;
+        let c: &Value = conditions
+            .iter()
+            .find(|c| c.get("type").and_then(|t| t.as_str()) == Some(condition_type))?;
+        let ok: bool = c.get("status").and_then(|s| s.as_str()) == Some("True");
+        let message: Option<String> = c
+            .get("message")
+            .and_then(|m| m.as_str())
+            .filter(|m| !m.is_empty())
+            .map(|m| m.to_string());
+        Some((ok, message))
+    }
+
+    // Fields set in the rendered manifest whose live value differs. Fields the manifest
+    // leaves out are ignored, so server-side defaults don't count as drift.
+    fn drift(kind: &str, rendered: &Value, live: &Value) -> Vec<HelmDriftField> {
+        let mut expected: Value = rendered.clone();
+        if let Some(map) = expected.as_object_mut() {
+            map.remove("apiVersion");
+            map.remove("kind");
+            map.remove("status");
+            if let Some(meta) = map.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+                meta.retain(|k, _| k == "labels" || k == "annotations");
+            }
+        }
+        let secret: bool = kind == "Secret";
+        if secret {
+            Self::encode_string_data(&mut expected);
+        }
+
+        let mut out: Vec<HelmDriftField> = Vec::new();
+        Self::compare("", &expected, Some(live), &mut out);
+        if secret {
+            let sensitive = |path: &str| {
+                ["data", "stringData"].iter().any(|root| {
+                    path == *root
+                        || path.strip_prefix(root).is_some_and(|rest| rest.starts_with(['.', '[']))
+                })
+            };
+            for field in out.iter_mut().filter(|f| sensitive(&f.path)) {
+                field.expected = None;
+                field.actual = None;
+            }
+        }
+        out.truncate(MAX_DRIFT_FIELDS);
+        out
+    }
+
+    // The apiserver folds Secret stringData into base64 data.
+    fn encode_string_data(secret: &mut Value) {
+        let Some(map) = secret.as_object_mut() else {
+            return;
+        };
+        let Some(Value::Object(string_data)) = map.remove("stringData") else {
+            return;
+        };
+        let data: &mut Value =
+            map.entry("data").or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if let Some(data) = data.as_object_mut() {
+            for (k, v) in string_data.into_iter() {
+                let text: String =
+                    v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string());
+                data.insert(k, Value::String(STANDARD.encode(text)));
+            }
+        }
+    }
+
+    fn compare(
+        path: &str,
+        expected: &Value,
+        actual: Option<&Value>,
+        out: &mut Vec<HelmDriftField>,
+    ) {
+        let join = |key: &str| -> String {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        match (expected, actual) {
+            // Nulls are dropped by the apiserver
+            (Value::Null, _) => {}
+            (Value::Object(e), None) if e.is_empty() => {}
+            (Value::Array(e), None) if e.is_empty() => {}
+            (Value::Object(e), Some(Value::Object(a))) => {
+                for (k, v) in e.iter() {
+                    Self::compare(&join(k), v, a.get(k), out);
+                }
+            }
+            (Value::Array(e), Some(Value::Array(a))) if e.len() == a.len() => {
+                for (i, ev) in e.iter().enumerate() {
+                    // Lists of named items (containers, ports, env) are matched by name
+                    match ev.get("name").and_then(|n| n.as_str()) {
+                        Some(name) => {
+                            let av: Option<&Value> = a
+                                .iter()
+                                .find(|x| x.get("name").and_then(|n| n.as_str()) == Some(name));
+                            Self::compare(&format!("{}[{}]", path, name), ev, av, out);
+                        }
+                        None => Self::compare(&format!("{}[{}]", path, i), ev, a.get(i), out),
+                    }
+                }
+            }
+            (e, Some(a)) if Self::scalar_eq(path, e, a) => {}
+            (e, a) => out.push(HelmDriftField {
+                path: path.to_string(),
+                expected: Some(e.clone()),
+                actual: a.cloned(),
+            }),
+        }
+    }
+
+    // Equal after the normalization the apiserver applies, only where it applies it:
+    // quantities such as "0.5" vs "500m", and numbers vs strings in IntOrString fields.
+    fn scalar_eq(path: &str, expected: &Value, actual: &Value) -> bool {
+        if expected == actual {
+            return true;
+        }
+        let segments: Vec<&str> =
+            path.split('.').map(|s| s.split('[').next().unwrap_or_default()).collect();
+        let field: &str = segments.last().copied().unwrap_or_default();
+        let parent: Option<&str> = segments.len().checked_sub(2).map(|i| segments[i]);
+        let grandparent: Option<&str> = segments.len().checked_sub(3).map(|i| segments[i]);
+        let as_text = |v: &Value| -> Option<String> {
+            match v {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            }
+        };
+        let (Some(e), Some(a)) = (as_text(expected), as_text(actual)) else {
+            return false;
+        };
+
+        let quantity: bool = match parent {
+            // Only under `resources`; other `limits` maps hold plain values
+            Some("requests" | "limits") => grandparent == Some("resources"),
+            Some(p) => QUANTITY_PARENTS.contains(&p),
+            None => false,
+        };
+        if quantity {
+            return match (parse_quantity(&e), parse_quantity(&a)) {
+                (Some(x), Some(y)) => (x - y).abs() <= f64::EPSILON * x.abs().max(y.abs()).max(1.0),
+                _ => false,
+            };
+        }
+        INT_OR_STRING_FIELDS.contains(&field) && e == a
+    }
+
+    fn type_key(obj: &Value) -> (String, String) {
+        (
+            Self::str_at(obj, &["apiVersion"]).unwrap_or_default(),
+            Self::str_at(obj, &["kind"]).unwrap_or_default(),
+        )
+    }
+
+    fn value_at<'a>(obj: &'a Value, path: &[&str]) -> Option<&'a Value> {
+        let mut cur: &Value = obj;
+        for p in path {
+            cur = cur.get(*p)?;
+        }
+        Some(cur)
+    }
+
+    fn str_at(obj: &Value, path: &[&str]) -> Option<String> {
+        Self::value_at(obj, path).and_then(|v| v.as_str()).map(|s| s.to_string())
+    }
+}
@@ -0,0 +1,217 @@
+use crate::manager::k8s::client::K8sClient;
+use crate::types::helm::{HelmRelease, HelmReleaseDetails};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flate2::read::GzDecoder;
+use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+use kube::api::{Api, ListParams};
+use kube::Client;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::io::Read;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Reads Helm v3 releases straight from the cluster storage (Secrets, or ConfigMaps
+/// for the configmap driver) so release details don't depend on the Helm CLI.
+pub struct HelmStorage;
+
+impl HelmStorage {
+    /// All stored revisions of a release, oldest first. When no namespace is given
+    /// every namespace is searched and the release must exist in only one of them.
+    pub async fn revisions(
+        client: Client,
+        namespace: Option<&str>,
+        release_name: &str,
+    ) -> Result<Vec<HelmRelease>, String> {
+        let ns: Option<String> = namespace.map(|s| s.to_string());
+        let lp: ListParams =
+            ListParams::default().labels(&format!("owner=helm,name={}", release_name));
+
+        let mut releases: Vec<HelmRelease> = Vec::new();
+        let secrets_api: Api<Secret> = K8sClient::api::<Secret>(client.clone(), ns.clone()).await;
+        let secrets = secrets_api
+            .list(&lp)
+            .await
+            .map_err(|e| format!("Failed to list release secrets: {}", e))?;
+        let mut decode_error: Option<String> = None;
+        for s in secrets.items.iter() {
+            match Self::decode_secret(s) {
+                Ok(release) => releases.push(release),
+                Err(e) => decode_error = Some(e),
+            }
+        }
+
+        // The configmap storage driver is rare; only look there when no secrets exist
+        if releases.is_empty() {
+            let cms_api: Api<ConfigMap> = K8sClient::api::<ConfigMap>(client, ns).await;
+            let cms = cms_api
+                .list(&lp)
+                .await
+                .map_err(|e| format!("Failed to list release configmaps: {}", e))?;
+            for c in cms.items.iter() {
+                match Self::decode_config_map(c) {
+                    Ok(release) => releases.push(release),
+                    Err(e) => decode_error = Some(e),
+                }
+            }
+        }
+
+        // A single unreadable revision shouldn't hide the rest of the history
+        if releases.is_empty() {
+            return Err(
+                decode_error.unwrap_or_else(|| format!("release {} not found", release_name))
+            );
+        }
+        let namespaces: BTreeSet<&str> = releases.iter().map(|r| r.namespace.as_str()).collect();
+        if namespaces.len() > 1 {
+            return Err(format!(
+                "release {} exists in several namespaces ({}); please select a namespace",
+                release_name,
+                namespaces.into_iter().collect::<Vec<_>>().join(", ")
+            ));
+        }
+
+        releases.sort_by_key(|r| r.version);
+        Ok(releases)
+    }
+
+    /// A single revision of a release; the latest one when `revision` is not given.
+    pub async fn release(
+        client: Client,
+        namespace: Option<&str>,
+        release_name: &str,
+        revision: Option<i32>,
+    ) -> Result<HelmRelease, String> {
+        let releases: Vec<HelmRelease> = Self::revisions(client, namespace, release_name).await?;
+        match revision {
+            Some(rev) => releases
+                .into_iter()
+                .find(|r| r.version == rev)
+                .ok_or_else(|| format!("revision {} of release {} not found", rev, release_name)),
+            None => releases
+                .into_iter()
+                .last()
+                .ok_or_else(|| format!("release {} not found", release_name)),
+        }
+    }
+
+    pub fn decode_secret(secret: &Secret) -> Result<HelmRelease, String> {
+        let name: String = secret.metadata.name.clone().unwrap_or_default();
+        let data = secret
+            .data
+            .as_ref()
+            .and_then(|d| d.get("release"))
+            .ok_or_else(|| format!("secret {} has no release data", name))?;
+        let mut release: HelmRelease =
+            Self::decode(&data.0).map_err(|e| format!("secret {}: {}", name, e))?;
+        if release.namespace.is_empty() {
+            release.namespace = secret.metadata.namespace.clone().unwrap_or_default();
+        }
+        Ok(release)
+    }
+
+    pub fn decode_config_map(cm: &ConfigMap) -> Result<HelmRelease, String> {
+        let name: String = cm.metadata.name.clone().unwrap_or_default();
+        let data: &String = cm
+            .data
+            .as_ref()
+            .and_then(|d| d.get("release"))
+            .ok_or_else(|| format!("configmap {} has no release data", name))?;
+        let mut release: HelmRelease =
+            Self::decode(data.as_bytes()).map_err(|e| format!("configmap {}: {}", name, e))?;
+        if release.namespace.is_empty() {
+            release.namespace = cm.metadata.namespace.clone().unwrap_or_default();
+        }
+        Ok(release)
+    }
+
+    // Helm stores base64(gzip(json)); very old releases were not compressed.
+    fn decode(encoded: &[u8]) -> Result<HelmRelease, String> {
+        let compact: Vec<u8> =
+            encoded.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+        let raw: Vec<u8> =
+            STANDARD.decode(&compact).map_err(|e| format!("invalid release encoding: {}", e))?;
+        let json: Vec<u8> = if raw.starts_with(&GZIP_MAGIC) {
+            let mut out: Vec<u8> = Vec::new();
+            GzDecoder::new(raw.as_slice())
+                .read_to_end(&mut out)
+                .map_err(|e| format!("failed to decompress release: {}", e))?;
+            out
+        } else {
+            raw
+        };
+        serde_json::from_slice(&json).map_err(|e| format!("failed to parse release: {}", e))
+    }
+
+    pub fn details(release: HelmRelease) -> HelmReleaseDetails {
+        let computed_values: Value = Self::computed_values(&release);
+        let metadata = release.chart.metadata;
+        HelmReleaseDetails {
+            chart: format!("{}-{}", metadata.name, metadata.version),
+            chart_version: metadata.version.clone(),
+            app_version: metadata.app_version.clone(),
+            chart_metadata: metadata,
+            name: release.name,
+            namespace: release.namespace,
+            revision: release.version,
+            status: release.info.status.unwrap_or_default(),
+            description: release.info.description.unwrap_or_default(),
+            first_deployed: release.info.first_deployed,
+            last_deployed: release.info.last_deployed,
+            user_values: Self::object_or_empty(release.config),
+            computed_values,
+            manifest: release.manifest,
+            notes: release.info.notes,
+            hooks: release.hooks,
+        }
+    }
+
+    /// One entry per revision in the shape `helm history -o json` prints.
+    pub fn history_entry(release: &HelmRelease) -> Value {
+        let metadata = &release.chart.metadata;
+        serde_json::json!({
+            "revision": release.version,
+            "updated": release.info.last_deployed.clone().unwrap_or_default(),
+            "status": release.info.status.clone().unwrap_or_default(),
+            "chart": format!("{}-{}", metadata.name, metadata.version),
+            "app_version": metadata.app_version.clone().unwrap_or_default(),
+            "description": release.info.description.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Chart defaults coalesced with the user-supplied values, like `helm get values --all`.
+    pub fn computed_values(release: &HelmRelease) -> Value {
+        let mut values: Value = Self::object_or_empty(release.chart.values.clone());
+        Self::coalesce(&mut values, &release.config);
+        values
+    }
+
+    /// User values win over chart defaults; a null user value removes the default key.
+    pub fn coalesce(base: &mut Value, overrides: &Value) {
+        let (Some(base_map), Some(override_map)) = (base.as_object_mut(), overrides.as_object())
+        else {
+            return;
+        };
+        for (key, value) in override_map.iter() {
+            match (base_map.get_mut(key), value) {
+                (_, Value::Null) => {
+                    base_map.remove(key);
+                }
+                (Some(existing), Value::Object(_)) if existing.is_object() => {
+                    Self::coalesce(existing, value);
+                }
+                _ => {
+                    base_map.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    fn object_or_empty(value: Value) -> Value {
+        if value.is_object() {
+            value
+        } else {
+            Value::Object(serde_json::Map::new())
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use crate::constants::metrics::{METRICS_POLL_INTERVAL, METRICS_UNAVAILABLE_POLL_INTERVAL};
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::dynamic_resources::DynamicK8sResources;
+use crate::types::metrics::{
+    ContainerUsage, MetricsReport, MetricsStatus, NodeUsage, PodUsage, ResourceAmounts,
+};
+use crate::utils::quantity::parse_quantity;
+use k8s_openapi::api::core::v1::{Container, Node, Pod, PodSpec};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::chrono::Utc;
+use kube::api::{Api, ApiResource, DynamicObject, ListParams};
+use kube::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tauri::{AppHandle, Emitter};
+
+const METRICS_GROUP: &str = "metrics.k8s.io";
+const METRICS_VERSION: &str = "v1beta1";
+
+/// Reads pod and node usage from the metrics.k8s.io API (metrics-server) and joins it
+/// with the requests, limits and capacity from the core objects.
+pub struct K8sMetrics;
+
+impl K8sMetrics {
+    pub async fn pod_metrics(
+        context_name: String,
+        namespaces: Option<Vec<String>>,
+    ) -> Result<MetricsReport<PodUsage>, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        Ok(Self::collect_pods(client, namespaces).await)
+    }
+
+    pub async fn node_metrics(context_name: String) -> Result<MetricsReport<NodeUsage>, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        Ok(Self::collect_nodes(client).await)
+    }
+
+    /// Poll pod usage and emit a report on every round. Runs until the watch is aborted.
+    pub async fn watch_pod_metrics(
+        app_handle: AppHandle,
+        context_name: String,
+        namespaces: Option<Vec<String>>,
+        event_name: String,
+    ) -> Result<(), String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        loop {
+            let report: MetricsReport<PodUsage> =
+                Self::collect_pods(client.clone(), namespaces.clone()).await;
+            Self::emit_report(&app_handle, &event_name, &report);
+            tokio::time::sleep(Self::next_poll(report.status)).await;
+        }
+    }
+
+    pub async fn watch_node_metrics(
+        app_handle: AppHandle,
+        context_name: String,
+        event_name: String,
+    ) -> Result<(), String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        loop {
+            let report: MetricsReport<NodeUsage> = Self::collect_nodes(client.clone()).await;
+            Self::emit_report(&app_handle, &event_name, &report);
+            tokio::time::sleep(Self::next_poll(report.status)).await;
+        }
+    }
+
+    pub(crate) async fn collect_pods(
+        client: Client,
+        namespaces: Option<Vec<String>>,
+    ) -> MetricsReport<PodUsage> {
+        let targets: Vec<Option<String>> = match namespaces.filter(|v| !v.is_empty()) {
+            Some(v) => v.into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+
+        let mut samples: Vec<DynamicObject> = Vec::new();
+        let mut pods: Vec<Pod> = Vec::new();
+        for ns in targets {
+            match Self::pod_samples(client.clone(), ns.as_deref()).await {
+                Ok(items) => samples.extend(items),
+                Err(e) => return Self::failed_report(e),
+            }
+            let pods_api: Api<Pod> = K8sClient::api::<Pod>(client.clone(), ns).await;
+            match pods_api.list(&ListParams::default()).await {
+                Ok(list) => pods.extend(list.items),
+                Err(e) => return Self::error_report(format!("failed to list pods: {}", e)),
+            }
+        }
+        Self::pod_report(&samples, &pods)
+    }
+
+    pub(crate) async fn collect_nodes(client: Client) -> MetricsReport<NodeUsage> {
+        // Only pods that still hold their resources count towards node requests
+        let pods_api: Api<Pod> = Api::all(client.clone());
+        let lp: ListParams =
+            ListParams::default().fields("status.phase!=Succeeded,status.phase!=Failed");
+        let pods: Vec<Pod> = match pods_api.list(&lp).await {
+            Ok(list) => list.items,
+            Err(e) => return Self::error_report(format!("failed to list pods: {}", e)),
+        };
+        Self::node_report(client, &pods).await
+    }
+
+    /// Pod and node reports for the whole cluster from a single pod list, for callers
+    /// that need both on every round.
+    pub(crate) async fn collect_all(
+        client: Client,
+    ) -> (MetricsReport<PodUsage>, MetricsReport<NodeUsage>) {
+        let pods_api: Api<Pod> = Api::all(client.clone());
+        let pods: Vec<Pod> = match pods_api.list(&ListParams::default()).await {
+            Ok(list) => list.items,
+            Err(e) => {
+                let message: String = format!("failed to list pods: {}", e);
+                return (Self::error_report(message.clone()), Self::error_report(message));
+            }
+        };
+        let (samples, nodes) =
+            tokio::join!(Self::pod_samples(client.clone(), None), Self::node_report(client, &pods));
+        let pods: MetricsReport<PodUsage> = match samples {
+            Ok(samples) => Self::pod_report(&samples, &pods),
+            Err(e) => Self::failed_report(e),
+        };
+        (pods, nodes)
+    }
+
+    async fn pod_samples(
+        client: Client,
+        namespace: Option<&str>,
+    ) -> Result<Vec<DynamicObject>, kube::Error> {
+        let ar: ApiResource =
+            DynamicK8sResources::api_resource(METRICS_GROUP, METRICS_VERSION, "PodMetrics", "pods");
+        let metrics_api: Api<DynamicObject> = match namespace {
+            Some(n) => Api::namespaced_with(client, n, &ar),
+            None => Api::all_with(client, &ar),
+        };
+        Ok(metrics_api.list(&ListParams::default()).await?.items)
+    }
+
+    fn pod_report(samples: &[DynamicObject], pods: &[Pod]) -> MetricsReport<PodUsage> {
+        let pods: HashMap<(String, String), &Pod> = pods
+            .iter()
+            .map(|pod| {
+                let key = (
+                    pod.metadata.namespace.clone().unwrap_or_default(),
+                    pod.metadata.name.clone().unwrap_or_default(),
+                );
+                (key, pod)
+            })
+            .collect();
+        let items: Vec<PodUsage> = samples
+            .iter()
+            .map(|sample| {
+                let namespace: String = sample.metadata.namespace.clone().unwrap_or_default();
+                let name: String = sample.metadata.name.clone().unwrap_or_default();
+                Self::pod_usage(sample, pods.get(&(namespace, name)).copied())
+            })
+            .collect();
+        Self::available_report(items)
+    }
+
+    async fn node_report(client: Client, pods: &[Pod]) -> MetricsReport<NodeUsage> {
+        let ar: ApiResource = DynamicK8sResources::api_resource(
+            METRICS_GROUP,
+            METRICS_VERSION,
+            "NodeMetrics",
+            "nodes",
+        );
+        let metrics_api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
+        let samples: Vec<DynamicObject> = match metrics_api.list(&ListParams::default()).await {
+            Ok(list) => list.items,
+            Err(e) => return Self::failed_report(e),
+        };
+        let nodes_api: Api<Node> = Api::all(client);
+        let nodes: Vec<Node> = match nodes_api.list(&ListParams::default()).await {
+            Ok(list) => list.items,
+            Err(e) => return Self::error_report(format!("failed to list nodes: {}", e)),
+        };
+        let mut pods_by_node: HashMap<String, Vec<&Pod>> = HashMap::new();
+        for pod in pods.iter() {
+            // Finished pods no longer hold their resources
+            let phase: Option<&str> = pod.status.as_ref().and_then(|s| s.phase.as_deref());
+            if matches!(phase, Some("Succeeded") | Some("Failed")) {
+                continue;
+            }
+            if let Some(node) = pod.spec.as_ref().and_then(|s| s.node_name.clone()) {
+                pods_by_node.entry(node).or_default().push(pod);
+            }
+        }
+
+        let items: Vec<NodeUsage> = samples
+            .iter()
+            .map(|sample| {
+                let name: String = sample.metadata.name.clone().unwrap_or_default();
+                let node: Option<&Node> =
+                    nodes.iter().find(|n| n.metadata.name.as_deref() == Some(name.as_str()));
+                let status = node.and_then(|n| n.status.as_ref());
+                let node_pods: &[&Pod] =
+                    pods_by_node.get(&name).map(|v| v.as_slice()).unwrap_or(&[]);
+                let specs: Vec<&PodSpec> =
+                    node_pods.iter().filter_map(|p| p.spec.as_ref()).collect();
+                NodeUsage {
+                    timestamp: Self::str_field(&sample.data, "timestamp"),
+                    window: Self::str_field(&sample.data, "window"),
+                    usage: Self::value_amounts(sample.data.get("usage")),
+                    capacity: Self::quantity_amounts(status.and_then(|s| s.capacity.as_ref())),
+                    allocatable: Self::quantity_amounts(
+                        status.and_then(|s| s.allocatable.as_ref()),
+                    ),
+                    requests: Self::sum(
+                        specs.iter().map(|s| Self::pod_amounts(s, Self::requests, false)),
+                        false,
+                    ),
+                    limits: Self::sum(
+                        specs.iter().map(|s| Self::pod_amounts(s, Self::limits, true)),
+                        true,
+                    ),
+                    pod_count: node_pods.len(),
+                    name,
+                }
+            })
+            .collect();
+        Self::available_report(items)
+    }
+
+    fn pod_usage(sample: &DynamicObject, pod: Option<&Pod>) -> PodUsage {
+        let spec_containers: &[Container] =
+            pod.and_then(|p| p.spec.as_ref()).map(|s| s.containers.as_slice()).unwrap_or(&[]);
+        let containers: Vec<ContainerUsage> = sample
+            .data
+            .get("containers")
+            .and_then(|c| c.as_array())
+            .map(|list| {
+                list.iter()
+                    .map(|c| {
+                        let name: String =
+                            c.get("name").and_then(|n| n.as_str()).unwrap_or_default().to_string();
+                        let spec: Option<&Container> =
+                            spec_containers.iter().find(|sc| sc.name == name);
+                        ContainerUsage {
+                            usage: Self::value_amounts(c.get("usage")),
+                            requests: spec.map(Self::requests).unwrap_or_default(),
+                            limits: spec.map(Self::limits).unwrap_or_default(),
+                            name,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        PodUsage {
+            name: sample.metadata.name.clone().unwrap_or_default(),
+            namespace: sample.metadata.namespace.clone().unwrap_or_default(),
+            node_name: pod.and_then(|p| p.spec.as_ref()).and_then(|s| s.node_name.clone()),
+            timestamp: Self::str_field(&sample.data, "timestamp"),
+            window: Self::str_field(&sample.data, "window"),
+            usage: Self::sum(containers.iter().map(|c| c.usage.clone()), false),
+            requests: Self::sum(spec_containers.iter().map(Self::requests), false),
+            limits: Self::sum(spec_containers.iter().map(Self::limits), true),
+            containers,
+        }
+    }
+
+    // What a pod holds on its node, as the scheduler counts it: the larger of its biggest
+    // init container and the sum of its containers, plus the RuntimeClass overhead.
+    fn pod_amounts(
+        spec: &PodSpec,
+        amounts: fn(&Container) -> ResourceAmounts,
+        strict: bool,
+    ) -> ResourceAmounts {
+        let mut held: ResourceAmounts = Self::sum(spec.containers.iter().map(amounts), strict);
+        for init in spec.init_containers.iter().flatten() {
+            held = Self::max(held, amounts(init), strict);
+        }
+        let overhead: ResourceAmounts = Self::quantity_amounts(spec.overhead.as_ref());
+        let add = |value: Option<f64>, extra: Option<f64>| -> Option<f64> {
+            match value {
+                Some(v) => Some(v + extra.unwrap_or(0.0)),
+                // A missing limit stays unbounded; a missing request is just the overhead
+                None if strict => None,
+                None => extra,
+            }
+        };
+        ResourceAmounts {
+            cpu: add(held.cpu, overhead.cpu),
+            memory: add(held.memory, overhead.memory),
+        }
+    }
+
+    // Per-resource maximum. With `strict`, a missing value on either side wins.
+    fn max(a: ResourceAmounts, b: ResourceAmounts, strict: bool) -> ResourceAmounts {
+        let pick = |x: Option<f64>, y: Option<f64>| -> Option<f64> {
+            match (x, y) {
+                (Some(x), Some(y)) => Some(x.max(y)),
+                _ if strict => None,
+                (x, y) => x.or(y),
+            }
+        };
+        ResourceAmounts {
+            cpu: pick(a.cpu, b.cpu),
+            memory: pick(a.memory, b.memory),
+        }
+    }
+
+    fn requests(container: &Container) -> ResourceAmounts {
+        Self::quantity_amounts(container.resources.as_ref().and_then(|r| r.requests.as_ref()))
+    }
+
+    fn limits(container: &Container) -> ResourceAmounts {
+        Self::quantity_amounts(container.resources.as_ref().and_then(|r| r.limits.as_ref()))
+    }
+
+    fn quantity_amounts(map: Option<&BTreeMap<String, Quantity>>) -> ResourceAmounts {
+        ResourceAmounts {
+            cpu: map.and_then(|m| m.get("cpu")).and_then(|q| parse_quantity(&q.0)),
+            memory: map.and_then(|m| m.get("memory")).and_then(|q| parse_quantity(&q.0)),
+        }
+    }
+
+    fn value_amounts(usage: Option<&Value>) -> ResourceAmounts {
+        let get = |key: &str| -> Option<f64> {
+            usage.and_then(|u| u.get(key)).and_then(|q| q.as_str()).and_then(parse_quantity)
+        };
+        ResourceAmounts {
+            cpu: get("cpu"),
+            memory: get("memory"),
+        }
+    }
+
+    // Totals of the given amounts. With `strict`, one missing value makes the total
+    // unknown (a container without a limit leaves the pod unbounded).
+    fn sum(amounts: impl Iterator<Item = ResourceAmounts>, strict: bool) -> ResourceAmounts {
+        let mut total: ResourceAmounts = ResourceAmounts::default();
+        let (mut cpu_missing, mut memory_missing): (bool, bool) = (false, false);
+        let mut any: bool = false;
+        for a in amounts {
+            any = true;
+            match a.cpu {
+                Some(v) => total.cpu = Some(total.cpu.unwrap_or(0.0) + v),
+                None => cpu_missing = true,
+            }
+            match a.memory {
+                Some(v) => total.memory = Some(total.memory.unwrap_or(0.0) + v),
+                None => memory_missing = true,
+            }
+        }
+        if strict && any {
+            if cpu_missing {
+                total.cpu = None;
+            }
+            if memory_missing {
+                total.memory = None;
+            }
+        }
+        total
+    }
+
+    fn str_field(data: &Value, key: &str) -> Option<String> {
+        data.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+    }
+
+    fn next_poll(status: MetricsStatus) -> std::time::Duration {
+        match status {
+            MetricsStatus::Unavailable => METRICS_UNAVAILABLE_POLL_INTERVAL,
+            _ => METRICS_POLL_INTERVAL,
+        }
+    }
+
+    fn available_report<T>(items: Vec<T>) -> MetricsReport<T> {
+        MetricsReport {
+            status: MetricsStatus::Available,
+            message: None,
+            collected_at: Utc::now().to_rfc3339(),
+            items,
+        }
+    }
+
+    fn error_report<T>(message: String) -> MetricsReport<T> {
+        MetricsReport {
+            status: MetricsStatus::Error,
+            message: Some(message),
+            collected_at: Utc::now().to_rfc3339(),
+            items: vec![],
+        }
+    }
+
+    // A missing (404) or unready (503) API group means no metrics provider is running.
+    fn failed_report<T>(e: kube::Error) -> MetricsReport<T> {
+        match &e {
+            kube::Error::Api(ae) if ae.code == 404 || ae.code == 503 => MetricsReport {
+                status: MetricsStatus::Unavailable,
+                message: Some(format!(
+                    "The metrics.k8s.io API is not available; install metrics-server to see resource usage ({})",
+                    ae.message
+                )),
+                collected_at: Utc::now().to_rfc3339(),
+                items: vec![],
+            },
+            _ => Self::error_report(format!("failed to read metrics: {}", e)),
+        }
+    }
+
+    fn emit_report<T: Serialize>(
+        app_handle: &AppHandle,
+        event_name: &str,
+        report: &MetricsReport<T>,
+    ) {
+        let event: Value = serde_json::json!({
+            "type": "METRICS",
+            "object": report,
+        });
+        let _ = app_handle.emit(event_name, event);
+    }
+}
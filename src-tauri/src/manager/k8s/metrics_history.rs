@@ -0,0 +1,218 @@
+use crate::constants::metrics::{
+    METRICS_HISTORY_MAX_POINTS, METRICS_HISTORY_PRUNE_INTERVAL, METRICS_HISTORY_SAMPLE_INTERVAL,
+    METRICS_HISTORY_TIERS, METRICS_UNAVAILABLE_POLL_INTERVAL,
+};
+use crate::databases::metrics_history::{HistorySample, MetricsHistoryRepo};
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::metrics::K8sMetrics;
+use crate::state::AppState;
+use crate::types::metrics::{
+    MetricsHistorySeries, MetricsHistorySettings, MetricsReport, MetricsSeriesKind, MetricsStatus,
+    NodeUsage, PodUsage,
+};
+use crate::utils::metrics_history::MetricsRecorderManager;
+use k8s_openapi::chrono::Utc;
+use kube::Client;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+/// Samples pod, container and node usage of connected contexts into the local database
+/// and serves downsampled ranges for charts.
+pub struct K8sMetricsHistory;
+
+impl K8sMetricsHistory {
+    pub async fn start_recording(app_handle: AppHandle, context_name: String) {
+        let recorders = app_handle.state::<MetricsRecorderManager>();
+        recorders
+            .start(context_name.clone(), Self::record_loop(app_handle.clone(), context_name))
+            .await;
+    }
+
+    pub async fn stop_recording(app_handle: &AppHandle, context_name: &str) {
+        app_handle.state::<MetricsRecorderManager>().stop(context_name).await;
+    }
+
+    pub fn settings(
+        app_state: &AppState,
+        context_name: &str,
+    ) -> Result<MetricsHistorySettings, String> {
+        app_state.metrics_history.settings(context_name).map_err(|e| e.to_string())
+    }
+
+    pub fn save_settings(
+        app_state: &AppState,
+        context_name: &str,
+        settings: MetricsHistorySettings,
+    ) -> Result<MetricsHistorySettings, String> {
+        let max_hours: u64 = METRICS_HISTORY_TIERS.iter().map(|(_, r)| r / 3600).max().unwrap_or(0);
+        if settings.retention_hours == 0 || settings.retention_hours as u64 > max_hours {
+            return Err(format!("retention must be between 1 and {} hours", max_hours));
+        }
+        if settings.max_series == 0 {
+            return Err("max series must be at least 1".to_string());
+        }
+        app_state
+            .metrics_history
+            .save_settings(context_name, &settings)
+            .map_err(|e| e.to_string())?;
+        // A shorter retention applies right away
+        app_state
+            .metrics_history
+            .prune(context_name, Self::now(), &Self::tiers(&settings))
+            .map_err(|e| e.to_string())?;
+        Ok(settings)
+    }
+
+    pub fn clear(app_state: &AppState, context_name: &str) -> Result<(), String> {
+        app_state.metrics_history.clear(context_name).map_err(|e| e.to_string())
+    }
+
+    pub fn list_series(
+        app_state: &AppState,
+        context_name: &str,
+        kind: MetricsSeriesKind,
+    ) -> Result<Vec<String>, String> {
+        let settings: MetricsHistorySettings = Self::settings(app_state, context_name)?;
+        let since: u64 = Self::now().saturating_sub(settings.retention_hours as u64 * 3600);
+        let mut keys: Vec<String> = app_state
+            .metrics_history
+            .series_keys(context_name, kind, since)
+            .map_err(|e| e.to_string())?;
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Usage of the given series between `from` and `to` (unix milliseconds). The finest
+    /// tier that still covers `from` and stays under the point limit is used.
+    pub fn query(
+        app_state: &AppState,
+        context_name: &str,
+        kind: MetricsSeriesKind,
+        keys: Vec<String>,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<MetricsHistorySeries>, String> {
+        if from > to {
+            return Err("query start must not be after its end".to_string());
+        }
+        let now: u64 = Self::now();
+        let from: u64 = (from.max(0) / 1000) as u64;
+        let to: u64 = (to.max(0) / 1000) as u64;
+        let settings: MetricsHistorySettings = Self::settings(app_state, context_name)?;
+        let tiers: Vec<(u64, u64)> = Self::tiers(&settings);
+        let (resolution, _) = tiers
+            .iter()
+            .find(|(resolution, retention)| {
+                now.saturating_sub(from) <= *retention
+                    && (to - from) / resolution <= METRICS_HISTORY_MAX_POINTS
+            })
+            .or(tiers.last())
+            .copied()
+            .ok_or_else(|| "no metrics history tiers configured".to_string())?;
+
+        keys.into_iter()
+            .map(|key| {
+                let points = app_state
+                    .metrics_history
+                    .query(context_name, resolution, kind, &key, from, to)
+                    .map_err(|e| e.to_string())?;
+                Ok(MetricsHistorySeries {
+                    kind,
+                    key,
+                    resolution_seconds: resolution,
+                    points,
+                })
+            })
+            .collect()
+    }
+
+    async fn record_loop(app_handle: AppHandle, context_name: String) {
+        let repo: MetricsHistoryRepo = match app_handle.try_state::<AppState>() {
+            Some(app_state) => app_state.metrics_history.clone(),
+            None => return,
+        };
+        let client: Client = match K8sClient::for_context(&context_name).await {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("metrics history: {}: {}", context_name, e);
+                return;
+            }
+        };
+        let mut last_prune: Option<Instant> = None;
+        loop {
+            let settings: MetricsHistorySettings = repo.settings(&context_name).unwrap_or_default();
+            let mut interval = METRICS_HISTORY_SAMPLE_INTERVAL;
+            if settings.enabled {
+                let (pods, nodes) = K8sMetrics::collect_all(client.clone()).await;
+                if pods.status == MetricsStatus::Unavailable {
+                    interval = METRICS_UNAVAILABLE_POLL_INTERVAL;
+                }
+                let samples: Vec<HistorySample> = Self::samples(&pods, &nodes);
+                let tiers: Vec<(u64, u64)> = Self::tiers(&settings);
+                let resolutions: Vec<u64> = tiers.iter().map(|(r, _)| *r).collect();
+                if let Err(e) = repo.record(
+                    &context_name,
+                    Self::now(),
+                    &samples,
+                    &resolutions,
+                    settings.max_series,
+                ) {
+                    eprintln!("metrics history: failed to record {}: {}", context_name, e);
+                }
+                if last_prune.is_none_or(|t| t.elapsed() >= METRICS_HISTORY_PRUNE_INTERVAL) {
+                    if let Err(e) = repo.prune(&context_name, Self::now(), &tiers) {
+                        eprintln!("metrics history: failed to prune {}: {}", context_name, e);
+                    }
+                    last_prune = Some(Instant::now());
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    fn samples(
+        pods: &MetricsReport<PodUsage>,
+        nodes: &MetricsReport<NodeUsage>,
+    ) -> Vec<HistorySample> {
+        let mut samples: Vec<HistorySample> = Vec::new();
+        for pod in pods.items.iter() {
+            let pod_key: String = format!("{}/{}", pod.namespace, pod.name);
+            for container in pod.containers.iter() {
+                samples.push(HistorySample {
+                    kind: MetricsSeriesKind::Container,
+                    key: format!("{}/{}", pod_key, container.name),
+                    cpu: container.usage.cpu,
+                    memory: container.usage.memory,
+                });
+            }
+            samples.push(HistorySample {
+                kind: MetricsSeriesKind::Pod,
+                key: pod_key,
+                cpu: pod.usage.cpu,
+                memory: pod.usage.memory,
+            });
+        }
+        for node in nodes.items.iter() {
+            samples.push(HistorySample {
+                kind: MetricsSeriesKind::Node,
+                key: node.name.clone(),
+                cpu: node.usage.cpu,
+                memory: node.usage.memory,
+            });
+        }
+        samples
+    }
+
+    // Built-in tiers with their retention capped by the context's setting.
+    fn tiers(settings: &MetricsHistorySettings) -> Vec<(u64, u64)> {
+        let limit: u64 = settings.retention_hours as u64 * 3600;
+        METRICS_HISTORY_TIERS
+            .iter()
+            .map(|(resolution, retention)| (*resolution, (*retention).min(limit)))
+            .collect()
+    }
+
+    fn now() -> u64 {
+        Utc::now().timestamp().max(0) as u64
+    }
+}
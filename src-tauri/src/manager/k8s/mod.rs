@@ -2,7 +2,21 @@ pub mod client;
 pub mod cluster_resources;
 pub mod contexts;
 pub mod dynamic_resources;
+pub mod eviction;
 pub mod helm;
+pub mod helm_chart;
+pub mod helm_diff;
+pub mod helm_resources;
+pub mod helm_storage;
+pub mod metrics;
+pub mod metrics_history;
+pub mod nodes;
+pub mod pod_files;
 pub mod pod_resources;
 pub mod port_forward;
+pub mod prometheus;
 pub mod resources;
+pub mod rollouts;
+pub mod scaling;
+pub mod service_proxy;
+pub mod termination;
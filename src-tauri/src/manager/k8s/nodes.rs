@@ -0,0 +1,397 @@
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::cluster_resources::K8sClusterResources;
+use crate::manager::k8s::eviction::{EvictionOutcome, EvictionStop, K8sEviction};
+use crate::types::nodes::{
+    DrainPodProgress, DrainPodStatus, NodeDrainOptions, NodeDrainReport, NodeTaintRemoval,
+    NodeTaintSpec,
+};
+use crate::utils::validation::{validate_label_value, validate_qualified_name};
+use futures_util::future::join_all;
+use k8s_openapi::api::core::v1::{Node, Pod, Taint};
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use kube::api::{Api, ListParams, Patch, PatchParams};
+use kube::Client;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio_util::sync::CancellationToken;
+
+const TAINT_EFFECTS: [&str; 3] = ["NoSchedule", "PreferNoSchedule", "NoExecute"];
+
+// What drain does with a pod on the node
+enum DrainAction {
+    Evict,
+    Skip(String),
+    // Refuse to drain the node at all, like kubectl without the matching flag
+    Refuse(String),
+}
+
+/// Node maintenance: cordon, uncordon, drain, labels and taints.
+pub struct K8sNodes;
+
+impl K8sNodes {
+    /// Mark nodes (un)schedulable. Returns one result per node.
+    pub async fn set_unschedulable(
+        context_name: String,
+        node_names: Vec<String>,
+        unschedulable: bool,
+    ) -> Result<Vec<Result<String, String>>, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let api: Api<Node> = Api::all(client);
+        let futures = node_names.into_iter().map(|name| {
+            let api: Api<Node> = api.clone();
+            async move { Self::patch_unschedulable(&api, &name, unschedulable).await.map(|_| name) }
+        });
+        Ok(join_all(futures).await)
+    }
+
+    /// Set and remove labels on every given node with one merge patch each. Input is
+    /// validated up front; nothing is patched when any key or value is invalid.
+    pub async fn update_labels(
+        context_name: String,
+        node_names: Vec<String>,
+        set: BTreeMap<String, String>,
+        remove: Vec<String>,
+    ) -> Result<Vec<Result<String, String>>, String> {
+        let mut labels: Map<String, Value> = Map::new();
+        for (key, value) in set.iter() {
+            validate_qualified_name(key)?;
+            validate_label_value(value)?;
+            labels.insert(key.clone(), Value::String(value.clone()));
+        }
+        for key in remove.iter() {
+            if set.contains_key(key) {
+                return Err(format!("label {} is both set and removed", key));
+            }
+            validate_qualified_name(key)?;
+            labels.insert(key.clone(), Value::Null);
+        }
+        if labels.is_empty() {
+            return Err("no label changes given".to_string());
+        }
+
+        let patch: Value = json!({ "metadata": { "labels": labels } });
+        let futures = node_names.into_iter().map(|name| {
+            let (context_name, patch) = (context_name.clone(), patch.clone());
+            async move {
+                K8sClusterResources::<Node>::patch(
+                    context_name,
+                    name.clone(),
+                    patch,
+                    "merge".into(),
+                )
+                .await
+                .map(|_| name)
+            }
+        });
+        Ok(join_all(futures).await)
+    }
+
+    /// Add, update and remove taints on every given node. Taints are a list, so each node
+    /// is read first and written back guarded by its resourceVersion.
+    pub async fn update_taints(
+        context_name: String,
+        node_names: Vec<String>,
+        set: Vec<NodeTaintSpec>,
+        remove: Vec<NodeTaintRemoval>,
+    ) -> Result<Vec<Result<String, String>>, String> {
+        for (i, t) in set.iter().enumerate() {
+            validate_qualified_name(&t.key)?;
+            validate_label_value(t.value.as_deref().unwrap_or_default())?;
+            Self::validate_effect(&t.effect)?;
+            if set[..i].iter().any(|o| o.key == t.key && o.effect == t.effect) {
+                return Err(format!("taint {}:{} is given twice", t.key, t.effect));
+            }
+        }
+        for r in remove.iter() {
+            validate_qualified_name(&r.key)?;
+            if let Some(effect) = r.effect.as_deref() {
+                Self::validate_effect(effect)?;
+            }
+        }
+        if set.is_empty() && remove.is_empty() {
+            return Err("no taint changes given".to_string());
+        }
+
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let api: Api<Node> = Api::all(client);
+        let futures = node_names.into_iter().map(|name| {
+            let (api, context_name) = (api.clone(), context_name.clone());
+            let (set, remove) = (&set, &remove);
+            async move {
+                let node: Node = api
+                    .get(&name)
+                    .await
+                    .map_err(|e| format!("failed to get node {}: {}", name, e))?;
+                let current: Vec<Taint> =
+                    node.spec.as_ref().and_then(|s| s.taints.clone()).unwrap_or_default();
+                let taints: Vec<Taint> = Self::apply_taints(&current, set, remove);
+                if taints == current {
+                    return Ok(name);
+                }
+                // A concurrent change to the node makes the patch fail with a conflict
+                let patch: Value = json!({
+                    "metadata": { "resourceVersion": node.metadata.resource_version },
+                    "spec": { "taints": taints },
+                });
+                K8sClusterResources::<Node>::patch(
+                    context_name,
+                    name.clone(),
+                    patch,
+                    "merge".into(),
+                )
+                .await
+                .map(|_| name)
+            }
+        });
+        Ok(join_all(futures).await)
+    }
+
+    /// Cordon the node and evict its pods, honouring PodDisruptionBudgets. Progress is
+    /// emitted per pod on `event_name`; the final report is emitted and returned.
+    /// Cancelling `cancel` stops retrying and waiting; the node stays cordoned.
+    pub async fn drain(
+        app_handle: AppHandle,
+        context_name: String,
+        node_name: String,
+        options: NodeDrainOptions,
+        event_name: String,
+        cancel: CancellationToken,
+    ) -> Result<NodeDrainReport, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let nodes_api: Api<Node> = Api::all(client.clone());
+        Self::patch_unschedulable(&nodes_api, &node_name, true).await?;
+
+        let pods_api: Api<Pod> = Api::all(client.clone());
+        let lp: ListParams = ListParams::default().fields(&format!("spec.nodeName={}", node_name));
+        let pods: Vec<Pod> = pods_api
+            .list(&lp)
+            .await
+            .map_err(|e| format!("failed to list pods on {}: {}", node_name, e))?
+            .items;
+
+        let mut to_evict: Vec<Pod> = Vec::new();
+        let mut progress: Vec<DrainPodProgress> = Vec::new();
+        let mut refusals: Vec<String> = Vec::new();
+        for pod in pods {
+            match Self::action(&pod, &options) {
+                DrainAction::Evict => to_evict.push(pod),
+                DrainAction::Skip(reason) => {
+                    progress.push(Self::progress(&pod, DrainPodStatus::Skipped, Some(reason)))
+                }
+                DrainAction::Refuse(reason) => refusals.push(format!(
+                    "{}/{}: {}",
+                    pod.metadata.namespace.as_deref().unwrap_or_default(),
+                    pod.metadata.name.as_deref().unwrap_or_default(),
+                    reason
+                )),
+            }
+        }
+        // Nothing is evicted when any pod would block the drain; the node stays cordoned
+        if !refusals.is_empty() {
+            return Err(format!("cannot drain {}:\n{}", node_name, refusals.join("\n")));
+        }
+        for p in progress.iter() {
+            Self::emit_progress(&app_handle, &event_name, p);
+        }
+
+        // Only used to name the budget in blocked messages; eviction enforces them either way
+        let pdb_api: Api<PodDisruptionBudget> = Api::all(client.clone());
+        let budgets: Vec<PodDisruptionBudget> =
+            pdb_api.list(&ListParams::default()).await.map(|l| l.items).unwrap_or_default();
+        let stop: EvictionStop = EvictionStop {
+            deadline: options
+                .timeout_seconds
+                .filter(|t| *t > 0)
+                .map(|t| Instant::now() + Duration::from_secs(t)),
+            cancel,
+        };
+
+        let futures = to_evict.iter().map(|pod| {
+            Self::drain_pod(&app_handle, &event_name, &client, pod, &budgets, &options, &stop)
+        });
+        progress.extend(join_all(futures).await);
+
+        let report: NodeDrainReport = NodeDrainReport {
+            node: node_name,
+            cancelled: stop.cancel.is_cancelled(),
+            completed: progress
+                .iter()
+                .all(|p| matches!(p.status, DrainPodStatus::Evicted | DrainPodStatus::Skipped)),
+            pods: progress,
+        };
+        let _ = app_handle.emit(&event_name, json!({ "type": "DRAIN_DONE", "object": &report }));
+        Ok(report)
+    }
+
+    async fn drain_pod(
+        app_handle: &AppHandle,
+        event_name: &str,
+        client: &Client,
+        pod: &Pod,
+        budgets: &[PodDisruptionBudget],
+        options: &NodeDrainOptions,
+        stop: &EvictionStop,
+    ) -> DrainPodProgress {
+        let name: String = pod.metadata.name.clone().unwrap_or_default();
+        let api: Api<Pod> =
+            K8sClient::api::<Pod>(client.clone(), pod.metadata.namespace.clone()).await;
+        let emit = |status: DrainPodStatus, message: Option<String>, pdbs: Vec<String>| {
+            let mut p: DrainPodProgress = Self::progress(pod, status, message);
+            p.disruption_budgets = pdbs;
+            Self::emit_progress(app_handle, event_name, &p);
+            p
+        };
+
+        // Pods already being deleted only need to finish terminating
+        if pod.metadata.deletion_timestamp.is_none() {
+            emit(DrainPodStatus::Evicting, None, vec![]);
+            let outcome: EvictionOutcome = K8sEviction::evict_until(
+                &api,
+                &name,
+                options.grace_period_seconds,
+                stop,
+                |message| {
+                    emit(
+                        DrainPodStatus::Blocked,
+                        Some(message.to_string()),
+                        K8sEviction::matching_budgets(pod, budgets),
+                    );
+                },
+            )
+            .await;
+            match outcome {
+                EvictionOutcome::Accepted => {}
+                EvictionOutcome::Gone => return emit(DrainPodStatus::Evicted, None, vec![]),
+                EvictionOutcome::Blocked(message) => {
+                    return emit(
+                        DrainPodStatus::Blocked,
+                        Some(message),
+                        K8sEviction::matching_budgets(pod, budgets),
+                    )
+                }
+                EvictionOutcome::Failed(message) => {
+                    return emit(DrainPodStatus::Failed, Some(message), vec![])
+                }
+            }
+        }
+
+        emit(DrainPodStatus::Terminating, None, vec![]);
+        match K8sEviction::wait_deleted(&api, &name, pod.metadata.uid.as_deref(), stop).await {
+            Ok(()) => emit(DrainPodStatus::Evicted, None, vec![]),
+            Err(message) => emit(DrainPodStatus::Failed, Some(message), vec![]),
+        }
+    }
+
+    // Same rules as kubectl drain: mirror pods are never touched, DaemonSet pods,
+    // unmanaged pods and emptyDir data each need an explicit opt-in.
+    fn action(pod: &Pod, options: &NodeDrainOptions) -> DrainAction {
+        let annotations = pod.metadata.annotations.as_ref();
+        if annotations.is_some_and(|a| a.contains_key("kubernetes.io/config.mirror")) {
+            return DrainAction::Skip("static (mirror) pod".to_string());
+        }
+        let controller =
+            pod.metadata.owner_references.iter().flatten().find(|o| o.controller == Some(true));
+        if controller.is_some_and(|o| o.kind == "DaemonSet") {
+            return if options.ignore_daemon_sets {
+                DrainAction::Skip("managed by a DaemonSet".to_string())
+            } else {
+                DrainAction::Refuse("managed by a DaemonSet; enable ignore DaemonSets".to_string())
+            };
+        }
+        let phase: Option<&str> = pod.status.as_ref().and_then(|s| s.phase.as_deref());
+        // Finished pods hold no resources and lose nothing when removed
+        if matches!(phase, Some("Succeeded") | Some("Failed")) {
+            return DrainAction::Evict;
+        }
+        if controller.is_none() && !options.force {
+            return DrainAction::Refuse(
+                "not managed by a controller and will not be recreated; enable force".to_string(),
+            );
+        }
+        let uses_empty_dir: bool = pod
+            .spec
+            .as_ref()
+            .and_then(|s| s.volumes.as_ref())
+            .is_some_and(|v| v.iter().any(|vol| vol.empty_dir.is_some()));
+        if uses_empty_dir && !options.delete_empty_dir_data {
+            return DrainAction::Refuse(
+                "uses emptyDir storage that is lost on eviction; enable delete emptyDir data"
+                    .to_string(),
+            );
+        }
+        DrainAction::Evict
+    }
+
+    fn apply_taints(
+        current: &[Taint],
+        set: &[NodeTaintSpec],
+        remove: &[NodeTaintRemoval],
+    ) -> Vec<Taint> {
+        let mut taints: Vec<Taint> = current
+            .iter()
+            .filter(|t| {
+                !remove
+                    .iter()
+                    .any(|r| r.key == t.key && r.effect.as_ref().is_none_or(|e| *e == t.effect))
+            })
+            .cloned()
+            .collect();
+        for spec in set {
+            let value: Option<String> = spec.value.clone().filter(|v| !v.is_empty());
+            match taints.iter_mut().find(|t| t.key == spec.key && t.effect == spec.effect) {
+                Some(t) => t.value = value,
+                None => taints.push(Taint {
+                    key: spec.key.clone(),
+                    value,
+                    effect: spec.effect.clone(),
+                    time_added: None,
+                }),
+            }
+        }
+        taints
+    }
+
+    fn validate_effect(effect: &str) -> Result<(), String> {
+        if TAINT_EFFECTS.contains(&effect) {
+            Ok(())
+        } else {
+            Err(format!(
+                "invalid taint effect {:?}; use one of {}",
+                effect,
+                TAINT_EFFECTS.join(", ")
+            ))
+        }
+    }
+
+    async fn patch_unschedulable(
+        api: &Api<Node>,
+        name: &str,
+        unschedulable: bool,
+    ) -> Result<(), String> {
+        // Uncordon removes the field, as kubectl does
+        let value: Value = if unschedulable { json!(true) } else { Value::Null };
+        let patch: Value = json!({ "spec": { "unschedulable": value } });
+        api.patch(name, &PatchParams::default(), &Patch::Merge(patch)).await.map(|_| ()).map_err(
+            |e| match e {
+                kube::Error::Api(ae) => format!("{}: {}", name, ae.message),
+                other => format!("{}: {}", name, other),
+            },
+        )
+    }
+
+    fn progress(pod: &Pod, status: DrainPodStatus, message: Option<String>) -> DrainPodProgress {
+        DrainPodProgress {
+            namespace: pod.metadata.namespace.clone().unwrap_or_default(),
+            name: pod.metadata.name.clone().unwrap_or_default(),
+            status,
+            message,
+            disruption_budgets: vec![],
+        }
+    }
+
+    fn emit_progress(app_handle: &AppHandle, event_name: &str, progress: &DrainPodProgress) {
+        let _ = app_handle.emit(event_name, json!({ "type": "DRAIN_POD", "object": progress }));
+    }
+}
@@ -0,0 +1,453 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::manager::k8s::pod_resources::{ExecOutput, PodResources};
+use crate::types::pod_files::{PodFileEntry, PodFileListing, PodFilePreview};
+
+// Field separator for `stat -c` output. The ASCII unit separator does not show up in
+// real file names, so names containing spaces or pipes survive the round trip.
+const FIELD_SEP: char = '\u{1f}';
+const STAT_FORMAT: &str = "%F\u{1f}%s\u{1f}%a\u{1f}%Y\u{1f}%U\u{1f}%G\u{1f}%n\u{1f}%N";
+
+// Exit code used by the scripts below to signal that `stat` is missing (busybox-less images).
+const EXIT_NO_STAT: i32 = 127;
+
+const DEFAULT_PREVIEW_KB: u32 = 64;
+const MAX_PREVIEW_KB: u32 = 1024;
+
+/// Browse a running container's filesystem through exec, using only POSIX `sh` plus
+/// `stat`/`ls`/`head`/`dd` so it works on both GNU coreutils and busybox images.
+pub struct PodFiles;
+
+impl PodFiles {
+    pub async fn list_dir(
+        context_name: String,
+        namespace: String,
+        pod_name: String,
+        container_name: Option<String>,
+        path: String,
+    ) -> Result<PodFileListing, String> {
+        let path: String = Self::normalize_path(&path);
+
+        // Collect every entry (including dotfiles) into "$@" and stat them in one call.
+        let script: String = format!(
+            r#"cd -- "$1" || exit 2
+command -v stat >/dev/null 2>&1 || exit {no_stat}
+set --
+for f in .[!.]* ..?* *; do
+  {{ [ -e "$f" ] || [ -L "$f" ]; }} && set -- "$@" "$f"
+done
+[ $# -eq 0 ] && exit 0
+exec stat -c '{fmt}' -- "$@""#,
+            no_stat = EXIT_NO_STAT,
+            fmt = STAT_FORMAT,
+        );
+
+        let out: ExecOutput = Self::run_script(
+            &context_name,
+            &namespace,
+            &pod_name,
+            &container_name,
+            &script,
+            &[&path],
+        )
+        .await?;
+
+        let mut entries: Vec<PodFileEntry> = if out.exit_code == EXIT_NO_STAT {
+            let ls_out: ExecOutput = Self::run_script(
+                &context_name,
+                &namespace,
+                &pod_name,
+                &container_name,
+                r#"exec ls -la -- "$1""#,
+                &[&path],
+            )
+            .await?;
+            Self::ensure_success(&ls_out, &path)?;
+            Self::parse_ls_output(&String::from_utf8_lossy(&ls_out.stdout), &path, false)
+        } else {
+            Self::ensure_success(&out, &path)?;
+            Self::parse_stat_output(&String::from_utf8_lossy(&out.stdout), &path)
+        };
+
+        // Directories first, then case-insensitive by name
+        entries.sort_by(|a, b| {
+            (b.kind == "directory")
+                .cmp(&(a.kind == "directory"))
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        Ok(PodFileListing { path, entries })
+    }
+
+    pub async fn stat(
+        context_name: String,
+        namespace: String,
+        pod_name: String,
+        container_name: Option<String>,
+        path: String,
+    ) -> Result<PodFileEntry, String> {
+        Self::stat_path(context_name, namespace, pod_name, container_name, path, false).await
+    }
+
+    // With `follow`, a symlink reports the type and size of the file it points to.
+    async fn stat_path(
+        context_name: String,
+        namespace: String,
+        pod_name: String,
+        container_name: Option<String>,
+        path: String,
+        follow: bool,
+    ) -> Result<PodFileEntry, String> {
+        let path: String = Self::normalize_path(&path);
+        let parent: String = Self::parent_path(&path);
+
+        let script: String = format!(
+            r#"command -v stat >/dev/null 2>&1 || exit {no_stat}
+exec stat {flags}-c '{fmt}' -- "$1""#,
+            no_stat = EXIT_NO_STAT,
+            flags = if follow { "-L " } else { "" },
+            fmt = STAT_FORMAT,
+        );
+
+        let out: ExecOutput = Self::run_script(
+            &context_name,
+            &namespace,
+            &pod_name,
+            &container_name,
+            &script,
+            &[&path],
+        )
+        .await?;
+
+        let entry: Option<PodFileEntry> = if out.exit_code == EXIT_NO_STAT {
+            let ls_out: ExecOutput = Self::run_script(
+                &context_name,
+                &namespace,
+                &pod_name,
+                &container_name,
+                if follow { r#"exec ls -ladL -- "$1""# } else { r#"exec ls -lad -- "$1""# },
+                &[&path],
+            )
+            .await?;
+            Self::ensure_success(&ls_out, &path)?;
+            Self::parse_ls_output(&String::from_utf8_lossy(&ls_out.stdout), &parent, true)
+                .into_iter()
+                .next()
+        } else {
+            Self::ensure_success(&out, &path)?;
+            Self::parse_stat_output(&String::from_utf8_lossy(&out.stdout), &parent)
+                .into_iter()
+                .next()
+        };
+
+        // `stat` and `ls -d` report the name as given; use the requested path verbatim.
+        entry
+            .map(|mut e| {
+                e.name = Self::base_name(&path);
+                e.path = path.clone();
+                e
+            })
+            .ok_or_else(|| format!("Failed to stat {}: unexpected output", path))
+    }
+
+    pub async fn read_preview(
+        context_name: String,
+        namespace: String,
+        pod_name: String,
+        container_name: Option<String>,
+        path: String,
+        max_kb: Option<u32>,
+    ) -> Result<PodFilePreview, String> {
+        let path: String = Self::normalize_path(&path);
+        // Follow symlinks so `truncated` compares against the target's size
+        let entry: PodFileEntry = Self::stat_path(
+            context_name.clone(),
+            namespace.clone(),
+            pod_name.clone(),
+            container_name.clone(),
+            path.clone(),
+            true,
+        )
+        .await?;
+        if entry.kind == "directory" {
+            return Err(format!("{} is a directory", path));
+        }
+
+        let limit: usize =
+            (max_kb.unwrap_or(DEFAULT_PREVIEW_KB).clamp(1, MAX_PREVIEW_KB) as usize) * 1024;
+        let limit_arg: String = limit.to_string();
+
+        let out: ExecOutput = Self::run_script(
+            &context_name,
+            &namespace,
+            &pod_name,
+            &container_name,
+            r#"if command -v head >/dev/null 2>&1; then exec head -c "$2" -- "$1"; fi
+exec dd if="$1" bs="$2" count=1 2>/dev/null"#,
+            &[&path, &limit_arg],
+        )
+        .await?;
+        Self::ensure_success(&out, &path)?;
+
+        let mut bytes: Vec<u8> = out.stdout;
+        bytes.truncate(limit);
+        let bytes_read: usize = bytes.len();
+        let truncated: bool = match entry.size {
+            Some(size) => size > bytes_read as u64,
+            None => bytes_read >= limit,
+        };
+
+        let (is_binary, encoding, content) = match Self::decode_text(&bytes, truncated) {
+            Some(text) => (false, "utf8", text),
+            None => (true, "base64", STANDARD.encode(&bytes)),
+        };
+
+        Ok(PodFilePreview {
+            path,
+            size: entry.size,
+            bytes_read,
+            truncated,
+            is_binary,
+            encoding: encoding.to_string(),
+            content,
+        })
+    }
+
+    async fn run_script(
+        context_name: &str,
+        namespace: &str,
+        pod_name: &str,
+        container_name: &Option<String>,
+        script: &str,
+        args: &[&str],
+    ) -> Result<ExecOutput, String> {
+        // Arguments are passed positionally ("$1", "$2") so paths are never shell-interpolated.
+        let mut command: Vec<String> =
+            vec!["sh".into(), "-c".into(), script.to_string(), "sh".into()];
+        command.extend(args.iter().map(|a| a.to_string()));
+        PodResources::exec_output(
+            context_name.to_string(),
+            namespace.to_string(),
+            pod_name.to_string(),
+            container_name.clone(),
+            command,
+        )
+        .await
+    }
+
+    fn ensure_success(out: &ExecOutput, path: &str) -> Result<(), String> {
+        if out.success() {
+            return Ok(());
+        }
+        let stderr: &str = out.stderr.trim();
+        if stderr.is_empty() {
+            Err(format!("Failed to access {}: exit code {}", path, out.exit_code))
+        } else {
+            Err(format!("Failed to access {}: {}", path, stderr))
+        }
+    }
+
+    // Text when the sample has no NUL bytes and is valid UTF-8; a multi-byte character
+    // cut off by the preview limit is tolerated.
+    fn decode_text(bytes: &[u8], truncated: bool) -> Option<String> {
+        if bytes.contains(&0) {
+            return None;
+        }
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Some(s.to_string()),
+            Err(e) if truncated && e.error_len().is_none() => {
+                Some(String::from_utf8_lossy(&bytes[..e.valid_up_to()]).to_string())
+            }
+            Err(_) => None,
+        }
+    }
+
+    fn parse_stat_output(output: &str, dir: &str) -> Vec<PodFileEntry> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.splitn(8, FIELD_SEP).collect();
+                if fields.len() < 7 {
+                    return None;
+                }
+                let kind: String = Self::kind_from_stat(fields[0]);
+                let name: String = fields[6].to_string();
+                let link_target: Option<String> = if kind == "symlink" {
+                    fields.get(7).and_then(|n| Self::link_target_from_quoted(n))
+                } else {
+                    None
+                };
+                Some(PodFileEntry {
+                    path: Self::join_path(dir, &name),
+                    name,
+                    kind,
+                    size: fields[1].parse::<u64>().ok(),
+                    mode: Some(fields[2].to_string()),
+                    modified: fields[3].parse::<i64>().ok(),
+                    owner: Some(fields[4].to_string()),
+                    group: Some(fields[5].to_string()),
+                    link_target,
+                })
+            })
+            .collect()
+    }
+
+    // Parse `ls -la` output (GNU or busybox). With `single` the name column is taken as the
+    // requested path itself rather than a child of `dir`.
+    fn parse_ls_output(output: &str, dir: &str, single: bool) -> Vec<PodFileEntry> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let perms: &str = line.split_whitespace().next()?;
+                if perms == "total" || perms.len() < 10 {
+                    return None;
+                }
+                let kind: String = Self::kind_from_ls(perms.chars().next()?);
+                // perms, links, owner, group, size, month, day, time/year, then the name.
+                // Device files have "major, minor" in place of the size.
+                let is_device: bool = kind == "block" || kind == "char";
+                let fixed: usize = if is_device { 9 } else { 8 };
+                let (tokens, rest) = Self::split_fields(line, fixed)?;
+                let size: Option<u64> = if is_device { None } else { tokens[4].parse().ok() };
+
+                let (name, link_target) = match rest.split_once(" -> ") {
+                    Some((n, t)) if kind == "symlink" => (n.to_string(), Some(t.to_string())),
+                    _ => (rest.to_string(), None),
+                };
+                if !single && (name == "." || name == "..") {
+                    return None;
+                }
+                let path: String = if single { name.clone() } else { Self::join_path(dir, &name) };
+
+                Some(PodFileEntry {
+                    name,
+                    path,
+                    kind,
+                    size,
+                    mode: Some(Self::mode_from_perms(perms)),
+                    modified: None,
+                    owner: Some(tokens[2].to_string()),
+                    group: Some(tokens[3].to_string()),
+                    link_target,
+                })
+            })
+            .collect()
+    }
+
+    // Split off the first `count` whitespace-separated tokens, returning the remainder
+    // verbatim so names containing spaces are preserved.
+    fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+        let mut tokens: Vec<&str> = Vec::with_capacity(count);
+        let mut rest: &str = line;
+        for _ in 0..count {
+            rest = rest.trim_start();
+            let end: usize = rest.find(char::is_whitespace)?;
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+        let rest: &str = rest.trim_start();
+        if rest.is_empty() {
+            None
+        } else {
+            Some((tokens, rest))
+        }
+    }
+
+    fn kind_from_stat(file_type: &str) -> String {
+        let t: String = file_type.to_lowercase();
+        let kind: &str = if t.contains("directory") {
+            "directory"
+        } else if t.contains("symbolic link") {
+            "symlink"
+        } else if t.contains("regular") {
+            "file"
+        } else if t.contains("fifo") {
+            "fifo"
+        } else if t.contains("socket") {
+            "socket"
+        } else if t.contains("block") {
+            "block"
+        } else if t.contains("character") {
+            "char"
+        } else {
+            "unknown"
+        };
+        kind.to_string()
+    }
+
+    fn kind_from_ls(type_char: char) -> String {
+        let kind: &str = match type_char {
+            '-' => "file",
+            'd' => "directory",
+            'l' => "symlink",
+            'p' => "fifo",
+            's' => "socket",
+            'b' => "block",
+            'c' => "char",
+            _ => "unknown",
+        };
+        kind.to_string()
+    }
+
+    // "drwxr-xr-x" -> "755"; setuid/setgid/sticky letters still count as execute bits.
+    fn mode_from_perms(perms: &str) -> String {
+        let bits: Vec<char> = perms.chars().skip(1).take(9).collect();
+        let digit = |chunk: &[char]| -> u32 {
+            let mut d: u32 = 0;
+            if chunk.first() == Some(&'r') {
+                d += 4;
+            }
+            if chunk.get(1) == Some(&'w') {
+                d += 2;
+            }
+            if matches!(chunk.get(2), Some('x') | Some('s') | Some('t')) {
+                d += 1;
+            }
+            d
+        };
+        bits.chunks(3).map(|c| digit(c).to_string()).collect()
+    }
+
+    // `%N` renders symlinks as "'name' -> 'target'"; strip the quoting from the target.
+    fn link_target_from_quoted(quoted: &str) -> Option<String> {
+        let (_, target) = quoted.split_once(" -> ")?;
+        let target: &str = target.trim();
+        let unquoted: &str = ['\'', '"', '`']
+            .iter()
+            .find_map(|q| target.strip_prefix(*q).and_then(|t| t.strip_suffix(*q)))
+            .unwrap_or(target);
+        Some(unquoted.to_string())
+    }
+
+    fn normalize_path(path: &str) -> String {
+        let trimmed: &str = path.trim();
+        if trimmed.is_empty() || trimmed == "/" {
+            return "/".to_string();
+        }
+        let with_root: String =
+            if trimmed.starts_with('/') { trimmed.to_string() } else { format!("/{}", trimmed) };
+        with_root.trim_end_matches('/').to_string()
+    }
+
+    fn join_path(dir: &str, name: &str) -> String {
+        if dir == "/" {
+            format!("/{}", name)
+        } else {
+            format!("{}/{}", dir.trim_end_matches('/'), name)
+        }
+    }
+
+    fn parent_path(path: &str) -> String {
+        match path.rfind('/') {
+            Some(0) | None => "/".to_string(),
+            Some(idx) => path[..idx].to_string(),
+        }
+    }
+
+    fn base_name(path: &str) -> String {
+        if path == "/" {
+            return "/".to_string();
+        }
+        path.rsplit('/').next().unwrap_or(path).to_string()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::manager::k8s::client::K8sClient;
 use futures_util::{AsyncBufReadExt, StreamExt};
-use k8s_openapi::{api::core::v1::Pod, chrono};
+use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::apis::meta::v1::Status, chrono};
 use kube::{
     api::{Api, AttachParams, LogParams},
     Client,
@@ -8,6 +8,20 @@ use kube::{
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 
+/// Raw result of a non-interactive exec: stdout bytes are kept as-is so callers
+/// can handle binary content, and the exit code is taken from the exec status.
+pub struct ExecOutput {
+    pub stdout: Vec<u8>,
+    pub stderr: String,
+    pub exit_code: i32,
+}
+
+impl ExecOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
 pub struct PodResources;
 
 impl PodResources {
@@ -163,4 +177,94 @@ impl PodResources {
 
         Ok(out)
     }
+
+    /// Run a command without a TTY and capture stdout, stderr and the exit code separately.
+    pub async fn exec_output(
+        context_name: String,
+        namespace: String,
+        pod_name: String,
+        container_name: Option<String>,
+        command: Vec<String>,
+    ) -> Result<ExecOutput, String> {
+        use tokio::io::AsyncReadExt;
+
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let api: Api<Pod> = K8sClient::api::<Pod>(client, Some(namespace)).await;
+
+        let mut params: AttachParams =
+            AttachParams::default().stdin(false).stdout(true).stderr(true).tty(false);
+
+        if let Some(container) = container_name {
+            params = params.container(container);
+        }
+
+        let mut attached = api
+            .exec(&pod_name, command, &params)
+            .await
+            .map_err(|e| format!("Failed to exec in pod {}: {}", pod_name, e))?;
+
+        let stdout_opt = attached.stdout();
+        let stderr_opt = attached.stderr();
+        let status_opt = attached.take_status();
+
+        // Drain both streams concurrently so a chatty stderr cannot stall stdout.
+        let read_stdout = async move {
+            let mut buf: Vec<u8> = Vec::new();
+            if let Some(mut stdout) = stdout_opt {
+                stdout
+                    .read_to_end(&mut buf)
+                    .await
+                    .map_err(|e| format!("Failed to read stdout: {}", e))?;
+            }
+            Ok::<Vec<u8>, String>(buf)
+        };
+        let read_stderr = async move {
+            let mut buf: Vec<u8> = Vec::new();
+            if let Some(mut stderr) = stderr_opt {
+                stderr
+                    .read_to_end(&mut buf)
+                    .await
+                    .map_err(|e| format!("Failed to read stderr: {}", e))?;
+            }
+            Ok::<Vec<u8>, String>(buf)
+        };
+        let (stdout, stderr) = tokio::join!(read_stdout, read_stderr);
+
+        let status = match status_opt {
+            Some(fut) => fut.await,
+            None => None,
+        };
+
+        Ok(ExecOutput {
+            stdout: stdout?,
+            stderr: String::from_utf8_lossy(&stderr?).to_string(),
+            exit_code: Self::exit_code_from_status(status.as_ref())?,
+        })
+    }
+
+    // The exec status reports "Success", or "Failure" with an ExitCode cause carrying the code.
+    // Without a status frame the outcome is unknown, which must not read as success.
+    fn exit_code_from_status(status: Option<&Status>) -> Result<i32, String> {
+        let Some(status) = status else {
+            return Err("Command ended without reporting an exit status".to_string());
+        };
+        if status.status.as_deref() == Some("Success") {
+            return Ok(0);
+        }
+        let code: i32 = status
+            .details
+            .as_ref()
+            .and_then(|d| d.causes.as_ref())
+            .and_then(|causes| {
+                causes.iter().find_map(|c| {
+                    if c.reason.as_deref() == Some("ExitCode") {
+                        c.message.as_deref().and_then(|m| m.trim().parse::<i32>().ok())
+                    } else {
+                        None
+                    }
+                })
+            })
+            .unwrap_or(1);
+        Ok(code)
+    }
 }
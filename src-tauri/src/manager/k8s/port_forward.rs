@@ -1,25 +1,48 @@
 use crate::manager::k8s::client::K8sClient;
-use crate::utils::port_forward::{PortForwardManager, PortForwardSession};
+use crate::manager::k8s::service_proxy::{ServiceProxy, ServiceProxyTarget};
+use crate::state::AppState;
+use crate::utils::port_forward::{
+    PortForwardConfig, PortForwardManager, PortForwardMeta, PortForwardMode, PortForwardOptions,
+    PortForwardPort, PortForwardPortSpec, PortForwardSession, PortForwardStats,
+    PortForwardStatsSnapshot, RemotePort,
+};
+use futures_util::StreamExt;
 use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
-use k8s_openapi::api::core::v1::{Pod, Service};
-use kube::api::{Api, ListParams};
-use kube::Client;
+use k8s_openapi::api::core::v1::{ContainerPort, Pod, Service, ServicePort};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use k8s_openapi::chrono::Utc;
+use kube::api::{Api, ListParams, WatchEvent, WatchParams};
+use kube::{Client, Config};
 use rand::Rng;
 use serde::Serialize;
 use std::collections::BTreeMap;
-use tauri::{AppHandle, Emitter};
-use tokio::io;
-use tokio::net::TcpListener;
-use tokio::sync::mpsc;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+const STATS_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Serialize, Clone)]
 struct PortForwardEventPayload {
     r#type: String,
     line: String,
 }
 
+#[derive(Serialize, Clone)]
+struct PortForwardStatsPayload {
+    r#type: String,
+    stats: PortForwardStatsSnapshot,
+}
+
 pub struct PortForwarder<'a> {
     app: AppHandle,
     state: &'a PortForwardManager,
@@ -34,257 +57,240 @@ impl<'a> PortForwarder<'a> {
         format!("port_forward/{}", session_id)
     }
 
-    fn labels_to_selector(labels: &BTreeMap<String, String>) -> Option<String> {
-        if labels.is_empty() {
-            None
-        } else {
-            Some(labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(","))
+    fn resolve_bind_address(bind_address: Option<&str>) -> Result<IpAddr, String> {
+        let addr: &str =
+            bind_address.map(|s| s.trim()).filter(|s| !s.is_empty()).unwrap_or("127.0.0.1");
+        let addr: &str = if addr.eq_ignore_ascii_case("localhost") { "127.0.0.1" } else { addr };
+        match addr.parse::<IpAddr>() {
+            Ok(ip) if ip.is_loopback() || ip.is_unspecified() => Ok(ip),
+            _ => Err(format!("unsupported bind address '{}': use 127.0.0.1, ::1 or 0.0.0.0", addr)),
         }
     }
 
-    async fn resolve_target_pod(
-        &self,
+    async fn resolve_ports(
         client: Client,
-        namespace: String,
-        resource_kind: String,
-        resource_name: String,
-    ) -> Result<String, String> {
-        match resource_kind.as_str() {
-            "pod" => Ok(resource_name),
+        namespace: &str,
+        resource_kind: &str,
+        resource_name: &str,
+        pod: &Pod,
+        specs: &[PortForwardPortSpec],
+    ) -> Result<Vec<PortForwardPort>, String> {
+        // Ports requested against a Service refer to the service port, which maps to a targetPort
+        let service_ports: Option<Vec<ServicePort>> = match resource_kind {
             "service" | "svc" => {
                 let svc_api: Api<Service> =
-                    K8sClient::api::<Service>(client.clone(), Some(namespace.clone())).await;
+                    K8sClient::api::<Service>(client, Some(namespace.to_string())).await;
                 let svc: Service = svc_api
-                    .get(&resource_name)
+                    .get(resource_name)
                     .await
                     .map_err(|e| format!("failed to get service {}: {}", resource_name, e))?;
-                let selector = svc.spec.and_then(|s| s.selector).unwrap_or_default();
-                let pods_api: Api<Pod> =
-                    K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
-                let lp = match Self::labels_to_selector(&selector) {
-                    Some(s) => ListParams::default().labels(&s),
-                    None => ListParams::default(),
-                };
-                let pods = pods_api.list(&lp).await.map_err(|e| {
-                    format!("failed to list pods for service {}: {}", resource_name, e)
-                })?;
-                let name = pods
-                    .items
-                    .into_iter()
-                    .find_map(|p| p.metadata.name)
-                    .ok_or_else(|| "no backing pods found".to_string())?;
-                Ok(name)
+                svc.spec.and_then(|s| s.ports)
             }
-            "deployment" | "deploy" => {
-                let api: Api<Deployment> =
-                    K8sClient::api::<Deployment>(client.clone(), Some(namespace.clone())).await;
-                let dep: Deployment = api
-                    .get(&resource_name)
-                    .await
-                    .map_err(|e| format!("failed to get deployment {}: {}", resource_name, e))?;
-                let selector =
-                    dep.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
-                let pods_api: Api<Pod> =
-                    K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
-                let lp = match Self::labels_to_selector(&selector) {
-                    Some(s) => ListParams::default().labels(&s),
-                    None => ListParams::default(),
-                };
-                let pods = pods_api.list(&lp).await.map_err(|e| {
-                    format!("failed to list pods for deployment {}: {}", resource_name, e)
-                })?;
-                let name = pods
-                    .items
-                    .into_iter()
-                    .find_map(|p| p.metadata.name)
-                    .ok_or_else(|| "no backing pods found".to_string())?;
-                Ok(name)
-            }
-            "replicaset" | "rs" => {
-                let api: Api<ReplicaSet> =
-                    K8sClient::api::<ReplicaSet>(client.clone(), Some(namespace.clone())).await;
-                let rs: ReplicaSet = api
-                    .get(&resource_name)
-                    .await
-                    .map_err(|e| format!("failed to get replicaset {}: {}", resource_name, e))?;
-                let selector =
-                    rs.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
-                let pods_api: Api<Pod> =
-                    K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
-                let lp = match Self::labels_to_selector(&selector) {
-                    Some(s) => ListParams::default().labels(&s),
-                    None => ListParams::default(),
-                };
-                let pods = pods_api.list(&lp).await.map_err(|e| {
-                    format!("failed to list pods for replicaset {}: {}", resource_name, e)
-                })?;
-                let name = pods
-                    .items
-                    .into_iter()
-                    .find_map(|p| p.metadata.name)
-                    .ok_or_else(|| "no backing pods found".to_string())?;
-                Ok(name)
-            }
-            "statefulset" | "sts" => {
-                let api: Api<StatefulSet> =
-                    K8sClient::api::<StatefulSet>(client.clone(), Some(namespace.clone())).await;
-                let sts: StatefulSet = api
-                    .get(&resource_name)
-                    .await
-                    .map_err(|e| format!("failed to get statefulset {}: {}", resource_name, e))?;
-                let selector =
-                    sts.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
-                let pods_api: Api<Pod> =
-                    K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
-                let lp = match Self::labels_to_selector(&selector) {
-                    Some(s) => ListParams::default().labels(&s),
-                    None => ListParams::default(),
-                };
-                let pods = pods_api.list(&lp).await.map_err(|e| {
-                    format!("failed to list pods for statefulset {}: {}", resource_name, e)
-                })?;
-                let name = pods
-                    .items
-                    .into_iter()
-                    .find_map(|p| p.metadata.name)
-                    .ok_or_else(|| "no backing pods found".to_string())?;
-                Ok(name)
+            _ => None,
+        };
+
+        specs.iter().map(|spec| Self::resolve_port(service_ports.as_deref(), pod, spec)).collect()
+    }
+
+    fn resolve_port(
+        service_ports: Option<&[ServicePort]>,
+        pod: &Pod,
+        spec: &PortForwardPortSpec,
+    ) -> Result<PortForwardPort, String> {
+        let matching: Vec<&ServicePort> = service_ports
+            .into_iter()
+            .flatten()
+            .filter(|p| match &spec.remote_port {
+                RemotePort::Number(n) => p.port == i32::from(*n),
+                RemotePort::Name(n) => p.name.as_deref() == Some(n.as_str()),
+            })
+            .collect();
+        // A number can be listed once per protocol (kube-dns: 53/UDP and 53/TCP); take the
+        // TCP one, so only a port declared exclusively for another protocol is rejected
+        let service_port: Option<&ServicePort> = matching
+            .iter()
+            .find(|p| p.protocol.as_deref().unwrap_or("TCP").eq_ignore_ascii_case("TCP"))
+            .or(matching.first())
+            .copied();
+
+        let (target, name): (i32, Option<String>) = if let Some(sp) = service_port {
+            Self::ensure_tcp(
+                sp.protocol.as_deref(),
+                &format!("service port {}", spec.remote_port),
+            )?;
+            let target: i32 = match &sp.target_port {
+                None => sp.port,
+                Some(IntOrString::Int(i)) => *i,
+                Some(IntOrString::String(port_name)) => {
+                    let cp: &ContainerPort =
+                        Self::find_container_port(pod, port_name).ok_or_else(|| {
+                            format!(
+                                "target port '{}' of service port {} not found on pod {}",
+                                port_name,
+                                spec.remote_port,
+                                pod.metadata.name.clone().unwrap_or_default()
+                            )
+                        })?;
+                    Self::ensure_tcp(
+                        cp.protocol.as_deref(),
+                        &format!("container port {}", port_name),
+                    )?;
+                    cp.container_port
+                }
+            };
+            (target, sp.name.clone())
+        } else {
+            match &spec.remote_port {
+                RemotePort::Number(n) => {
+                    let declared: Vec<&ContainerPort> = Self::container_ports(pod)
+                        .into_iter()
+                        .filter(|cp| cp.container_port == i32::from(*n))
+                        .collect();
+                    // Only reject when the port is declared exclusively for a non-TCP protocol
+                    let tcp_declared: bool = declared.iter().any(|cp| {
+                        cp.protocol.as_deref().unwrap_or("TCP").eq_ignore_ascii_case("TCP")
+                    });
+                    if let (false, Some(cp)) = (tcp_declared, declared.first()) {
+                        Self::ensure_tcp(cp.protocol.as_deref(), &format!("container port {}", n))?;
+                    }
+                    (i32::from(*n), declared.first().and_then(|cp| cp.name.clone()))
+                }
+                RemotePort::Name(port_name) => {
+                    let cp: &ContainerPort =
+                        Self::find_container_port(pod, port_name).ok_or_else(|| {
+                            format!(
+                                "port '{}' not found on pod {}",
+                                port_name,
+                                pod.metadata.name.clone().unwrap_or_default()
+                            )
+                        })?;
+                    Self::ensure_tcp(
+                        cp.protocol.as_deref(),
+                        &format!("container port {}", port_name),
+                    )?;
+                    (cp.container_port, Some(port_name.clone()))
+                }
             }
-            "daemonset" | "ds" => {
-                let api: Api<DaemonSet> =
-                    K8sClient::api::<DaemonSet>(client.clone(), Some(namespace.clone())).await;
-                let ds: DaemonSet = api
-                    .get(&resource_name)
-                    .await
-                    .map_err(|e| format!("failed to get daemonset {}: {}", resource_name, e))?;
-                let selector =
-                    ds.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
-                let pods_api: Api<Pod> =
-                    K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
-                let lp = match Self::labels_to_selector(&selector) {
-                    Some(s) => ListParams::default().labels(&s),
-                    None => ListParams::default(),
-                };
-                let pods = pods_api.list(&lp).await.map_err(|e| {
-                    format!("failed to list pods for daemonset {}: {}", resource_name, e)
+        };
+
+        let remote_port: u16 =
+            u16::try_from(target).map_err(|_| format!("invalid remote port {}", target))?;
+        Ok(PortForwardPort {
+            local_port: spec.local_port,
+            remote_port,
+            requested_port: spec.remote_port.clone(),
+            name,
+            protocol: "TCP".to_string(),
+        })
+    }
+
+    fn container_ports(pod: &Pod) -> Vec<&ContainerPort> {
+        pod.spec
+            .as_ref()
+            .map(|s| s.containers.iter().flat_map(|c| c.ports.iter().flatten()).collect())
+            .unwrap_or_default()
+    }
+
+    fn find_container_port<'p>(pod: &'p Pod, name: &str) -> Option<&'p ContainerPort> {
+        Self::container_ports(pod).into_iter().find(|cp| cp.name.as_deref() == Some(name))
+    }
+
+    // The apiserver port-forward protocol only carries TCP streams.
+    fn ensure_tcp(protocol: Option<&str>, what: &str) -> Result<(), String> {
+        let protocol: &str = protocol.unwrap_or("TCP");
+        if protocol.eq_ignore_ascii_case("TCP") {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} uses {}; port forwarding only supports TCP",
+                what,
+                protocol.to_uppercase()
+            ))
+        }
+    }
+
+    // Bind every local port up front so bind errors surface to the caller and
+    // port 0 can be replaced with the port the OS picked.
+    async fn bind_listeners(
+        bind_ip: IpAddr,
+        ports: Vec<PortForwardPort>,
+    ) -> Result<Vec<(TcpListener, PortForwardPort)>, String> {
+        let mut listeners: Vec<(TcpListener, PortForwardPort)> = Vec::new();
+        for mut port in ports {
+            let listener: TcpListener =
+                TcpListener::bind((bind_ip, port.local_port)).await.map_err(|e| {
+                    format!("failed to bind local port {}:{}: {}", bind_ip, port.local_port, e)
                 })?;
-                let name = pods
-                    .items
-                    .into_iter()
-                    .find_map(|p| p.metadata.name)
-                    .ok_or_else(|| "no backing pods found".to_string())?;
-                Ok(name)
-            }
-            _ => Err(format!("unsupported resource kind: {}", resource_kind)),
+            port.local_port = listener
+                .local_addr()
+                .map_err(|e| format!("failed to read bound address: {}", e))?
+                .port();
+            listeners.push((listener, port));
         }
+        Ok(listeners)
     }
 
     async fn spawn_forward_task(
         &self,
-        pods_api: Api<Pod>,
-        pod_name: String,
-        local_port: u16,
-        remote_port: u16,
-        event_name: String,
+        session_id: &str,
         context: String,
-        namespace: String,
-        resource_kind: String,
-        resource_name: String,
+        resolver: PodResolver,
+        pod_name: String,
+        listeners: Vec<(TcpListener, PortForwardPort)>,
+        bind_address: String,
     ) -> PortForwardSession {
-        let (kill_tx, mut kill_rx) = mpsc::channel::<()>(1);
+        let (kill_tx, kill_rx) = mpsc::channel::<()>(1);
+        let (failover_tx, failover_rx) = mpsc::channel::<String>(16);
         let app = self.app.clone();
-        let ev_name = event_name.clone();
+        let ev_name = Self::make_event_name(session_id);
         let cancel_token = CancellationToken::new();
-        let cancel_main = cancel_token.clone();
+        let ports: Vec<PortForwardPort> = listeners.iter().map(|(_, p)| p.clone()).collect();
+        let pods_api: Api<Pod> =
+            K8sClient::api::<Pod>(resolver.client.clone(), Some(resolver.namespace.clone())).await;
+        // Pod currently backing the session; swapped by the supervisor on failover
+        let target: Arc<RwLock<String>> = Arc::new(RwLock::new(pod_name.clone()));
+        let supervisor = Supervisor {
+            app: app.clone(),
+            session_id: session_id.to_string(),
+            event_name: ev_name.clone(),
+            resolver: resolver.clone(),
+            target: target.clone(),
+            cancel: cancel_token.clone(),
+        };
+
+        let stats: Arc<PortForwardStats> = Arc::new(PortForwardStats::default());
+        let ctx = ConnectionContext {
+            app: app.clone(),
+            event_name: ev_name.clone(),
+            pods_api: pods_api.clone(),
+            failover_tx,
+            cancel: cancel_token.clone(),
+            stats: stats.clone(),
+        };
+        tokio::spawn(emit_stats_loop(
+            app.clone(),
+            ev_name.clone(),
+            stats.clone(),
+            cancel_token.clone(),
+        ));
 
         let handle: JoinHandle<()> = tokio::spawn(async move {
-            match pods_api.portforward(&pod_name, &[remote_port]).await {
-                Ok(mut pf) => {
-                    match TcpListener::bind(("127.0.0.1", local_port)).await {
-                        Ok(listener) => {
-                            let _ = app.emit(
-                                &ev_name,
-                                PortForwardEventPayload {
-                                    r#type: "PF_STDOUT".into(),
-                                    line: format!(
-                                        "listening on 127.0.0.1:{} -> {}:{}",
-                                        local_port, pod_name, remote_port
-                                    ),
-                                },
-                            );
-                            loop {
-                                tokio::select! {
-                                  _ = kill_rx.recv() => {
-                                    cancel_main.cancel();
-                                    let _ = app.emit(&ev_name, PortForwardEventPayload { r#type: "PF_DONE".into(), line: "stopped".into() });
-                                    break;
-                                  }
-                                  _ = cancel_main.cancelled() => {
-                                    let _ = app.emit(&ev_name, PortForwardEventPayload { r#type: "PF_DONE".into(), line: "stopped".into() });
-                                    break;
-                                  }
-                                  incoming = listener.accept() => {
-                                    match incoming {
-                                      Ok((mut socket, _addr)) => {
-                                        let mut attempts: u8 = 20; // ~2s total with 100ms sleep
-                                        let mut got_stream = false;
-                                        while attempts > 0 {
-                                          if let Some(mut remote) = pf.take_stream(remote_port) {
-                                            got_stream = true;
-                                            let cancel_copy = cancel_main.clone();
-                                            tokio::spawn(async move {
-                                              tokio::select! {
-                                                _ = cancel_copy.cancelled() => {
-                                                  // cancellation: drop sockets
-                                                }
-                                                _ = io::copy_bidirectional(&mut socket, &mut remote) => {
-                                                  // done
-                                                }
-                                              }
-                                            });
-                                            break;
-                                          } else {
-                                            attempts -= 1;
-                                            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-                                          }
-                                        }
-
-                                        if !got_stream {
-                                          let _ = app.emit(&ev_name, PortForwardEventPayload { r#type: "PF_ERROR".into(), line: format!("no remote stream available for port {} after retries", remote_port) });
-                                        }
-                                      }
-                                      Err(e) => {
-                                        let _ = app.emit(&ev_name, PortForwardEventPayload { r#type: "PF_ERROR".into(), line: format!("listener accept error: {}", e) });
-                                      }
-                                    }
-                                  }
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            let _ = app.emit(
-                                &ev_name,
-                                PortForwardEventPayload {
-                                    r#type: "PF_ERROR".into(),
-                                    line: format!(
-                                        "failed to bind local port {}: {}",
-                                        local_port, e
-                                    ),
-                                },
-                            );
-                        }
-                    }
-                }
-                Err(e) => {
-                    let _ = app.emit(
-                        &ev_name,
-                        PortForwardEventPayload {
-                            r#type: "PF_ERROR".into(),
-                            line: format!("portforward start error: {}", e),
-                        },
-                    );
-                }
+            for (listener, port) in listeners {
+                let local: String = listener
+                    .local_addr()
+                    .map(|a| a.to_string())
+                    .unwrap_or_else(|_| port.local_port.to_string());
+                emit_event(
+                    &app,
+                    &ev_name,
+                    "PF_STDOUT",
+                    format!("listening on {} -> {}:{}", local, pod_name, port.remote_port),
+                );
+                tokio::spawn(accept_loop(ctx.clone(), target.clone(), listener, port));
             }
+
+            supervisor.run(pods_api, failover_rx, kill_rx).await;
+            emit_event(&app, &ev_name, "PF_DONE", "stopped".to_string());
         });
 
         PortForwardSession {
@@ -292,12 +298,15 @@ impl<'a> PortForwarder<'a> {
             handle: Some(handle),
             cancel_token: Some(cancel_token),
             context,
-            namespace,
-            resource_kind,
-            resource_name,
-            local_port,
-            remote_port,
+            namespace: resolver.namespace,
+            resource_kind: resolver.resource_kind,
+            resource_name: resolver.resource_name,
+            bind_address,
+            ports,
             status: "Running".to_string(),
+            mode: PortForwardMode::Tcp,
+            meta: PortForwardMeta::default(),
+            stats,
         }
     }
 
@@ -307,9 +316,14 @@ impl<'a> PortForwarder<'a> {
         namespace: String,
         resource_kind: String,
         resource_name: String,
-        local_port: u16,
-        remote_port: u16,
+        ports: Vec<PortForwardPortSpec>,
+        options: PortForwardOptions,
     ) -> Result<serde_json::Value, String> {
+        if ports.is_empty() {
+            return Err("at least one port is required".to_string());
+        }
+        let bind_ip: IpAddr = Self::resolve_bind_address(options.bind_address.as_deref())?;
+
         let session_id: String = {
             let mut rng = rand::thread_rng();
             format!("pf-{:08x}", rng.gen::<u32>())
@@ -317,35 +331,43 @@ impl<'a> PortForwarder<'a> {
         let event_name: String = Self::make_event_name(&session_id);
 
         let client: Client = K8sClient::for_context(&context).await?;
-        let pod_name: String = self
-            .resolve_target_pod(
-                client.clone(),
-                namespace.clone(),
-                resource_kind.clone(),
-                resource_name.clone(),
-            )
-            .await?;
-        let pods_api: Api<Pod> =
-            K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
+        let resolver = PodResolver::new(client.clone(), &namespace, &resource_kind, &resource_name);
+        let pod: Pod = resolver.resolve().await?;
+        let pod_name: String = pod.metadata.name.clone().unwrap_or_default();
+        let resolved: Vec<PortForwardPort> = Self::resolve_ports(
+            client.clone(),
+            &namespace,
+            &resource_kind,
+            &resource_name,
+            &pod,
+            &ports,
+        )
+        .await?;
+        let listeners = Self::bind_listeners(bind_ip, resolved).await?;
+        let bound_ports: Vec<PortForwardPort> = listeners.iter().map(|(_, p)| p.clone()).collect();
 
-        let session = self
+        let mut session = self
             .spawn_forward_task(
-                pods_api,
-                pod_name,
-                local_port,
-                remote_port,
-                event_name.clone(),
+                &session_id,
                 context.clone(),
-                namespace.clone(),
-                resource_kind.clone(),
-                resource_name.clone(),
+                resolver,
+                pod_name,
+                listeners,
+                bind_ip.to_string(),
             )
             .await;
+        session.meta = PortForwardMeta {
+            created_at: Utc::now().timestamp(),
+            ..options.meta
+        };
         self.state.insert(session_id.clone(), session).await;
+        self.persist(&session_id).await;
 
         Ok(serde_json::json!({
           "eventName": event_name,
           "sessionId": session_id,
+          "bindAddress": bind_ip.to_string(),
+          "ports": bound_ports,
         }))
     }
 
@@ -356,48 +378,762 @@ impl<'a> PortForwarder<'a> {
         state.stop(&session_id).await
     }
 
-    pub async fn delete(
-        state: tauri::State<'_, PortForwardManager>,
-        session_id: String,
+    /// Open an HTTP(S) service through the apiserver proxy on a local port.
+    pub async fn start_service_proxy(
+        &self,
+        context: String,
+        namespace: String,
+        service_name: String,
+        port: PortForwardPortSpec,
+        mode: PortForwardMode,
+        options: PortForwardOptions,
+    ) -> Result<serde_json::Value, String> {
+        if mode == PortForwardMode::Tcp {
+            return Err("service proxy mode must be http or https".to_string());
+        }
+        let bind_ip: IpAddr = Self::resolve_bind_address(options.bind_address.as_deref())?;
+
+        let session_id: String = {
+            let mut rng = rand::thread_rng();
+            format!("pf-{:08x}", rng.gen::<u32>())
+        };
+        let config = PortForwardConfig {
+            context,
+            namespace,
+            resource_kind: "service".to_string(),
+            resource_name: service_name,
+            bind_address: bind_ip.to_string(),
+            ports: vec![PortForwardPort {
+                local_port: port.local_port,
+                remote_port: 0,
+                requested_port: port.remote_port,
+                name: None,
+                protocol: "TCP".to_string(),
+            }],
+            mode,
+        };
+
+        let mut session: PortForwardSession =
+            self.launch_service_proxy(&session_id, config).await?;
+        session.meta = PortForwardMeta {
+            created_at: Utc::now().timestamp(),
+            ..options.meta
+        };
+        let bound_ports: Vec<PortForwardPort> = session.ports.clone();
+        // The proxy always speaks plain HTTP locally, whatever the upstream scheme
+        let local_ip: IpAddr =
+            if bind_ip.is_unspecified() { IpAddr::from([127, 0, 0, 1]) } else { bind_ip };
+        let url: String = format!(
+            "http://{}/",
+            SocketAddr::new(
+                local_ip,
+                bound_ports.first().map(|p| p.local_port).unwrap_or_default()
+            )
+        );
+        self.state.insert(session_id.clone(), session).await;
+        self.persist(&session_id).await;
+
+        Ok(serde_json::json!({
+          "eventName": Self::make_event_name(&session_id),
+          "sessionId": session_id,
+          "bindAddress": bind_ip.to_string(),
+          "ports": bound_ports,
+          "url": url,
+        }))
+    }
+
+    async fn launch_service_proxy(
+        &self,
+        session_id: &str,
+        config: PortForwardConfig,
+    ) -> Result<PortForwardSession, String> {
+        let bind_ip: IpAddr = Self::resolve_bind_address(Some(&config.bind_address))?;
+        let requested: &PortForwardPort =
+            config.ports.first().ok_or_else(|| "a service port is required".to_string())?;
+
+        let kube_config: Config = K8sClient::config_for_context(&config.context).await?;
+        let client: Client = Client::try_from(kube_config.clone()).map_err(|e| e.to_string())?;
+        let mut port: PortForwardPort = ServiceProxy::resolve_port(
+            client.clone(),
+            &config.namespace,
+            &config.resource_name,
+            &requested.requested_port,
+        )
+        .await?;
+        port.local_port = requested.local_port;
+        let (listener, port) = Self::bind_listeners(bind_ip, vec![port])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "failed to bind local port".to_string())?;
+
+        let ev_name: String = Self::make_event_name(session_id);
+        let cancel_token = CancellationToken::new();
+        let stats: Arc<PortForwardStats> = Arc::new(PortForwardStats::default());
+        let target = ServiceProxyTarget {
+            namespace: config.namespace.clone(),
+            service: config.resource_name.clone(),
+            port: port.name.clone().unwrap_or_else(|| port.remote_port.to_string()),
+            mode: config.mode,
+        };
+        let proxy: ServiceProxy = ServiceProxy::new(
+            self.app.clone(),
+            ev_name.clone(),
+            &kube_config,
+            target,
+            stats.clone(),
+        )?;
+
+        emit_event(
+            &self.app,
+            &ev_name,
+            "PF_STDOUT",
+            format!(
+                "proxying http://{}:{} -> {}://{}:{}",
+                bind_ip,
+                port.local_port,
+                config.mode.protocol().to_lowercase(),
+                config.resource_name,
+                port.remote_port
+            ),
+        );
+        tokio::spawn(emit_stats_loop(
+            self.app.clone(),
+            ev_name.clone(),
+            stats.clone(),
+            cancel_token.clone(),
+        ));
+        let handle: JoinHandle<()> =
+            tokio::spawn(proxy.run(listener, port.clone(), cancel_token.clone()));
+
+        Ok(PortForwardSession {
+            kill_tx: None,
+            handle: Some(handle),
+            cancel_token: Some(cancel_token),
+            context: config.context,
+            namespace: config.namespace,
+            resource_kind: config.resource_kind,
+            resource_name: config.resource_name,
+            bind_address: bind_ip.to_string(),
+            ports: vec![port],
+            status: "Running".to_string(),
+            mode: config.mode,
+            meta: PortForwardMeta::default(),
+            stats,
+        })
+    }
+
+    pub async fn delete(&self, session_id: String) -> Result<(), String> {
+        self.state.delete(&session_id).await?;
+        if let Some(app_state) = self.app.try_state::<AppState>() {
+            app_state.port_forwards.delete_definition(&session_id).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    pub async fn rename(&self, session_id: String, name: String) -> Result<(), String> {
+        self.state
+            .update_meta(&session_id, |m| {
+                m.name = if name.trim().is_empty() { None } else { Some(name.trim().to_string()) };
+            })
+            .await?;
+        self.persist(&session_id).await;
+        Ok(())
+    }
+
+    pub async fn set_group(
+        &self,
+        session_ids: Vec<String>,
+        group: Option<String>,
     ) -> Result<(), String> {
-        state.delete(&session_id).await
+        let group: Option<String> = group.map(|g| g.trim().to_string()).filter(|g| !g.is_empty());
+        for id in session_ids {
+            let group = group.clone();
+            self.state.update_meta(&id, |m| m.group = group).await?;
+            self.persist(&id).await;
+        }
+        Ok(())
+    }
+
+    pub async fn set_auto_start(&self, session_id: String, auto_start: bool) -> Result<(), String> {
+        self.state.update_meta(&session_id, |m| m.auto_start = auto_start).await?;
+        self.persist(&session_id).await;
+        Ok(())
+    }
+
+    pub async fn start_group(&self, group: String) -> Vec<Result<String, String>> {
+        let mut results: Vec<Result<String, String>> = Vec::new();
+        for id in self.state.group_ids(&group).await {
+            match self.resume(id.clone()).await {
+                Ok(()) => results.push(Ok(id)),
+                Err(e) => results.push(Err(format!("{}: {}", id, e))),
+            }
+        }
+        results
+    }
+
+    pub async fn stop_group(&self, group: String) -> Vec<Result<String, String>> {
+        let mut results: Vec<Result<String, String>> = Vec::new();
+        for id in self.state.group_ids(&group).await {
+            match self.state.stop(&id).await {
+                Ok(()) => results.push(Ok(id)),
+                Err(e) => results.push(Err(format!("{}: {}", id, e))),
+            }
+        }
+        results
+    }
+
+    /// Resume the stopped forwards of a context that are flagged for auto-start.
+    pub async fn start_auto_forwards(&self, context: &str) {
+        for id in self.state.auto_start_ids(context).await {
+            if let Err(e) = self.resume(id.clone()).await {
+                emit_event(
+                    &self.app,
+                    &Self::make_event_name(&id),
+                    "PF_ERROR",
+                    format!("auto-start failed: {}", e),
+                );
+            }
+        }
+    }
+
+    // Save the session's definition so it is restored on the next app start.
+    async fn persist(&self, session_id: &str) {
+        let Some(def) = self.state.definition(session_id).await else {
+            return;
+        };
+        if let Some(app_state) = self.app.try_state::<AppState>() {
+            if let Err(e) = app_state.port_forwards.save_definition(&def) {
+                eprintln!("port forward: failed to save {}: {}", session_id, e);
+            }
+        }
     }
 
     pub async fn resume(&self, session_id: String) -> Result<(), String> {
-        // Retrieve stored configuration
-        let (context, namespace, resource_kind, resource_name, local_port, remote_port) = self
-            .state
-            .get_config(&session_id)
-            .await
-            .ok_or_else(|| "session not found".to_string())?;
+        let Some(config) = self.state.begin_start(&session_id).await? else {
+            return Ok(());
+        };
+        let session: PortForwardSession = match self.start_session(&session_id, config).await {
+            Ok(session) => session,
+            Err(e) => {
+                self.state.abort_start(&session_id).await;
+                return Err(e);
+            }
+        };
+        if self.state.finish_start(&session_id, session).await {
+            self.persist(&session_id).await;
+        }
+        Ok(())
+    }
 
-        let client: Client = K8sClient::for_context(&context).await?;
-        let pod_name: String = self
-            .resolve_target_pod(
-                client.clone(),
-                namespace.clone(),
-                resource_kind.clone(),
-                resource_name.clone(),
-            )
-            .await?;
-        let pods_api: Api<Pod> =
-            K8sClient::api::<Pod>(client.clone(), Some(namespace.clone())).await;
+    // Bind and spawn a session from its stored configuration.
+    async fn start_session(
+        &self,
+        session_id: &str,
+        config: PortForwardConfig,
+    ) -> Result<PortForwardSession, String> {
+        if config.mode != PortForwardMode::Tcp {
+            return self.launch_service_proxy(session_id, config).await;
+        }
+        let bind_ip: IpAddr = Self::resolve_bind_address(Some(&config.bind_address))?;
 
-        let event_name: String = Self::make_event_name(&session_id);
-        let session = self
+        // Keep the previously bound local ports; remote ports are resolved again
+        // since the backing pod (and its named ports) may have changed.
+        let specs: Vec<PortForwardPortSpec> = config
+            .ports
+            .iter()
+            .map(|p| PortForwardPortSpec {
+                local_port: p.local_port,
+                remote_port: p.requested_port.clone(),
+            })
+            .collect();
+
+        let client: Client = K8sClient::for_context(&config.context).await?;
+        let resolver = PodResolver::new(
+            client.clone(),
+            &config.namespace,
+            &config.resource_kind,
+            &config.resource_name,
+        );
+        let pod: Pod = resolver.resolve().await?;
+        let pod_name: String = pod.metadata.name.clone().unwrap_or_default();
+        let resolved: Vec<PortForwardPort> = Self::resolve_ports(
+            client.clone(),
+            &config.namespace,
+            &config.resource_kind,
+            &config.resource_name,
+            &pod,
+            &specs,
+        )
+        .await?;
+        let listeners = Self::bind_listeners(bind_ip, resolved).await?;
+
+        Ok(self
             .spawn_forward_task(
-                pods_api,
+                session_id,
+                config.context,
+                resolver,
                 pod_name,
-                local_port,
-                remote_port,
-                event_name,
-                context,
-                namespace,
-                resource_kind,
-                resource_name,
+                listeners,
+                bind_ip.to_string(),
             )
-            .await;
-        self.state.insert(session_id, session).await;
-        Ok(())
+            .await)
+    }
+}
+
+/// Resolves the pod backing a forwarded resource. Kept separate from `PortForwarder`
+/// so the session supervisor can re-resolve after the selected pod goes away.
+#[derive(Clone)]
+pub(crate) struct PodResolver {
+    client: Client,
+    namespace: String,
+    resource_kind: String,
+    resource_name: String,
+}
+
+impl PodResolver {
+    pub(crate) fn new(
+        client: Client,
+        namespace: &str,
+        resource_kind: &str,
+        resource_name: &str,
+    ) -> Self {
+        Self {
+            client,
+            namespace: namespace.to_string(),
+            resource_kind: resource_kind.to_string(),
+            resource_name: resource_name.to_string(),
+        }
+    }
+
+    fn labels_to_selector(labels: &BTreeMap<String, String>) -> Option<String> {
+        if labels.is_empty() {
+            None
+        } else {
+            Some(labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(","))
+        }
+    }
+
+    pub(crate) async fn resolve(&self) -> Result<Pod, String> {
+        let client: Client = self.client.clone();
+        let namespace: String = self.namespace.clone();
+        let resource_name: &str = &self.resource_name;
+
+        let (what, selector): (&str, BTreeMap<String, String>) = match self.resource_kind.as_str() {
+            // An explicitly chosen pod is used as-is, even when not Ready, so it can be debugged.
+            "pod" => {
+                let pods_api: Api<Pod> = K8sClient::api::<Pod>(client, Some(namespace)).await;
+                return pods_api
+                    .get(resource_name)
+                    .await
+                    .map_err(|e| format!("failed to get pod {}: {}", resource_name, e));
+            }
+            "service" | "svc" => {
+                let svc_api: Api<Service> =
+                    K8sClient::api::<Service>(client.clone(), Some(namespace.clone())).await;
+                let svc: Service = svc_api
+                    .get(resource_name)
+                    .await
+                    .map_err(|e| format!("failed to get service {}: {}", resource_name, e))?;
+                ("service", svc.spec.and_then(|s| s.selector).unwrap_or_default())
+            }
+            "deployment" | "deploy" => {
+                let api: Api<Deployment> =
+                    K8sClient::api::<Deployment>(client.clone(), Some(namespace.clone())).await;
+                let dep: Deployment = api
+                    .get(resource_name)
+                    .await
+                    .map_err(|e| format!("failed to get deployment {}: {}", resource_name, e))?;
+                let selector =
+                    dep.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
+                ("deployment", selector)
+            }
+            "replicaset" | "rs" => {
+                let api: Api<ReplicaSet> =
+                    K8sClient::api::<ReplicaSet>(client.clone(), Some(namespace.clone())).await;
+                let rs: ReplicaSet = api
+                    .get(resource_name)
+                    .await
+                    .map_err(|e| format!("failed to get replicaset {}: {}", resource_name, e))?;
+                let selector =
+                    rs.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
+                ("replicaset", selector)
+            }
+            "statefulset" | "sts" => {
+                let api: Api<StatefulSet> =
+                    K8sClient::api::<StatefulSet>(client.clone(), Some(namespace.clone())).await;
+                let sts: StatefulSet = api
+                    .get(resource_name)
+                    .await
+                    .map_err(|e| format!("failed to get statefulset {}: {}", resource_name, e))?;
+                let selector =
+                    sts.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
+                ("statefulset", selector)
+            }
+            "daemonset" | "ds" => {
+                let api: Api<DaemonSet> =
+                    K8sClient::api::<DaemonSet>(client.clone(), Some(namespace.clone())).await;
+                let ds: DaemonSet = api
+                    .get(resource_name)
+                    .await
+                    .map_err(|e| format!("failed to get daemonset {}: {}", resource_name, e))?;
+                let selector =
+                    ds.spec.map(|s| s.selector).and_then(|ls| ls.match_labels).unwrap_or_default();
+                ("daemonset", selector)
+            }
+            _ => return Err(format!("unsupported resource kind: {}", self.resource_kind)),
+        };
+
+        let pods_api: Api<Pod> = K8sClient::api::<Pod>(client, Some(namespace)).await;
+        let lp = match Self::labels_to_selector(&selector) {
+            Some(s) => ListParams::default().labels(&s),
+            None => ListParams::default(),
+        };
+        let pods = pods_api
+            .list(&lp)
+            .await
+            .map_err(|e| format!("failed to list pods for {} {}: {}", what, resource_name, e))?;
+        pods.items
+            .into_iter()
+            .find(|p| p.metadata.name.is_some() && is_pod_ready(p))
+            .ok_or_else(|| format!("no ready backing pods found for {} {}", what, resource_name))
+    }
+}
+
+fn is_pod_ready(pod: &Pod) -> bool {
+    if pod.metadata.deletion_timestamp.is_some() {
+        return false;
+    }
+    let status = match pod.status.as_ref() {
+        Some(s) => s,
+        None => return false,
+    };
+    status.phase.as_deref() == Some("Running")
+        && status
+            .conditions
+            .as_ref()
+            .map(|conds| conds.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+            .unwrap_or(false)
+}
+
+/// Auto-start a context's flagged forwards in the background once it is connected.
+pub fn spawn_auto_start(app: AppHandle, context: String) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<PortForwardManager>();
+        PortForwarder::new(app.clone(), &state).start_auto_forwards(&context).await;
+    });
+}
+
+pub(crate) fn emit_event(app: &AppHandle, event_name: &str, kind: &str, line: String) {
+    let _ = app.emit(
+        event_name,
+        PortForwardEventPayload {
+            r#type: kind.into(),
+            line,
+        },
+    );
+}
+
+/// Follows the pod backing a session and fails over to another ready pod when it is
+/// deleted, starts terminating, or its port-forward websocket breaks.
+struct Supervisor {
+    app: AppHandle,
+    session_id: String,
+    event_name: String,
+    resolver: PodResolver,
+    target: Arc<RwLock<String>>,
+    cancel: CancellationToken,
+}
+
+impl Supervisor {
+    async fn run(
+        &self,
+        pods_api: Api<Pod>,
+        mut failover_rx: mpsc::Receiver<String>,
+        mut kill_rx: mpsc::Receiver<()>,
+    ) {
+        loop {
+            let current: String = self.target.read().await.clone();
+            let reason: String = tokio::select! {
+                _ = kill_rx.recv() => {
+                    self.cancel.cancel();
+                    break;
+                }
+                _ = self.cancel.cancelled() => break,
+                reason = watch_pod_until_gone(pods_api.clone(), current.clone()) => reason,
+                Some(failed) = failover_rx.recv() => {
+                    // Failures reported against a pod we already moved away from are stale
+                    if failed != current {
+                        continue;
+                    }
+                    format!("connection to pod {} failed", failed)
+                }
+            };
+
+            match self.reconnect(reason).await {
+                Some(pod_name) => *self.target.write().await = pod_name,
+                None => break,
+            }
+        }
+    }
+
+    // Re-resolve a ready pod with exponential backoff until one is found or the session stops.
+    async fn reconnect(&self, reason: String) -> Option<String> {
+        let manager = self.app.state::<PortForwardManager>();
+        manager.set_status(&self.session_id, "Reconnecting").await;
+
+        let mut reason: String = reason;
+        let mut delay: Duration = RECONNECT_INITIAL_DELAY;
+        let mut attempt: u32 = 0;
+        loop {
+            attempt += 1;
+            emit_event(
+                &self.app,
+                &self.event_name,
+                "PF_RECONNECTING",
+                format!("{}; looking for a ready pod (attempt {})", reason, attempt),
+            );
+
+            match self.resolver.resolve().await {
+                Ok(pod) if pod_gone_reason(&pod).is_none() => {
+                    let pod_name: String = pod.metadata.name.unwrap_or_default();
+                    manager.set_status(&self.session_id, "Running").await;
+                    emit_event(
+                        &self.app,
+                        &self.event_name,
+                        "PF_RECONNECTED",
+                        format!("forwarding to pod {}", pod_name),
+                    );
+                    return Some(pod_name);
+                }
+                Ok(pod) => reason = pod_gone_reason(&pod).unwrap_or_default(),
+                Err(e) => reason = e,
+            }
+
+            tokio::select! {
+                _ = self.cancel.cancelled() => return None,
+                _ = tokio::time::sleep(delay) => {}
+            }
+            delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+        }
+    }
+}
+
+fn pod_gone_reason(pod: &Pod) -> Option<String> {
+    let name: String = pod.metadata.name.clone().unwrap_or_default();
+    if pod.metadata.deletion_timestamp.is_some() {
+        return Some(format!("pod {} is terminating", name));
+    }
+    match pod.status.as_ref().and_then(|s| s.phase.as_deref()) {
+        Some("Failed") | Some("Succeeded") => Some(format!("pod {} has stopped", name)),
+        _ => None,
+    }
+}
+
+// Resolves once the pod is deleted, terminating or finished. Watch errors and
+// timeouts just restart the watch.
+async fn watch_pod_until_gone(pods_api: Api<Pod>, pod_name: String) -> String {
+    loop {
+        let resource_version: String = match pods_api.get_opt(&pod_name).await {
+            Ok(None) => return format!("pod {} was deleted", pod_name),
+            Ok(Some(pod)) => {
+                if let Some(reason) = pod_gone_reason(&pod) {
+                    return reason;
+                }
+                pod.metadata.resource_version.unwrap_or_default()
+            }
+            Err(_) => {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                continue;
+            }
+        };
+
+        let wp: WatchParams = WatchParams::default().fields(&format!("metadata.name={}", pod_name));
+        if let Ok(stream) = pods_api.watch(&wp, &resource_version).await {
+            let mut stream = stream.boxed();
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(WatchEvent::Deleted(_)) => return format!("pod {} was deleted", pod_name),
+                    Ok(WatchEvent::Added(pod)) | Ok(WatchEvent::Modified(pod)) => {
+                        if let Some(reason) = pod_gone_reason(&pod) {
+                            return reason;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+// Per-session handles shared by the accept loops and the connections they spawn.
+#[derive(Clone)]
+struct ConnectionContext {
+    app: AppHandle,
+    event_name: String,
+    pods_api: Api<Pod>,
+    failover_tx: mpsc::Sender<String>,
+    cancel: CancellationToken,
+    stats: Arc<PortForwardStats>,
+}
+
+impl ConnectionContext {
+    fn report_error(&self, message: String) {
+        self.stats.record_error(message.clone());
+        emit_event(&self.app, &self.event_name, "PF_ERROR", message);
+    }
+}
+
+// Emits PF_STATS while the session runs, skipping ticks where nothing changed.
+async fn emit_stats_loop(
+    app: AppHandle,
+    event_name: String,
+    stats: Arc<PortForwardStats>,
+    cancel: CancellationToken,
+) {
+    let mut last: Option<PortForwardStatsSnapshot> = None;
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = tokio::time::sleep(STATS_INTERVAL) => {}
+        }
+        let snapshot: PortForwardStatsSnapshot = stats.snapshot();
+        if last.as_ref() == Some(&snapshot) {
+            continue;
+        }
+        let _ = app.emit(
+            &event_name,
+            PortForwardStatsPayload {
+                r#type: "PF_STATS".to_string(),
+                stats: snapshot.clone(),
+            },
+        );
+        last = Some(snapshot);
+    }
+}
+
+async fn accept_loop(
+    ctx: ConnectionContext,
+    target: Arc<RwLock<String>>,
+    listener: TcpListener,
+    port: PortForwardPort,
+) {
+    loop {
+        tokio::select! {
+            _ = ctx.cancel.cancelled() => break,
+            incoming = listener.accept() => match incoming {
+                Ok((socket, addr)) => {
+                    let pod_name: String = target.read().await.clone();
+                    tokio::spawn(forward_connection(
+                        ctx.clone(),
+                        pod_name,
+                        socket,
+                        addr,
+                        port.local_port,
+                        port.remote_port,
+                    ));
+                }
+                Err(e) => ctx.report_error(format!("listener accept error: {}", e)),
+            }
+        }
+    }
+}
+
+// Records the connection in the session stats around the actual forwarding.
+async fn forward_connection(
+    ctx: ConnectionContext,
+    pod_name: String,
+    socket: TcpStream,
+    client_addr: SocketAddr,
+    local_port: u16,
+    remote_port: u16,
+) {
+    let conn_id: u64 = ctx.stats.open_connection(
+        client_addr.to_string(),
+        local_port,
+        remote_port,
+        pod_name.clone(),
+    );
+    let received: AtomicU64 = AtomicU64::new(0);
+    let sent: AtomicU64 = AtomicU64::new(0);
+
+    let result: Result<(), String> =
+        pipe_connection(&ctx, pod_name, socket, remote_port, &received, &sent).await;
+    if let Err(e) = &result {
+        emit_event(&ctx.app, &ctx.event_name, "PF_ERROR", e.clone());
+    }
+    ctx.stats.close_connection(
+        conn_id,
+        received.load(Ordering::Relaxed),
+        sent.load(Ordering::Relaxed),
+        result.err(),
+    );
+}
+
+// Each port-forward stream carries exactly one connection, so open a fresh
+// forwarder per accepted client. Websocket failures are reported to the supervisor.
+async fn pipe_connection(
+    ctx: &ConnectionContext,
+    pod_name: String,
+    mut socket: TcpStream,
+    remote_port: u16,
+    received: &AtomicU64,
+    sent: &AtomicU64,
+) -> Result<(), String> {
+    let mut pf = match ctx.pods_api.portforward(&pod_name, &[remote_port]).await {
+        Ok(pf) => pf,
+        Err(e) => {
+            let _ = ctx.failover_tx.try_send(pod_name);
+            return Err(format!("portforward start error: {}", e));
+        }
+    };
+
+    let Some(remote) = pf.take_stream(remote_port) else {
+        return Err(format!("no remote stream available for port {}", remote_port));
+    };
+
+    let (client_read, client_write) = socket.split();
+    let (remote_read, remote_write) = io::split(remote);
+    let upload = copy_counted(client_read, remote_write, |n| {
+        sent.fetch_add(n, Ordering::Relaxed);
+        ctx.stats.add_bytes_out(n);
+    });
+    let download = copy_counted(remote_read, client_write, |n| {
+        received.fetch_add(n, Ordering::Relaxed);
+        ctx.stats.add_bytes_in(n);
+    });
+
+    tokio::select! {
+        _ = ctx.cancel.cancelled() => {
+            // cancellation: drop sockets
+            pf.abort();
+            Ok(())
+        }
+        copied = async { tokio::try_join!(upload, download) } => {
+            if let Err(e) = pf.join().await {
+                let _ = ctx.failover_tx.try_send(pod_name);
+                return Err(format!("portforward error: {}", e));
+            }
+            copied.map(|_| ()).map_err(|e| format!("connection error: {}", e))
+        }
+    }
+}
+
+// Like `io::copy`, but reports progress as it goes so PF_STATS reflects live traffic.
+async fn copy_counted<R, W>(mut reader: R, mut writer: W, on_bytes: impl Fn(u64)) -> io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buf: Vec<u8> = vec![0u8; 16 * 1024];
+    loop {
+        let n: usize = reader.read(&mut buf).await?;
+        if n == 0 {
+            return writer.shutdown().await;
+        }
+        writer.write_all(&buf[..n]).await?;
+        on_bytes(n as u64);
     }
 }
@@ -0,0 +1,565 @@
+use crate::constants::prometheus::{
+    PROMETHEUS_BUILTIN_QUERIES, PROMETHEUS_DEFAULT_RATE, PROMETHEUS_MAX_POINTS,
+    PROMETHEUS_REQUEST_TIMEOUT, PROMETHEUS_WORKLOAD_POD_PATTERNS,
+};
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::port_forward::PodResolver;
+use crate::manager::k8s::service_proxy::ServiceProxyTarget;
+use crate::state::AppState;
+use crate::types::prometheus::{
+    PrometheusAccess, PrometheusCandidate, PrometheusConfig, PrometheusQueryResult,
+    PrometheusQueryTemplate, PrometheusRange, PrometheusSeries,
+};
+use crate::utils::port_forward::PortForwardMode;
+use bytes::Bytes;
+use http::{header, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Empty};
+use hyper_util::rt::TokioIo;
+use k8s_openapi::api::core::v1::{Pod, Service, ServicePort};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::{Api, ListParams};
+use kube::Client;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+// Placeholders a query template may use, filled from the run's variables. `$workload_pods`
+// comes before `$workload` so the shorter placeholder does not eat its prefix.
+const TEMPLATE_VARIABLES: [&str; 6] = [
+    "namespace",
+    "pod",
+    "workload_pods",
+    "workload",
+    "node",
+    "rate",
+];
+
+/// Runs PromQL against the Prometheus configured for a context, reached through the
+/// apiserver service proxy or a port-forward to one of its pods.
+pub struct K8sPrometheus;
+
+impl K8sPrometheus {
+    pub fn config(
+        app_state: &AppState,
+        context_name: &str,
+    ) -> Result<Option<PrometheusConfig>, String> {
+        app_state.prometheus.get_config(context_name).map_err(|e| e.to_string())
+    }
+
+    pub fn save_config(
+        app_state: &AppState,
+        context_name: &str,
+        mut config: PrometheusConfig,
+    ) -> Result<PrometheusConfig, String> {
+        config.namespace = config.namespace.trim().to_string();
+        config.service = config.service.trim().to_string();
+        config.port = config.port.trim().to_string();
+        if config.namespace.is_empty() || config.service.is_empty() || config.port.is_empty() {
+            return Err("namespace, service and port are required".to_string());
+        }
+        let https: bool = match config.scheme.as_deref().map(|s| s.trim().to_lowercase()) {
+            None => false,
+            Some(s) if s.is_empty() || s == "http" => false,
+            Some(s) if s == "https" => true,
+            Some(s) => return Err(format!("unsupported scheme {}; use http or https", s)),
+        };
+        if https && config.access == PrometheusAccess::PortForward {
+            return Err(
+                "HTTPS Prometheus can only be reached through the service proxy".to_string()
+            );
+        }
+        config.scheme = Some(if https { "https" } else { "http" }.to_string());
+        config.path_prefix = config
+            .path_prefix
+            .map(|p| p.trim().trim_end_matches('/').to_string())
+            .filter(|p| !p.is_empty())
+            .map(|p| if p.starts_with('/') { p } else { format!("/{}", p) });
+
+        let mut ids: Vec<&str> = Vec::new();
+        for q in config.queries.iter() {
+            if q.id.trim().is_empty() || q.expr.trim().is_empty() {
+                return Err("custom queries need an id and an expression".to_string());
+            }
+            if ids.contains(&q.id.as_str()) {
+                return Err(format!("duplicate query id {}", q.id));
+            }
+            ids.push(&q.id);
+        }
+        for q in config.queries.iter_mut() {
+            q.builtin = false;
+        }
+
+        app_state.prometheus.save_config(context_name, &config).map_err(|e| e.to_string())?;
+        Ok(config)
+    }
+
+    pub fn delete_config(app_state: &AppState, context_name: &str) -> Result<(), String> {
+        app_state.prometheus.delete_config(context_name).map_err(|e| e.to_string())
+    }
+
+    /// Built-in queries with the context's overrides applied, followed by its own queries.
+    pub fn queries(
+        app_state: &AppState,
+        context_name: &str,
+    ) -> Result<Vec<PrometheusQueryTemplate>, String> {
+        let custom: Vec<PrometheusQueryTemplate> =
+            Self::config(app_state, context_name)?.map(|c| c.queries).unwrap_or_default();
+        let mut queries: Vec<PrometheusQueryTemplate> = PROMETHEUS_BUILTIN_QUERIES
+            .iter()
+            .map(|(id, title, scope, unit, expr)| {
+                custom.iter().find(|q| q.id == *id).cloned().unwrap_or(PrometheusQueryTemplate {
+                    id: id.to_string(),
+                    title: title.to_string(),
+                    scope: scope.to_string(),
+                    unit: unit.to_string(),
+                    expr: expr.to_string(),
+                    builtin: true,
+                })
+            })
+            .collect();
+        for q in custom {
+            if !queries.iter().any(|b| b.id == q.id) {
+                queries.push(q);
+            }
+        }
+        Ok(queries)
+    }
+
+    /// Instant query at `time` (unix seconds), or now.
+    pub async fn query(
+        app_state: &AppState,
+        context_name: &str,
+        query: String,
+        time: Option<f64>,
+    ) -> Result<PrometheusQueryResult, String> {
+        let config: PrometheusConfig = Self::required_config(app_state, context_name)?;
+        let mut params: Vec<(&str, String)> = vec![("query", query.clone())];
+        if let Some(t) = time {
+            params.push(("time", t.to_string()));
+        }
+        let body: Value = Self::get(context_name, &config, "/api/v1/query", &params).await?;
+        Ok(Self::parse_result(query, body))
+    }
+
+    pub async fn query_range(
+        app_state: &AppState,
+        context_name: &str,
+        query: String,
+        range: PrometheusRange,
+    ) -> Result<PrometheusQueryResult, String> {
+        if range.step <= 0.0 {
+            return Err("step must be positive".to_string());
+        }
+        if range.end < range.start {
+            return Err("range end must not be before its start".to_string());
+        }
+        if (range.end - range.start) / range.step > PROMETHEUS_MAX_POINTS {
+            return Err(format!(
+                "range has more than {} points per series; use a larger step",
+                PROMETHEUS_MAX_POINTS
+            ));
+        }
+        let config: PrometheusConfig = Self::required_config(app_state, context_name)?;
+        let params: Vec<(&str, String)> = vec![
+            ("query", query.clone()),
+            ("start", range.start.to_string()),
+            ("end", range.end.to_string()),
+            ("step", range.step.to_string()),
+        ];
+        let body: Value = Self::get(context_name, &config, "/api/v1/query_range", &params).await?;
+        Ok(Self::parse_result(query, body))
+    }
+
+    /// Run a built-in or configured query; a range query when `range` is set.
+    pub async fn run_query(
+        app_state: &AppState,
+        context_name: &str,
+        query_id: &str,
+        variables: HashMap<String, String>,
+        range: Option<PrometheusRange>,
+    ) -> Result<PrometheusQueryResult, String> {
+        let template: PrometheusQueryTemplate = Self::queries(app_state, context_name)?
+            .into_iter()
+            .find(|q| q.id == query_id)
+            .ok_or_else(|| format!("unknown query {}", query_id))?;
+        let query: String = Self::render(&template, &variables)?;
+        match range {
+            Some(r) => Self::query_range(app_state, context_name, query, r).await,
+            None => Self::query(app_state, context_name, query, None).await,
+        }
+    }
+
+    /// Reachability check; returns the Prometheus version.
+    pub async fn check(app_state: &AppState, context_name: &str) -> Result<String, String> {
+        let config: PrometheusConfig = Self::required_config(app_state, context_name)?;
+        let body: Value = Self::get(context_name, &config, "/api/v1/status/buildinfo", &[]).await?;
+        Ok(body.pointer("/data/version").and_then(|v| v.as_str()).unwrap_or("unknown").to_string())
+    }
+
+    /// Services that look like a Prometheus server, to prefill the configuration.
+    pub async fn discover(context_name: &str) -> Result<Vec<PrometheusCandidate>, String> {
+        let client: Client = K8sClient::for_context(context_name).await?;
+        let svc_api: Api<Service> = Api::all(client);
+        let services = svc_api
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| format!("failed to list services: {}", e))?;
+        let excluded: [&str; 6] = [
+            "operator",
+            "alertmanager",
+            "exporter",
+            "pushgateway",
+            "adapter",
+            "kube-state",
+        ];
+
+        let mut candidates: Vec<PrometheusCandidate> = services
+            .items
+            .into_iter()
+            .filter_map(|svc| {
+                let name: String = svc.metadata.name.clone()?;
+                if !name.contains("prometheus") || excluded.iter().any(|x| name.contains(x)) {
+                    return None;
+                }
+                let ports: Vec<ServicePort> = svc.spec.and_then(|s| s.ports).unwrap_or_default();
+                let port: &ServicePort = ports.iter().find(|p| p.port == 9090).or_else(|| {
+                    ports.iter().find(|p| {
+                        matches!(p.name.as_deref(), Some("web") | Some("http-web") | Some("http"))
+                    })
+                })?;
+                Some(PrometheusCandidate {
+                    namespace: svc.metadata.namespace.clone().unwrap_or_default(),
+                    service: name,
+                    port: port.name.clone().unwrap_or_else(|| port.port.to_string()),
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| (&a.namespace, &a.service).cmp(&(&b.namespace, &b.service)));
+        Ok(candidates)
+    }
+
+    fn required_config(
+        app_state: &AppState,
+        context_name: &str,
+    ) -> Result<PrometheusConfig, String> {
+        Self::config(app_state, context_name)?
+            .ok_or_else(|| format!("Prometheus is not configured for {}", context_name))
+    }
+
+    // Fill in placeholders. Values are escaped for a PromQL string literal; `$workload`
+    // sits inside a regex matcher, so its regex metacharacters are escaped as well.
+    fn render(
+        template: &PrometheusQueryTemplate,
+        variables: &HashMap<String, String>,
+    ) -> Result<String, String> {
+        let mut expr: String = template.expr.clone();
+        for name in TEMPLATE_VARIABLES {
+            let placeholder: String = format!("${}", name);
+            if !expr.contains(&placeholder) {
+                continue;
+            }
+            let value: String = match (name, variables.get(name)) {
+                ("rate", v) => {
+                    let rate: &str = v.map(|s| s.trim()).unwrap_or(PROMETHEUS_DEFAULT_RATE);
+                    if !Self::is_duration(rate) {
+                        return Err(format!("invalid rate interval {}", rate));
+                    }
+                    rate.to_string()
+                }
+                ("workload_pods", _) => Self::workload_pods(template, variables)?,
+                ("workload", Some(v)) => Self::escape_regex(v),
+                (_, Some(v)) => Self::escape_string(v),
+                (_, None) => {
+                    return Err(format!("query {} needs the {} variable", template.id, name))
+                }
+            };
+            expr = expr.replace(&placeholder, &value);
+        }
+        Ok(expr)
+    }
+
+    // Regex for the pods of `$workload`, exact for its `workload_kind` so workloads
+    // sharing a name prefix (web and web-api) are not mixed together.
+    fn workload_pods(
+        template: &PrometheusQueryTemplate,
+        variables: &HashMap<String, String>,
+    ) -> Result<String, String> {
+        let workload: &String = variables
+            .get("workload")
+            .ok_or_else(|| format!("query {} needs the workload variable", template.id))?;
+        let kind: &String = variables
+            .get("workload_kind")
+            .ok_or_else(|| format!("query {} needs the workload_kind variable", template.id))?;
+        let suffix: &str = PROMETHEUS_WORKLOAD_POD_PATTERNS
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(kind.trim()))
+            .map(|(_, pattern)| *pattern)
+            .ok_or_else(|| format!("unsupported workload kind {}", kind))?;
+        // The suffix has no quotes or backslashes, so it is safe in the string literal as is
+        Ok(format!("{}{}", Self::escape_regex(workload), suffix))
+    }
+
+    fn escape_string(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    fn escape_regex(value: &str) -> String {
+        let mut escaped: String = String::new();
+        for c in value.chars() {
+            if "\\.+*?()|[]{}^$".contains(c) {
+                // One backslash for the regex, doubled for the string literal
+                escaped.push_str("\\\\");
+            } else if c == '"' {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    // PromQL durations such as 30s, 5m or 1h30m.
+    fn is_duration(value: &str) -> bool {
+        let units: [&str; 7] = ["ms", "s", "m", "h", "d", "w", "y"];
+        let mut rest: &str = value;
+        if rest.is_empty() {
+            return false;
+        }
+        while !rest.is_empty() {
+            let digits: usize = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+            if digits == 0 {
+                return false;
+            }
+            rest = &rest[digits..];
+            match units.iter().find(|u| rest.starts_with(**u)) {
+                Some(u) => rest = &rest[u.len()..],
+                None => return false,
+            }
+        }
+        true
+    }
+
+    async fn get(
+        context_name: &str,
+        config: &PrometheusConfig,
+        api_path: &str,
+        params: &[(&str, String)],
+    ) -> Result<Value, String> {
+        let mut path: String =
+            format!("{}{}", config.path_prefix.as_deref().unwrap_or_default(), api_path);
+        if !params.is_empty() {
+            let query: String =
+                form_urlencoded::Serializer::new(String::new()).extend_pairs(params).finish();
+            path = format!("{}?{}", path, query);
+        }
+        let client: Client = K8sClient::for_context(context_name).await?;
+        let request = async {
+            match config.access {
+                PrometheusAccess::ServiceProxy => Self::send_proxy(client, config, &path).await,
+                PrometheusAccess::PortForward => {
+                    Self::send_port_forward(client, config, &path).await
+                }
+            }
+        };
+        let (status, body): (StatusCode, Bytes) =
+            tokio::time::timeout(PROMETHEUS_REQUEST_TIMEOUT, request)
+                .await
+                .map_err(|_| "Prometheus request timed out".to_string())??;
+
+        // Prometheus answers errors with a JSON body as well; anything else came from a hop
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(v) if v.get("status").and_then(|s| s.as_str()) == Some("success") => Ok(v),
+            Ok(v) if v.get("status").and_then(|s| s.as_str()) == Some("error") => Err(format!(
+                "{}: {}",
+                v.get("errorType").and_then(|t| t.as_str()).unwrap_or("error"),
+                v.get("error").and_then(|t| t.as_str()).unwrap_or_default()
+            )),
+            _ => {
+                let text: String = String::from_utf8_lossy(&body).chars().take(300).collect();
+                Err(format!("Prometheus returned {}: {}", status, text.trim()))
+            }
+        }
+    }
+
+    async fn send_proxy(
+        client: Client,
+        config: &PrometheusConfig,
+        path: &str,
+    ) -> Result<(StatusCode, Bytes), String> {
+        let target: ServiceProxyTarget = ServiceProxyTarget {
+            namespace: config.namespace.clone(),
+            service: config.service.clone(),
+            port: config.port.clone(),
+            mode: if config.scheme.as_deref() == Some("https") {
+                PortForwardMode::Https
+            } else {
+                PortForwardMode::Http
+            },
+        };
+        let req: Request<Vec<u8>> = Request::get(format!("{}{}", target.base_path(), path))
+            .header(header::ACCEPT, "application/json")
+            .body(Vec::new())
+            .map_err(|e| format!("failed to build Prometheus request: {}", e))?;
+        let res: Response<kube::client::Body> = client
+            .send(req.map(kube::client::Body::from))
+            .await
+            .map_err(|e| format!("Prometheus request failed: {}", e))?;
+        let status: StatusCode = res.status();
+        let body: Bytes = res
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| format!("failed to read Prometheus response: {}", e))?
+            .to_bytes();
+        Ok((status, body))
+    }
+
+    // One HTTP/1 request over a port-forward stream to a ready pod behind the service.
+    async fn send_port_forward(
+        client: Client,
+        config: &PrometheusConfig,
+        path: &str,
+    ) -> Result<(StatusCode, Bytes), String> {
+        let resolver: PodResolver =
+            PodResolver::new(client.clone(), &config.namespace, "service", &config.service);
+        let pod: Pod = resolver.resolve().await?;
+        let pod_name: String = pod.metadata.name.clone().unwrap_or_default();
+        let port: u16 = Self::target_port(client.clone(), config, &pod).await?;
+
+        let pods_api: Api<Pod> =
+            K8sClient::api::<Pod>(client, Some(config.namespace.clone())).await;
+        let mut pf = pods_api
+            .portforward(&pod_name, &[port])
+            .await
+            .map_err(|e| format!("port-forward to {} failed: {}", pod_name, e))?;
+        let stream = pf
+            .take_stream(port)
+            .ok_or_else(|| format!("port-forward to {} has no stream", pod_name))?;
+
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(|e| format!("Prometheus connection failed: {}", e))?;
+        let conn_task = tokio::spawn(conn);
+        let req: Request<Empty<Bytes>> = Request::get(path)
+            .header(header::HOST, "localhost")
+            .header(header::ACCEPT, "application/json")
+            .body(Empty::new())
+            .map_err(|e| format!("failed to build Prometheus request: {}", e))?;
+        let result = async {
+            let res = sender
+                .send_request(req)
+                .await
+                .map_err(|e| format!("Prometheus request failed: {}", e))?;
+            let status: StatusCode = res.status();
+            let body: Bytes = res
+                .into_body()
+                .collect()
+                .await
+                .map_err(|e| format!("failed to read Prometheus response: {}", e))?
+                .to_bytes();
+            Ok((status, body))
+        }
+        .await;
+        conn_task.abort();
+        pf.abort();
+        result
+    }
+
+    // The container port behind the configured service port.
+    async fn target_port(
+        client: Client,
+        config: &PrometheusConfig,
+        pod: &Pod,
+    ) -> Result<u16, String> {
+        let svc_api: Api<Service> =
+            K8sClient::api::<Service>(client, Some(config.namespace.clone())).await;
+        let svc: Service = svc_api
+            .get(&config.service)
+            .await
+            .map_err(|e| format!("failed to get service {}: {}", config.service, e))?;
+        let ports: Vec<ServicePort> = svc.spec.and_then(|s| s.ports).unwrap_or_default();
+        let sp: &ServicePort = ports
+            .iter()
+            .find(|p| {
+                p.name.as_deref() == Some(config.port.as_str()) || p.port.to_string() == config.port
+            })
+            .ok_or_else(|| format!("service {} has no port {}", config.service, config.port))?;
+        let number: i32 = match sp.target_port.as_ref() {
+            Some(IntOrString::Int(n)) => *n,
+            Some(IntOrString::String(name)) => pod
+                .spec
+                .iter()
+                .flat_map(|s| s.containers.iter())
+                .flat_map(|c| c.ports.iter().flatten())
+                .find(|p| p.name.as_deref() == Some(name.as_str()))
+                .map(|p| p.container_port)
+                .ok_or_else(|| format!("pod has no container port named {}", name))?,
+            None => sp.port,
+        };
+        u16::try_from(number).map_err(|_| format!("invalid target port {}", number))
+    }
+
+    fn parse_result(query: String, body: Value) -> PrometheusQueryResult {
+        let data: &Value = body.get("data").unwrap_or(&Value::Null);
+        let result_type: String =
+            data.get("resultType").and_then(|t| t.as_str()).unwrap_or_default().to_string();
+        let result: &Value = data.get("result").unwrap_or(&Value::Null);
+        let series: Vec<PrometheusSeries> = match result_type.as_str() {
+            "vector" | "matrix" => result
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|item| {
+                            let metric: BTreeMap<String, String> = item
+                                .get("metric")
+                                .and_then(|m| m.as_object())
+                                .map(|m| {
+                                    m.iter()
+                                        .map(|(k, v)| {
+                                            (k.clone(), v.as_str().unwrap_or_default().to_string())
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default();
+                            let values: Vec<(f64, Option<f64>)> = match item.get("values") {
+                                Some(Value::Array(points)) => {
+                                    points.iter().filter_map(Self::sample).collect()
+                                }
+                                _ => item.get("value").and_then(Self::sample).into_iter().collect(),
+                            };
+                            PrometheusSeries { metric, values }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            // A scalar is a single [time, value] pair
+            "scalar" => vec![PrometheusSeries {
+                metric: BTreeMap::new(),
+                values: Self::sample(result).into_iter().collect(),
+            }],
+            _ => vec![],
+        };
+        let warnings: Vec<String> = body
+            .get("warnings")
+            .and_then(|w| w.as_array())
+            .map(|w| w.iter().filter_map(|s| s.as_str()).map(|s| s.to_string()).collect())
+            .unwrap_or_default();
+        PrometheusQueryResult {
+            query,
+            result_type,
+            series,
+            warnings,
+        }
+    }
+
+    // [<unix seconds>, "<value>"]; values are strings so NaN and ±Inf survive JSON.
+    fn sample(point: &Value) -> Option<(f64, Option<f64>)> {
+        let pair: &Vec<Value> = point.as_array()?;
+        let ts: f64 = pair.first()?.as_f64()?;
+        let value: Option<f64> = pair
+            .get(1)
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|v| v.is_finite());
+        Some((ts, value))
+    }
+}
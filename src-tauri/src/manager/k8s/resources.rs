@@ -16,7 +16,10 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 
-use crate::{manager::k8s::client::K8sClient, types::event::EventType};
+use crate::{
+    manager::k8s::client::K8sClient,
+    types::{delete::DeleteOptions, event::EventType},
+};
 
 pub struct K8sResources<T> {
     _marker: std::marker::PhantomData<T>,
@@ -186,15 +189,17 @@ where
         context_name: String,
         namespace: Option<String>,
         names: Vec<String>,
+        options: DeleteOptions,
     ) -> Result<Vec<Result<String, String>>, String> {
+        options.validate()?;
         let client: kube::Client = K8sClient::for_context(&context_name).await?;
 
         let futures = names.into_iter().map(|name| {
             let client: kube::Client = client.clone();
             let namespace: Option<String> = namespace.clone();
+            let dp: DeleteParams = options.params_for(&name);
             async move {
                 let api: Api<T> = K8sClient::api::<T>(client, namespace).await;
-                let dp: DeleteParams = DeleteParams::default();
                 match api.delete(&name, &dp).await {
                     Ok(_) => Ok(name),
                     Err(e) => Err(Self::extract_error(&e, &name)),
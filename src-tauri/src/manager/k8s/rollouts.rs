@@ -0,0 +1,813 @@
+use crate::constants::rollouts::{ROLLOUT_DEFAULT_TIMEOUT_SECONDS, ROLLOUT_POLL_INTERVAL};
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::resources::K8sResources;
+use crate::types::rollouts::{
+    RolloutKind, RolloutOutcome, RolloutPhase, RolloutPod, RolloutProgress, RolloutReplicaSet,
+    RolloutRevision, RolloutStatus,
+};
+use crate::utils::diff::unified_diff;
+use k8s_openapi::api::apps::v1::{
+    ControllerRevision, DaemonSet, Deployment, ReplicaSet, StatefulSet,
+};
+use k8s_openapi::api::core::v1::{Pod, PodTemplateSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+use kube::api::{Api, ListParams, Patch, PatchParams, PostParams};
+use kube::core::NamespaceResourceScope;
+use kube::{Client, Resource};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
+const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+// Added to ReplicaSet templates by the Deployment controller, never part of the Deployment's
+const POD_TEMPLATE_HASH_LABEL: &str = "pod-template-hash";
+// Set on StatefulSet and DaemonSet pods from the ControllerRevision they were created from
+const CONTROLLER_REVISION_HASH_LABEL: &str = "controller-revision-hash";
+
+// How the pods of the current revision are recognised
+enum NewPods {
+    // Owned by this ReplicaSet
+    OwnedBy(Option<String>),
+    // Created from this ControllerRevision
+    Revision(Option<String>),
+}
+
+/// The `kubectl rollout` family for Deployments, StatefulSets and DaemonSets.
+pub struct K8sRollouts;
+
+impl K8sRollouts {
+    pub async fn status(
+        context_name: String,
+        kind: RolloutKind,
+        namespace: String,
+        name: String,
+    ) -> Result<RolloutStatus, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        Ok(match kind {
+            RolloutKind::Deployment => {
+                Self::deployment_status(&Self::get::<Deployment>(client, &namespace, &name).await?)
+            }
+            RolloutKind::StatefulSet => Self::stateful_set_status(
+                &Self::get::<StatefulSet>(client, &namespace, &name).await?,
+            ),
+            RolloutKind::DaemonSet => {
+                Self::daemon_set_status(&Self::get::<DaemonSet>(client, &namespace, &name).await?)
+            }
+        })
+    }
+
+    /// Revisions oldest first, each with its pod template diffed against the one before.
+    pub async fn history(
+        context_name: String,
+        kind: RolloutKind,
+        namespace: String,
+        name: String,
+    ) -> Result<Vec<RolloutRevision>, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let mut revisions: Vec<RolloutRevision> =
+            Self::revisions(client, kind, &namespace, &name).await?.0;
+        let mut previous: Option<String> = None;
+        for revision in revisions.iter_mut() {
+            let yaml: String = serde_yaml::to_string(&revision.template).unwrap_or_default();
+            if let Some(prev) = previous.as_ref() {
+                revision.diff = unified_diff(prev, &yaml);
+            }
+            previous = Some(yaml);
+        }
+        Ok(revisions)
+    }
+
+    /// Pause or resume a Deployment rollout; the other kinds cannot be paused.
+    pub async fn set_paused(
+        context_name: String,
+        kind: RolloutKind,
+        namespace: String,
+        name: String,
+        paused: bool,
+    ) -> Result<Value, String> {
+        if kind != RolloutKind::Deployment {
+            return Err(format!("{:?} rollouts cannot be paused, only Deployments", kind));
+        }
+        let patch: Value = json!({
+            "spec": { "paused": if paused { json!(true) } else { Value::Null } }
+        });
+        K8sResources::<Deployment>::patch(
+            context_name,
+            Some(namespace),
+            name,
+            patch,
+            "merge".into(),
+        )
+        .await
+    }
+
+    /// Roll back to `to_revision`, or to the previous revision when it is None or 0.
+    pub async fn undo(
+        context_name: String,
+        kind: RolloutKind,
+        namespace: String,
+        name: String,
+        to_revision: Option<i64>,
+    ) -> Result<Value, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let (revisions, paused): (Vec<RolloutRevision>, bool) =
+            Self::revisions(client.clone(), kind, &namespace, &name).await?;
+        if paused {
+            return Err(format!("cannot roll back paused deployment {}; resume it first", name));
+        }
+        let target: &RolloutRevision = match to_revision.filter(|r| *r > 0) {
+            Some(rev) => revisions
+                .iter()
+                .find(|r| r.revision == rev)
+                .ok_or_else(|| format!("revision {} not found in the rollout history", rev))?,
+            None => revisions
+                .iter()
+                .rev()
+                .find(|r| !r.current)
+                .ok_or_else(|| "no previous revision to roll back to".to_string())?,
+        };
+        if target.current {
+            return Err(format!("revision {} is already the current one", target.revision));
+        }
+
+        match kind {
+            RolloutKind::Deployment => {
+                // Replace the whole template: a merge patch would keep fields added since
+                let api: Api<Deployment> = Api::namespaced(client, &namespace);
+                let mut deployment: Deployment =
+                    api.get(&name).await.map_err(|e| Self::extract_error(&e, &name))?;
+                let template: PodTemplateSpec = serde_json::from_value(target.template.clone())
+                    .map_err(|e| {
+                        format!("invalid template in revision {}: {}", target.revision, e)
+                    })?;
+                if let Some(spec) = deployment.spec.as_mut() {
+                    spec.template = template;
+                }
+                let updated: Deployment = api
+                    .replace(&name, &PostParams::default(), &deployment)
+                    .await
+                    .map_err(|e| Self::extract_error(&e, &name))?;
+                serde_json::to_value(&updated).map_err(|e| e.to_string())
+            }
+            // ControllerRevision data is the strategic merge patch that restores the template
+            RolloutKind::StatefulSet => {
+                let patch: Value = Self::revision_patch(client.clone(), &namespace, target).await?;
+                Self::patch_strategic::<StatefulSet>(client, &namespace, &name, patch).await
+            }
+            RolloutKind::DaemonSet => {
+                let patch: Value = Self::revision_patch(client.clone(), &namespace, target).await?;
+                Self::patch_strategic::<DaemonSet>(client, &namespace, &name, patch).await
+            }
+        }
+    }
+
+    /// Follow a rollout, emitting a `ROLLOUT_PROGRESS` snapshot whenever it changes and
+    /// `ROLLOUT_DONE` once it completes, fails or times out. The timeout defaults to the
+    /// Deployment's progressDeadlineSeconds. Failed polls are retried; the watch ends with
+    /// `ERROR` only when the workload is deleted or errors last past the timeout.
+    pub async fn watch(
+        app_handle: AppHandle,
+        context_name: String,
+        kind: RolloutKind,
+        namespace: String,
+        name: String,
+        timeout_seconds: Option<u64>,
+        event_name: String,
+    ) -> Result<(), String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let started: Instant = Instant::now();
+        let mut last: Option<RolloutProgress> = None;
+        loop {
+            let mut progress: RolloutProgress =
+                match Self::progress(client.clone(), kind, &namespace, &name).await {
+                    Ok(p) => p,
+                    // Only a deleted workload ends the watch; other errors are retried
+                    // until the rollout's timeout
+                    Err(e) => {
+                        let gone: bool =
+                            !Self::exists(client.clone(), kind, &namespace, &name).await;
+                        let timeout: u64 = timeout_seconds
+                            .or(last.as_ref().map(|p| p.timeout_seconds))
+                            .unwrap_or(ROLLOUT_DEFAULT_TIMEOUT_SECONDS);
+                        let timed_out: bool = started.elapsed() >= Duration::from_secs(timeout);
+                        match last.take() {
+                            Some(mut progress) if timed_out && !gone => {
+                                progress.outcome = Some(RolloutOutcome::TimedOut);
+                                let _ = app_handle.emit(
+                                    &event_name,
+                                    json!({ "type": "ROLLOUT_DONE", "object": &progress }),
+                                );
+                                return Ok(());
+                            }
+                            _ if gone || timed_out => {
+                                let _ = app_handle
+                                    .emit(&event_name, json!({ "type": "ERROR", "message": &e }));
+                                return Err(e);
+                            }
+                            previous => last = previous,
+                        }
+                        eprintln!("rollout {}/{}: {}", namespace, name, e);
+                        tokio::time::sleep(ROLLOUT_POLL_INTERVAL).await;
+                        continue;
+                    }
+                };
+            if let Some(timeout) = timeout_seconds {
+                progress.timeout_seconds = timeout;
+            }
+            progress.outcome = match progress.status.phase {
+                RolloutPhase::Complete => Some(RolloutOutcome::Complete),
+                RolloutPhase::Failed => Some(RolloutOutcome::Failed),
+                _ if started.elapsed() >= Duration::from_secs(progress.timeout_seconds) => {
+                    Some(RolloutOutcome::TimedOut)
+                }
+                _ => None,
+            };
+
+            if progress.outcome.is_some() {
+                let _ = app_handle
+                    .emit(&event_name, json!({ "type": "ROLLOUT_DONE", "object": &progress }));
+                return Ok(());
+            }
+            if last.as_ref() != Some(&progress) {
+                let _ = app_handle
+                    .emit(&event_name, json!({ "type": "ROLLOUT_PROGRESS", "object": &progress }));
+                last = Some(progress);
+            }
+            tokio::time::sleep(ROLLOUT_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn progress(
+        client: Client,
+        kind: RolloutKind,
+        namespace: &str,
+        name: &str,
+    ) -> Result<RolloutProgress, String> {
+        let (status, selector, timeout_seconds, replica_sets, new_pods): (
+            RolloutStatus,
+            Option<LabelSelector>,
+            u64,
+            Vec<RolloutReplicaSet>,
+            NewPods,
+        ) = match kind {
+            RolloutKind::Deployment => {
+                let deployment: Deployment =
+                    Self::get::<Deployment>(client.clone(), namespace, name).await?;
+                let current: Option<i64> = Self::revision_annotation(&deployment.metadata);
+                let api: Api<ReplicaSet> = Api::namespaced(client.clone(), namespace);
+                let replica_sets: Vec<RolloutReplicaSet> = api
+                    .list(&ListParams::default())
+                    .await
+                    .map_err(|e| format!("failed to list replica sets: {}", e))?
+                    .items
+                    .iter()
+                    .filter(|rs| Self::owned_by(&rs.metadata, &deployment.metadata))
+                    .map(|rs| {
+                        let status = rs.status.clone().unwrap_or_default();
+                        let revision: Option<i64> = Self::revision_annotation(&rs.metadata);
+                        RolloutReplicaSet {
+                            name: rs.metadata.name.clone().unwrap_or_default(),
+                            revision,
+                            desired: rs.spec.as_ref().and_then(|s| s.replicas).unwrap_or(0),
+                            ready: status.ready_replicas.unwrap_or(0),
+                            available: status.available_replicas.unwrap_or(0),
+                            new: revision.is_some() && revision == current,
+                        }
+                    })
+                    .collect();
+                let new_rs: Option<String> =
+                    replica_sets.iter().find(|rs| rs.new).map(|rs| rs.name.clone());
+                let spec = deployment.spec.as_ref();
+                (
+                    Self::deployment_status(&deployment),
+                    spec.map(|s| s.selector.clone()),
+                    spec.and_then(|s| s.progress_deadline_seconds)
+                        .map(|s| s.max(0) as u64)
+                        .unwrap_or(ROLLOUT_DEFAULT_TIMEOUT_SECONDS),
+                    replica_sets,
+                    NewPods::OwnedBy(new_rs),
+                )
+            }
+            RolloutKind::StatefulSet => {
+                let sts: StatefulSet =
+                    Self::get::<StatefulSet>(client.clone(), namespace, name).await?;
+                (
+                    Self::stateful_set_status(&sts),
+                    sts.spec.as_ref().map(|s| s.selector.clone()),
+                    ROLLOUT_DEFAULT_TIMEOUT_SECONDS,
+                    vec![],
+                    NewPods::Revision(sts.status.as_ref().and_then(|s| s.update_revision.clone())),
+                )
+            }
+            RolloutKind::DaemonSet => {
+                let ds: DaemonSet = Self::get::<DaemonSet>(client.clone(), namespace, name).await?;
+                let newest: Option<String> =
+                    Self::controller_revisions(client.clone(), &ds.metadata)
+                        .await?
+                        .into_iter()
+                        .max_by_key(|r| r.revision)
+                        .map(|r| r.name);
+                (
+                    Self::daemon_set_status(&ds),
+                    ds.spec.as_ref().map(|s| s.selector.clone()),
+                    ROLLOUT_DEFAULT_TIMEOUT_SECONDS,
+                    vec![],
+                    NewPods::Revision(newest),
+                )
+            }
+        };
+
+        let pod_api: Api<Pod> = Api::namespaced(client, namespace);
+        let lp: ListParams = match selector.as_ref() {
+            Some(sel) => ListParams::default().labels(&Self::selector_string(sel)),
+            None => ListParams::default(),
+        };
+        let pods: Vec<Pod> =
+            pod_api.list(&lp).await.map_err(|e| format!("failed to list pods: {}", e))?.items;
+        let (new, old): (Vec<Pod>, Vec<Pod>) =
+            pods.into_iter().partition(|p| Self::is_new_pod(p, name, &new_pods));
+
+        Ok(RolloutProgress {
+            status,
+            replica_sets,
+            new_pods: new.iter().map(Self::rollout_pod).collect(),
+            old_pods: old.iter().map(Self::rollout_pod).collect(),
+            timeout_seconds,
+            outcome: None,
+        })
+    }
+
+    fn is_new_pod(pod: &Pod, workload: &str, new_pods: &NewPods) -> bool {
+        match new_pods {
+            NewPods::OwnedBy(rs) => pod
+                .metadata
+                .owner_references
+                .iter()
+                .flatten()
+                .any(|o| rs.as_deref() == Some(o.name.as_str())),
+            // StatefulSet pods carry the revision name, DaemonSet pods only its hash suffix
+            NewPods::Revision(revision) => pod
+                .metadata
+                .labels
+                .as_ref()
+                .and_then(|l| l.get(CONTROLLER_REVISION_HASH_LABEL))
+                .is_some_and(|hash| {
+                    revision.as_deref().is_some_and(|r| {
+                        r == hash || r == format!("{}-{}", workload, hash).as_str()
+                    })
+                }),
+        }
+    }
+
+    fn rollout_pod(pod: &Pod) -> RolloutPod {
+        let status = pod.status.clone().unwrap_or_default();
+        let containers = status.container_statuses.clone().unwrap_or_default();
+        let ready: bool =
+            status.conditions.iter().flatten().any(|c| c.type_ == "Ready" && c.status == "True");
+        // Waiting reasons first (CrashLoopBackOff, ImagePullBackOff), then why the
+        // last container run ended (OOMKilled, Error), then pod-level reasons (Evicted)
+        let waiting = containers
+            .iter()
+            .filter_map(|c| c.state.as_ref()?.waiting.as_ref())
+            .find(|w| w.reason.is_some());
+        let terminated = containers
+            .iter()
+            .filter_map(|c| {
+                let state = c.state.as_ref();
+                state
+                    .and_then(|s| s.terminated.as_ref())
+                    .or_else(|| c.last_state.as_ref().and_then(|s| s.terminated.as_ref()))
+            })
+            .find(|t| t.reason.is_some());
+        let (reason, message): (Option<String>, Option<String>) = match (waiting, terminated) {
+            (Some(w), _) => (w.reason.clone(), w.message.clone()),
+            (None, Some(t)) => (t.reason.clone(), t.message.clone()),
+            (None, None) => (status.reason.clone(), status.message.clone()),
+        };
+
+        RolloutPod {
+            name: pod.metadata.name.clone().unwrap_or_default(),
+            phase: status.phase.clone(),
+            ready,
+            terminating: pod.metadata.deletion_timestamp.is_some(),
+            restarts: containers.iter().map(|c| c.restart_count).sum(),
+            node: pod.spec.as_ref().and_then(|s| s.node_name.clone()),
+            reason,
+            message,
+        }
+    }
+
+    fn selector_string(selector: &LabelSelector) -> String {
+        let mut parts: Vec<String> =
+            selector.match_labels.iter().flatten().map(|(k, v)| format!("{}={}", k, v)).collect();
+        for expr in selector.match_expressions.iter().flatten() {
+            let values: String = expr.values.clone().unwrap_or_default().join(",");
+            parts.push(match expr.operator.as_str() {
+                "In" => format!("{} in ({})", expr.key, values),
+                "NotIn" => format!("{} notin ({})", expr.key, values),
+                "DoesNotExist" => format!("!{}", expr.key),
+                _ => expr.key.clone(),
+            });
+        }
+        parts.join(",")
+    }
+
+    pub fn deployment_status(deployment: &Deployment) -> RolloutStatus {
+        let status = deployment.status.clone().unwrap_or_default();
+        let desired: i32 = deployment.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+        let updated: i32 = status.updated_replicas.unwrap_or(0);
+        let ready: i32 = status.ready_replicas.unwrap_or(0);
+        let available: i32 = status.available_replicas.unwrap_or(0);
+        let total: i32 = status.replicas.unwrap_or(0);
+        let conditions = status.conditions.unwrap_or_default();
+        let reasons: Vec<String> = conditions
+            .iter()
+            .filter(|c| c.type_ == "ReplicaFailure" && c.status == "True")
+            .map(|c| {
+                format!(
+                    "{}: {}",
+                    c.reason.clone().unwrap_or_default(),
+                    c.message.clone().unwrap_or_default()
+                )
+            })
+            .collect();
+        let deadline_exceeded: Option<String> = conditions
+            .iter()
+            .find(|c| {
+                c.type_ == "Progressing" && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+            })
+            .map(|c| c.message.clone().unwrap_or_else(|| "progress deadline exceeded".into()));
+
+        let (phase, message): (RolloutPhase, String) =
+            if Self::generation_pending(&deployment.metadata, status.observed_generation) {
+                (RolloutPhase::Progressing, "Waiting for the spec update to be observed".into())
+            } else if let Some(message) = deadline_exceeded {
+                (RolloutPhase::Failed, message)
+            } else if deployment.spec.as_ref().and_then(|s| s.paused) == Some(true) {
+                (RolloutPhase::Paused, "Rollout is paused".into())
+            } else if updated < desired {
+                (
+                    RolloutPhase::Progressing,
+                    format!("{} of {} new replicas have been updated", updated, desired),
+                )
+            } else if total > updated {
+                (
+                    RolloutPhase::Progressing,
+                    format!("{} old replicas are pending termination", total - updated),
+                )
+            } else if available < updated {
+                (
+                    RolloutPhase::Progressing,
+                    format!("{} of {} updated replicas are available", available, updated),
+                )
+            } else {
+                (RolloutPhase::Complete, "Successfully rolled out".into())
+            };
+
+        RolloutStatus {
+            kind: RolloutKind::Deployment,
+            namespace: deployment.metadata.namespace.clone().unwrap_or_default(),
+            name: deployment.metadata.name.clone().unwrap_or_default(),
+            phase,
+            message,
+            reasons,
+            revision: Self::revision_annotation(&deployment.metadata).map(|r| r.to_string()),
+            desired,
+            updated,
+            ready,
+            available,
+        }
+    }
+
+    pub fn stateful_set_status(stateful_set: &StatefulSet) -> RolloutStatus {
+        let status = stateful_set.status.clone().unwrap_or_default();
+        let spec = stateful_set.spec.as_ref();
+        let desired: i32 = spec.and_then(|s| s.replicas).unwrap_or(1);
+        let updated: i32 = status.updated_replicas.unwrap_or(0);
+        let ready: i32 = status.ready_replicas.unwrap_or(0);
+        let available: i32 = status.available_replicas.unwrap_or(0);
+        let strategy = spec.and_then(|s| s.update_strategy.as_ref());
+        let rolling: bool =
+            strategy.and_then(|u| u.type_.as_deref()).is_none_or(|t| t == "RollingUpdate");
+        let partition: i32 =
+            strategy.and_then(|u| u.rolling_update.as_ref()).and_then(|r| r.partition).unwrap_or(0);
+
+        let (phase, message): (RolloutPhase, String) =
+            if Self::generation_pending(&stateful_set.metadata, status.observed_generation) {
+                (RolloutPhase::Progressing, "Waiting for the spec update to be observed".into())
+            } else if !rolling {
+                (RolloutPhase::Complete, "OnDelete strategy: pods update when deleted".into())
+            } else if ready < desired {
+                (RolloutPhase::Progressing, format!("{} of {} pods are ready", ready, desired))
+            } else if partition > 0 {
+                let above: i32 = (desired - partition).max(0);
+                if updated < above {
+                    (
+                        RolloutPhase::Progressing,
+                        format!("{} of {} pods above the partition are updated", updated, above),
+                    )
+                } else {
+                    (
+                        RolloutPhase::Complete,
+                        format!("Partitioned rollout complete: {} pods updated", updated),
+                    )
+                }
+            } else if status.update_revision != status.current_revision {
+                (
+                    RolloutPhase::Progressing,
+                    format!(
+                        "{} of {} pods have been updated to revision {}",
+                        updated,
+                        desired,
+                        status.update_revision.clone().unwrap_or_default()
+                    ),
+                )
+            } else {
+                (RolloutPhase::Complete, "Rolling update complete".into())
+            };
+
+        RolloutStatus {
+            kind: RolloutKind::StatefulSet,
+            namespace: stateful_set.metadata.namespace.clone().unwrap_or_default(),
+            name: stateful_set.metadata.name.clone().unwrap_or_default(),
+            phase,
+            message,
+            reasons: vec![],
+            revision: status.update_revision.clone(),
+            desired,
+            updated,
+            ready,
+            available,
+        }
+    }
+
+    pub fn daemon_set_status(daemon_set: &DaemonSet) -> RolloutStatus {
+        let status = daemon_set.status.clone().unwrap_or_default();
+        let desired: i32 = status.desired_number_scheduled;
+        let updated: i32 = status.updated_number_scheduled.unwrap_or(0);
+        let ready: i32 = status.number_ready;
+        let available: i32 = status.number_available.unwrap_or(0);
+        let rolling: bool = daemon_set
+            .spec
+            .as_ref()
+            .and_then(|s| s.update_strategy.as_ref())
+            .and_then(|u| u.type_.as_deref())
+            .is_none_or(|t| t == "RollingUpdate");
+
+        let (phase, message): (RolloutPhase, String) =
+            if Self::generation_pending(&daemon_set.metadata, status.observed_generation) {
+                (RolloutPhase::Progressing, "Waiting for the spec update to be observed".into())
+            } else if !rolling {
+                (RolloutPhase::Complete, "OnDelete strategy: pods update when deleted".into())
+            } else if updated < desired {
+                (
+                    RolloutPhase::Progressing,
+                    format!("{} of {} new pods have been updated", updated, desired),
+                )
+            } else if available < desired {
+                (
+                    RolloutPhase::Progressing,
+                    format!("{} of {} updated pods are available", available, desired),
+                )
+            } else {
+                (RolloutPhase::Complete, "Successfully rolled out".into())
+            };
+
+        RolloutStatus {
+            kind: RolloutKind::DaemonSet,
+            namespace: daemon_set.metadata.namespace.clone().unwrap_or_default(),
+            name: daemon_set.metadata.name.clone().unwrap_or_default(),
+            phase,
+            message,
+            reasons: vec![],
+            revision: None,
+            desired,
+            updated,
+            ready,
+            available,
+        }
+    }
+
+    /// Revisions of the workload sorted oldest first (diffs left empty), and whether it
+    /// is a paused Deployment.
+    async fn revisions(
+        client: Client,
+        kind: RolloutKind,
+        namespace: &str,
+        name: &str,
+    ) -> Result<(Vec<RolloutRevision>, bool), String> {
+        let (mut revisions, paused): (Vec<RolloutRevision>, bool) = match kind {
+            RolloutKind::Deployment => {
+                let deployment: Deployment =
+                    Self::get::<Deployment>(client.clone(), namespace, name).await?;
+                let paused: bool = deployment.spec.as_ref().and_then(|s| s.paused) == Some(true);
+                (Self::replica_set_revisions(client, &deployment).await?, paused)
+            }
+            RolloutKind::StatefulSet => {
+                let sts: StatefulSet =
+                    Self::get::<StatefulSet>(client.clone(), namespace, name).await?;
+                let current: Option<String> = sts.status.and_then(|s| s.update_revision);
+                let revisions: Vec<RolloutRevision> =
+                    Self::controller_revisions(client, &sts.metadata).await?;
+                (
+                    revisions
+                        .into_iter()
+                        .map(|mut r| {
+                            r.current = current.as_deref() == Some(r.name.as_str());
+                            r
+                        })
+                        .collect(),
+                    false,
+                )
+            }
+            RolloutKind::DaemonSet => {
+                let ds: DaemonSet = Self::get::<DaemonSet>(client.clone(), namespace, name).await?;
+                (Self::controller_revisions(client, &ds.metadata).await?, false)
+            }
+        };
+        revisions.sort_by_key(|r| r.revision);
+        // DaemonSets don't record their revision; the newest one is live
+        if kind == RolloutKind::DaemonSet {
+            if let Some(last) = revisions.last_mut() {
+                last.current = true;
+            }
+        }
+        Ok((revisions, paused))
+    }
+
+    async fn replica_set_revisions(
+        client: Client,
+        deployment: &Deployment,
+    ) -> Result<Vec<RolloutRevision>, String> {
+        let namespace: String = deployment.metadata.namespace.clone().unwrap_or_default();
+        let api: Api<ReplicaSet> = Api::namespaced(client, &namespace);
+        let list = api
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| format!("failed to list replica sets: {}", e))?;
+        let current: Option<i64> = Self::revision_annotation(&deployment.metadata);
+
+        Ok(list
+            .items
+            .into_iter()
+            .filter(|rs| Self::owned_by(&rs.metadata, &deployment.metadata))
+            .filter_map(|rs| {
+                let revision: i64 = Self::revision_annotation(&rs.metadata)?;
+                let mut template: PodTemplateSpec =
+                    rs.spec.as_ref().and_then(|s| s.template.clone()).unwrap_or_default();
+                if let Some(labels) = template.metadata.as_mut().and_then(|m| m.labels.as_mut()) {
+                    labels.remove(POD_TEMPLATE_HASH_LABEL);
+                }
+                Some(RolloutRevision {
+                    revision,
+                    name: rs.metadata.name.clone().unwrap_or_default(),
+                    created_at: Self::created_at(&rs.metadata),
+                    change_cause: Self::change_cause(&rs.metadata),
+                    replicas: Some(rs.status.as_ref().map(|s| s.replicas).unwrap_or(0)),
+                    current: current == Some(revision),
+                    template: serde_json::to_value(&template).unwrap_or(Value::Null),
+                    diff: String::new(),
+                })
+            })
+            .collect())
+    }
+
+    async fn controller_revisions(
+        client: Client,
+        owner: &ObjectMeta,
+    ) -> Result<Vec<RolloutRevision>, String> {
+        let namespace: String = owner.namespace.clone().unwrap_or_default();
+        let api: Api<ControllerRevision> = Api::namespaced(client, &namespace);
+        let list = api
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| format!("failed to list controller revisions: {}", e))?;
+
+        Ok(list
+            .items
+            .into_iter()
+            .filter(|cr| Self::owned_by(&cr.metadata, owner))
+            .map(|cr| {
+                let mut template: Value = cr
+                    .data
+                    .as_ref()
+                    .and_then(|d| d.0.pointer("/spec/template"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                if let Some(obj) = template.as_object_mut() {
+                    obj.remove("$patch");
+                }
+                RolloutRevision {
+                    revision: cr.revision,
+                    name: cr.metadata.name.clone().unwrap_or_default(),
+                    created_at: Self::created_at(&cr.metadata),
+                    change_cause: Self::change_cause(&cr.metadata),
+                    replicas: None,
+                    current: false,
+                    template,
+                    diff: String::new(),
+                }
+            })
+            .collect())
+    }
+
+    async fn revision_patch(
+        client: Client,
+        namespace: &str,
+        target: &RolloutRevision,
+    ) -> Result<Value, String> {
+        let revision: ControllerRevision =
+            Self::get::<ControllerRevision>(client, namespace, &target.name).await?;
+        revision
+            .data
+            .map(|d| d.0)
+            .ok_or_else(|| format!("revision {} has no data", target.revision))
+    }
+
+    async fn patch_strategic<T>(
+        client: Client,
+        namespace: &str,
+        name: &str,
+        patch: Value,
+    ) -> Result<Value, String>
+    where
+        T: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+            + Clone
+            + DeserializeOwned
+            + serde::Serialize
+            + Debug,
+    {
+        let api: Api<T> = Api::namespaced(client, namespace);
+        let updated: T = api
+            .patch(name, &PatchParams::default(), &Patch::Strategic(patch))
+            .await
+            .map_err(|e| Self::extract_error(&e, name))?;
+        serde_json::to_value(&updated).map_err(|e| e.to_string())
+    }
+
+    async fn get<T>(client: Client, namespace: &str, name: &str) -> Result<T, String>
+    where
+        T: Resource<DynamicType = (), Scope = NamespaceResourceScope>
+            + Clone
+            + DeserializeOwned
+            + Debug,
+    {
+        let api: Api<T> = Api::namespaced(client, namespace);
+        api.get(name).await.map_err(|e| Self::extract_error(&e, name))
+    }
+
+    // False only when the workload itself is not found; other errors count as present
+    async fn exists(client: Client, kind: RolloutKind, namespace: &str, name: &str) -> bool {
+        let found: Result<bool, kube::Error> = match kind {
+            RolloutKind::Deployment => Api::<Deployment>::namespaced(client, namespace)
+                .get_opt(name)
+                .await
+                .map(|d| d.is_some()),
+            RolloutKind::StatefulSet => Api::<StatefulSet>::namespaced(client, namespace)
+                .get_opt(name)
+                .await
+                .map(|s| s.is_some()),
+            RolloutKind::DaemonSet => Api::<DaemonSet>::namespaced(client, namespace)
+                .get_opt(name)
+                .await
+                .map(|d| d.is_some()),
+        };
+        found.unwrap_or(true)
+    }
+
+    fn generation_pending(meta: &ObjectMeta, observed: Option<i64>) -> bool {
+        meta.generation.unwrap_or(0) > observed.unwrap_or(0)
+    }
+
+    fn owned_by(meta: &ObjectMeta, owner: &ObjectMeta) -> bool {
+        meta.owner_references.iter().flatten().any(|o| owner.uid.as_deref() == Some(&o.uid))
+    }
+
+    fn revision_annotation(meta: &ObjectMeta) -> Option<i64> {
+        meta.annotations.as_ref()?.get(REVISION_ANNOTATION)?.parse().ok()
+    }
+
+    fn change_cause(meta: &ObjectMeta) -> Option<String> {
+        meta.annotations.as_ref()?.get(CHANGE_CAUSE_ANNOTATION).cloned()
+    }
+
+    fn created_at(meta: &ObjectMeta) -> Option<String> {
+        meta.creation_timestamp.as_ref().map(|t| t.0.to_rfc3339())
+    }
+
+    fn extract_error(e: &kube::Error, name: &str) -> String {
+        match e {
+            kube::Error::Api(ae) => {
+                if ae.message.is_empty() {
+                    format!("{}: resource {}", ae.reason, name)
+                } else {
+                    ae.message.clone()
+                }
+            }
+            other => format!("{}: {}", name, other),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::dynamic_resources::DynamicK8sResources;
+use crate::manager::k8s::resources::K8sResources;
+use crate::types::scaling::{ScaleAutoscaler, ScaleResult};
+use k8s_openapi::api::autoscaling::v1::{HorizontalPodAutoscaler, Scale};
+use kube::api::{Api, ApiResource, DynamicObject, ListParams, Patch, PatchParams};
+use kube::discovery::{ApiCapabilities, Scope};
+use kube::Client;
+use serde_json::{json, Value};
+
+/// Scaling through the `/scale` subresource of any kind that has one, custom resources
+/// included, with a check for HorizontalPodAutoscalers that would undo the change.
+pub struct K8sScaling;
+
+impl K8sScaling {
+    /// Set the replicas of a resource. When an HPA targets it the scale is only applied
+    /// with `force`; otherwise the result carries the autoscaler and a warning.
+    pub async fn scale(
+        context_name: String,
+        api_version: String,
+        kind: String,
+        namespace: Option<String>,
+        name: String,
+        replicas: i32,
+        force: bool,
+    ) -> Result<ScaleResult, String> {
+        if replicas < 0 {
+            return Err("replicas must not be negative".to_string());
+        }
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let (api, ar): (Api<DynamicObject>, ApiResource) =
+            Self::scalable_api(client.clone(), &api_version, &kind, namespace.as_deref()).await?;
+        let autoscaler: Option<ScaleAutoscaler> = match namespace.as_deref() {
+            Some(ns) => Self::autoscaler_for(client, ns, &ar, &name).await,
+            None => None,
+        };
+        if let Some(hpa) = autoscaler.as_ref().filter(|_| !force) {
+            return Ok(ScaleResult {
+                scaled: false,
+                previous_replicas: None,
+                replicas: None,
+                warning: Some(Self::autoscaler_warning(hpa, &kind, &name)),
+                autoscaler,
+            });
+        }
+
+        let previous: Scale =
+            api.get_scale(&name).await.map_err(|e| Self::extract_error(&e, &name))?;
+        let patch: Value = json!({ "spec": { "replicas": replicas } });
+        let scale: Scale = api
+            .patch_scale(&name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .map_err(|e| Self::extract_error(&e, &name))?;
+        Ok(ScaleResult {
+            scaled: true,
+            previous_replicas: previous.spec.and_then(|s| s.replicas),
+            replicas: scale.spec.and_then(|s| s.replicas),
+            warning: autoscaler.as_ref().map(|hpa| Self::autoscaler_warning(hpa, &kind, &name)),
+            autoscaler,
+        })
+    }
+
+    /// Scale, then return the updated object. A scale held back by an autoscaler is an
+    /// error carrying the warning, for the per-kind commands.
+    pub async fn scale_object(
+        context_name: String,
+        api_version: &str,
+        kind: &str,
+        namespace: Option<String>,
+        name: String,
+        replicas: i32,
+        force: bool,
+    ) -> Result<Value, String> {
+        let result: ScaleResult = Self::scale(
+            context_name.clone(),
+            api_version.to_string(),
+            kind.to_string(),
+            namespace.clone(),
+            name.clone(),
+            replicas,
+            force,
+        )
+        .await?;
+        if !result.scaled {
+            return Err(result.warning.unwrap_or_default());
+        }
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let (api, _): (Api<DynamicObject>, ApiResource) =
+            DynamicK8sResources::discovered_api(client, api_version, kind, namespace.as_deref())
+                .await?;
+        let obj: DynamicObject =
+            api.get(&name).await.map_err(|e| Self::extract_error(&e, &name))?;
+        serde_json::to_value(&obj).map_err(|e| e.to_string())
+    }
+
+    /// Change the replica bounds of an autoscaler instead of scaling its target.
+    pub async fn update_autoscaler(
+        context_name: String,
+        namespace: String,
+        name: String,
+        min_replicas: Option<i32>,
+        max_replicas: Option<i32>,
+    ) -> Result<Value, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let api: Api<HorizontalPodAutoscaler> = Api::namespaced(client, &namespace);
+        let hpa: HorizontalPodAutoscaler =
+            api.get(&name).await.map_err(|e| Self::extract_error(&e, &name))?;
+        let spec = hpa.spec.unwrap_or_default();
+        let min: i32 = min_replicas.or(spec.min_replicas).unwrap_or(1);
+        let max: i32 = max_replicas.unwrap_or(spec.max_replicas);
+        if min < 1 {
+            return Err("minReplicas must be at least 1".to_string());
+        }
+        if max < min {
+            return Err(format!("maxReplicas ({}) must not be below minReplicas ({})", max, min));
+        }
+
+        let patch: Value = json!({ "spec": { "minReplicas": min, "maxReplicas": max } });
+        K8sResources::<HorizontalPodAutoscaler>::patch(
+            context_name,
+            Some(namespace),
+            name,
+            patch,
+            "merge".into(),
+        )
+        .await
+    }
+
+    async fn scalable_api(
+        client: Client,
+        api_version: &str,
+        kind: &str,
+        namespace: Option<&str>,
+    ) -> Result<(Api<DynamicObject>, ApiResource), String> {
+        if kind == "DaemonSet" {
+            return Err(
+                "DaemonSets run one pod per eligible node and cannot be scaled; use a node \
+                 selector or affinity to change where they run"
+                    .to_string(),
+            );
+        }
+        let (ar, caps): (ApiResource, ApiCapabilities) =
+            DynamicK8sResources::discover_capabilities(&client, api_version, kind).await?;
+        if !caps.subresources.iter().any(|(sub, _)| sub.plural == "scale") {
+            return Err(format!("{} has no scale subresource and cannot be scaled", kind));
+        }
+        let api: Api<DynamicObject> = match (caps.scope, namespace) {
+            (Scope::Namespaced, Some(ns)) => Api::namespaced_with(client, ns, &ar),
+            (Scope::Namespaced, None) => {
+                return Err(format!("{} is namespaced, a namespace is required", kind))
+            }
+            (Scope::Cluster, _) => Api::all_with(client, &ar),
+        };
+        Ok((api, ar))
+    }
+
+    // Best effort: without permission to list HPAs the scale goes ahead unchecked
+    async fn autoscaler_for(
+        client: Client,
+        namespace: &str,
+        ar: &ApiResource,
+        name: &str,
+    ) -> Option<ScaleAutoscaler> {
+        let api: Api<HorizontalPodAutoscaler> = Api::namespaced(client, namespace);
+        let list = api.list(&ListParams::default()).await.ok()?;
+        list.items.into_iter().find_map(|hpa| {
+            let spec = hpa.spec?;
+            let target = &spec.scale_target_ref;
+            let target_group: &str = target
+                .api_version
+                .as_deref()
+                .and_then(|v| v.split_once('/'))
+                .map(|(g, _)| g)
+                .unwrap_or("");
+            if target.kind != ar.kind || target.name != name || target_group != ar.group {
+                return None;
+            }
+            let status = hpa.status.unwrap_or_default();
+            Some(ScaleAutoscaler {
+                name: hpa.metadata.name.unwrap_or_default(),
+                min_replicas: spec.min_replicas,
+                max_replicas: spec.max_replicas,
+                current_replicas: status.current_replicas,
+                desired_replicas: status.desired_replicas,
+            })
+        })
+    }
+
+    fn autoscaler_warning(hpa: &ScaleAutoscaler, kind: &str, name: &str) -> String {
+        format!(
+            "{} {} is managed by HorizontalPodAutoscaler {} (min {}, max {}), which will \
+             override the replica count; change its min/max replicas instead",
+            kind,
+            name,
+            hpa.name,
+            hpa.min_replicas.unwrap_or(1),
+            hpa.max_replicas
+        )
+    }
+
+    fn extract_error(e: &kube::Error, name: &str) -> String {
+        match e {
+            kube::Error::Api(ae) => {
+                if ae.message.is_empty() {
+                    format!("{}: resource {}", ae.reason, name)
+                } else {
+                    ae.message.clone()
+                }
+            }
+            other => format!("{}: {}", name, other),
+        }
+    }
+}
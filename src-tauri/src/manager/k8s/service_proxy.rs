@@ -0,0 +1,447 @@
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::port_forward::emit_event;
+use crate::utils::port_forward::{PortForwardMode, PortForwardPort, PortForwardStats, RemotePort};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use http::{Request, Response, StatusCode, Uri};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client as HttpClient;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use k8s_openapi::api::core::v1::{Service, ServicePort};
+use kube::api::Api;
+use kube::client::ConfigExt;
+use kube::{Client, Config};
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tokio::io;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tower::util::BoxCloneSyncService;
+use tower::{ServiceBuilder, ServiceExt};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+type ProxyBody = UnsyncBoxBody<Bytes, BoxError>;
+// kube's `Client` only sends buffered request bodies, so the proxy has its own HTTP stack
+// with the same base URL, auth and headers, which streams them.
+type Upstream = BoxCloneSyncService<Request<ProxyBody>, Response<Incoming>, BoxError>;
+
+// Headers that only apply to a single hop and must not be forwarded
+const HOP_BY_HOP_HEADERS: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+];
+
+/// Service and port a proxy session forwards to through the apiserver.
+#[derive(Clone)]
+pub struct ServiceProxyTarget {
+    pub namespace: String,
+    pub service: String,
+    // Service port name, or its number when the port is unnamed
+    pub port: String,
+    pub mode: PortForwardMode,
+}
+
+impl ServiceProxyTarget {
+    // e.g. /api/v1/namespaces/monitoring/services/https:grafana:3000/proxy
+    pub(crate) fn base_path(&self) -> String {
+        let scheme: &str = if self.mode == PortForwardMode::Https { "https:" } else { "" };
+        format!(
+            "/api/v1/namespaces/{}/services/{}{}:{}/proxy",
+            self.namespace, scheme, self.service, self.port
+        )
+    }
+}
+
+/// Local HTTP reverse proxy that sends every request through the apiserver's
+/// `services/{name}:{port}/proxy` subresource with the context's credentials.
+pub struct ServiceProxy {
+    app: AppHandle,
+    event_name: String,
+    upstream: Upstream,
+    target: ServiceProxyTarget,
+    stats: Arc<PortForwardStats>,
+}
+
+/// One accepted client connection. Shared by its requests, their bodies and a websocket
+/// splice, and logged as closed with its byte counts once the last of them is done.
+struct ProxyConnection {
+    id: u64,
+    stats: Arc<PortForwardStats>,
+    cancel: CancellationToken,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    error: Mutex<Option<String>>,
+}
+
+impl ProxyConnection {
+    // Received from the service
+    fn add_in(&self, n: u64) {
+        self.bytes_in.fetch_add(n, Ordering::Relaxed);
+        self.stats.add_bytes_in(n);
+    }
+
+    // Sent to the service
+    fn add_out(&self, n: u64) {
+        self.bytes_out.fetch_add(n, Ordering::Relaxed);
+        self.stats.add_bytes_out(n);
+    }
+
+    fn set_error(&self, error: String) {
+        *self.error.lock().unwrap_or_else(|e| e.into_inner()) = Some(error);
+    }
+}
+
+impl Drop for ProxyConnection {
+    fn drop(&mut self) {
+        let error: Option<String> = self.error.lock().unwrap_or_else(|e| e.into_inner()).take();
+        self.stats.close_connection(
+            self.id,
+            self.bytes_in.load(Ordering::Relaxed),
+            self.bytes_out.load(Ordering::Relaxed),
+            error,
+        );
+    }
+}
+
+impl ServiceProxy {
+    pub fn new(
+        app: AppHandle,
+        event_name: String,
+        config: &Config,
+        target: ServiceProxyTarget,
+        stats: Arc<PortForwardStats>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            app,
+            event_name,
+            upstream: Self::upstream(config)?,
+            target,
+            stats,
+        })
+    }
+
+    // Same stack as kube's default client, minus response timeouts: proxied responses
+    // and websockets may stay open for as long as the user keeps them.
+    fn upstream(config: &Config) -> Result<Upstream, String> {
+        if config.proxy_url.is_some() {
+            return Err("the HTTP proxy does not support a kubeconfig proxy-url".to_string());
+        }
+        let mut connector: HttpConnector = HttpConnector::new();
+        connector.enforce_http(false);
+        connector.set_connect_timeout(config.connect_timeout);
+        let connector = config
+            .rustls_https_connector_with_connector(connector)
+            .map_err(|e| format!("failed to set up TLS: {}", e))?;
+        let client: HttpClient<_, ProxyBody> =
+            HttpClient::builder(TokioExecutor::new()).build(connector);
+        let service = ServiceBuilder::new()
+            .layer(config.base_uri_layer())
+            .option_layer(config.auth_layer().map_err(|e| e.to_string())?)
+            .layer(config.extra_headers_layer().map_err(|e| e.to_string())?)
+            .map_err(BoxError::from)
+            .service(client);
+        Ok(BoxCloneSyncService::new(service))
+    }
+
+    /// Look up the service port to proxy to; only TCP ports can carry HTTP.
+    pub async fn resolve_port(
+        client: Client,
+        namespace: &str,
+        service: &str,
+        requested: &RemotePort,
+    ) -> Result<PortForwardPort, String> {
+        let svc_api: Api<Service> =
+            K8sClient::api::<Service>(client, Some(namespace.to_string())).await;
+        let svc: Service = svc_api
+            .get(service)
+            .await
+            .map_err(|e| format!("failed to get service {}: {}", service, e))?;
+        let ports: Vec<ServicePort> = svc.spec.and_then(|s| s.ports).unwrap_or_default();
+        let matching: Vec<&ServicePort> = ports
+            .iter()
+            .filter(|p| match requested {
+                RemotePort::Number(n) => p.port == i32::from(*n),
+                RemotePort::Name(n) => p.name.as_deref() == Some(n.as_str()),
+            })
+            .collect();
+        // The same number may be listed for UDP too; the TCP entry is the one to proxy
+        let sp: &ServicePort = matching
+            .iter()
+            .find(|p| p.protocol.as_deref().unwrap_or("TCP").eq_ignore_ascii_case("TCP"))
+            .or(matching.first())
+            .copied()
+            .ok_or_else(|| format!("service {} has no port {}", service, requested))?;
+
+        let protocol: &str = sp.protocol.as_deref().unwrap_or("TCP");
+        if !protocol.eq_ignore_ascii_case("TCP") {
+            return Err(format!(
+                "service port {} uses {}; the HTTP proxy only supports TCP",
+                requested,
+                protocol.to_uppercase()
+            ));
+        }
+
+        Ok(PortForwardPort {
+            local_port: 0,
+            remote_port: u16::try_from(sp.port)
+                .map_err(|_| format!("invalid service port {}", sp.port))?,
+            requested_port: requested.clone(),
+            name: sp.name.clone(),
+            protocol: "TCP".to_string(),
+        })
+    }
+
+    pub async fn run(
+        self,
+        listener: TcpListener,
+        port: PortForwardPort,
+        cancel: CancellationToken,
+    ) {
+        let proxy: Arc<ServiceProxy> = Arc::new(self);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                incoming = listener.accept() => match incoming {
+                    Ok((socket, addr)) => {
+                        let proxy: Arc<ServiceProxy> = proxy.clone();
+                        let conn: Arc<ProxyConnection> = Arc::new(ProxyConnection {
+                            id: proxy.stats.open_connection(
+                                addr.to_string(),
+                                port.local_port,
+                                port.remote_port,
+                                proxy.target.service.clone(),
+                            ),
+                            stats: proxy.stats.clone(),
+                            cancel: cancel.clone(),
+                            bytes_in: AtomicU64::new(0),
+                            bytes_out: AtomicU64::new(0),
+                            error: Mutex::new(None),
+                        });
+                        tokio::spawn(async move {
+                            let service = {
+                                let conn: Arc<ProxyConnection> = conn.clone();
+                                service_fn(move |req| {
+                                    let proxy: Arc<ServiceProxy> = proxy.clone();
+                                    let conn: Arc<ProxyConnection> = conn.clone();
+                                    async move { proxy.handle(req, conn).await }
+                                })
+                            };
+                            let http = http1::Builder::new()
+                                .serve_connection(TokioIo::new(socket), service)
+                                .with_upgrades();
+                            tokio::select! {
+                                _ = conn.cancel.cancelled() => {}
+                                res = http => if let Err(e) = res {
+                                    conn.set_error(format!("connection error: {}", e));
+                                },
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        let message: String = format!("listener accept error: {}", e);
+                        report_error(&proxy, message);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn handle(
+        &self,
+        req: Request<Incoming>,
+        conn: Arc<ProxyConnection>,
+    ) -> Result<Response<ProxyBody>, Infallible> {
+        match self.forward(req, conn).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                report_error(self, e.clone());
+                Ok(error_response(StatusCode::BAD_GATEWAY, e))
+            }
+        }
+    }
+
+    // Boxed outside of `forward`, which otherwise fails the Send check of the spawned
+    // connection task on the boxed error's lifetime
+    fn send(
+        &self,
+        req: Request<ProxyBody>,
+    ) -> BoxFuture<'static, Result<Response<Incoming>, BoxError>> {
+        Box::pin(self.upstream.clone().oneshot(req))
+    }
+
+    async fn forward(
+        &self,
+        mut req: Request<Incoming>,
+        conn: Arc<ProxyConnection>,
+    ) -> Result<Response<ProxyBody>, String> {
+        let base: String = self.target.base_path();
+        let uri: Uri = rewrite_uri(&base, req.uri())?;
+        let upgrade: Option<HeaderValue> = websocket_upgrade(req.headers());
+        let client_upgrade = upgrade.as_ref().map(|_| hyper::upgrade::on(&mut req));
+
+        let (parts, body) = req.into_parts();
+        let sent: Arc<ProxyConnection> = conn.clone();
+        let mut upstream: Request<ProxyBody> = Request::builder()
+            .method(parts.method)
+            .uri(uri)
+            .body(counted(body, move |n| sent.add_out(n)))
+            .map_err(|e| format!("failed to build proxy request: {}", e))?;
+        *upstream.headers_mut() = forward_headers(&parts.headers, upgrade);
+
+        let mut res: Response<Incoming> =
+            self.send(upstream).await.map_err(|e| format!("proxy request failed: {}", e))?;
+
+        // Websocket upgrades: once the apiserver switched protocols, splice both upgraded connections
+        if let Some(client_upgrade) = client_upgrade {
+            if res.status() == StatusCode::SWITCHING_PROTOCOLS {
+                let upstream_upgrade = hyper::upgrade::on(&mut res);
+                let app: AppHandle = self.app.clone();
+                let event_name: String = self.event_name.clone();
+                tokio::spawn(async move {
+                    match tokio::try_join!(client_upgrade, upstream_upgrade) {
+                        Ok((downstream, upstream)) => {
+                            let mut downstream = TokioIo::new(downstream);
+                            let mut upstream = TokioIo::new(upstream);
+                            tokio::select! {
+                                _ = conn.cancel.cancelled() => {}
+                                res = io::copy_bidirectional(&mut downstream, &mut upstream) => {
+                                    match res {
+                                        Ok((out, into)) => {
+                                            conn.add_out(out);
+                                            conn.add_in(into);
+                                        }
+                                        Err(e) => conn.set_error(format!("websocket error: {}", e)),
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => emit_event(
+                            &app,
+                            &event_name,
+                            "PF_ERROR",
+                            format!("websocket upgrade failed: {}", e),
+                        ),
+                    }
+                });
+                return Ok(res.map(|b| counted(b, |_| {})));
+            }
+        }
+
+        strip_hop_by_hop(res.headers_mut());
+        rewrite_location(res.headers_mut(), &base);
+        Ok(res.map(|b| counted(b, move |n| conn.add_in(n))))
+    }
+}
+
+// Stream a body through, reporting the size of each data frame.
+fn counted<B>(body: B, on_data: impl Fn(u64) + Send + 'static) -> ProxyBody
+where
+    B: hyper::body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    body.map_frame(move |frame| {
+        if let Some(data) = frame.data_ref() {
+            on_data(data.len() as u64);
+        }
+        frame
+    })
+    .map_err(Into::into)
+    .boxed_unsync()
+}
+
+fn report_error(proxy: &ServiceProxy, message: String) {
+    proxy.stats.record_error(message.clone());
+    emit_event(&proxy.app, &proxy.event_name, "PF_ERROR", message);
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<ProxyBody> {
+    let mut res: Response<ProxyBody> = Response::new(
+        Full::new(Bytes::from(message)).map_err(|never| match never {}).boxed_unsync(),
+    );
+    *res.status_mut() = status;
+    res.headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain; charset=utf-8"));
+    res
+}
+
+// Prefix the local path with the proxy base. Requests that already carry it
+// (links the apiserver rewrote in HTML pages) are passed through unchanged.
+fn rewrite_uri(base: &str, uri: &Uri) -> Result<Uri, String> {
+    let path_and_query: &str = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let full: String = if uri.path().starts_with(base) {
+        path_and_query.to_string()
+    } else {
+        format!("{}{}", base, path_and_query)
+    };
+    full.parse::<Uri>().map_err(|e| format!("invalid proxy path {}: {}", full, e))
+}
+
+// Redirects pointing into the apiserver proxy are mapped back onto the local listener.
+fn rewrite_location(headers: &mut HeaderMap, base: &str) {
+    let Some(location) = headers.get(header::LOCATION).and_then(|v| v.to_str().ok()) else {
+        return;
+    };
+    let Some(idx) = location.find(base) else {
+        return;
+    };
+    let rest: &str = &location[idx + base.len()..];
+    let local: String = if rest.starts_with('/') { rest.to_string() } else { format!("/{}", rest) };
+    if let Ok(value) = HeaderValue::from_str(&local) {
+        headers.insert(header::LOCATION, value);
+    }
+}
+
+fn websocket_upgrade(headers: &HeaderMap) -> Option<HeaderValue> {
+    let upgrade: &HeaderValue = headers.get(header::UPGRADE)?;
+    let is_upgrade: bool = headers
+        .get(header::CONNECTION)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|t| t.trim().eq_ignore_ascii_case("upgrade")));
+    (is_upgrade && upgrade.to_str().is_ok_and(|v| v.eq_ignore_ascii_case("websocket")))
+        .then(|| upgrade.clone())
+}
+
+// Host and Authorization belong to the local hop; the kube client sets its own.
+fn forward_headers(incoming: &HeaderMap, upgrade: Option<HeaderValue>) -> HeaderMap {
+    let mut headers: HeaderMap = incoming.clone();
+    strip_hop_by_hop(&mut headers);
+    headers.remove(header::HOST);
+    headers.remove(header::AUTHORIZATION);
+    if let Some(upgrade) = upgrade {
+        headers.insert(header::CONNECTION, HeaderValue::from_static("Upgrade"));
+        headers.insert(header::UPGRADE, upgrade);
+    }
+    headers
+}
+
+fn strip_hop_by_hop(headers: &mut HeaderMap) {
+    // Headers listed in Connection are hop-by-hop as well
+    let listed: Vec<HeaderName> = headers
+        .get_all(header::CONNECTION)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+        .collect();
+    for name in listed {
+        headers.remove(name);
+    }
+    for name in HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+    headers.remove(header::UPGRADE);
+}
@@ -0,0 +1,335 @@
+use crate::constants::termination::{TERMINATION_LIST_CONCURRENCY, TERMINATION_REMAINING_NAMES};
+use crate::manager::k8s::client::K8sClient;
+use crate::manager::k8s::dynamic_resources::DynamicK8sResources;
+use crate::types::termination::{RemainingResources, TerminationCondition, TerminationDiagnostics};
+use futures_util::{stream, StreamExt};
+use k8s_openapi::api::core::v1::Namespace;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{APIResource, APIResourceList};
+use kube::api::{Api, ApiResource, DynamicObject, ListParams, PostParams};
+use kube::core::gvk::GroupVersionKind;
+use kube::Client;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+// Namespace conditions that are set while namespace deletion is failing
+const NAMESPACE_FAILURE_CONDITIONS: [&str; 3] = [
+    "NamespaceDeletionDiscoveryFailure",
+    "NamespaceDeletionGroupVersionParsingFailure",
+    "NamespaceDeletionContentFailure",
+];
+
+// (uid, name, finalizers) of an object left in a terminating namespace
+type ListedObject = (String, String, Vec<String>);
+
+/// Diagnostics and finalizer removal for namespaces and objects stuck in Terminating.
+pub struct K8sTermination;
+
+impl K8sTermination {
+    pub async fn diagnose(
+        context_name: String,
+        api_version: String,
+        kind: String,
+        namespace: Option<String>,
+        resource_name: String,
+    ) -> Result<TerminationDiagnostics, String> {
+        let client: Client = K8sClient::for_context(&context_name).await?;
+        let (api, ar): (Api<DynamicObject>, ApiResource) = DynamicK8sResources::discovered_api(
+            client.clone(),
+            &api_version,
+            &kind,
+            namespace.as_deref(),
+        )
+        .await?;
+        let obj: DynamicObject = api
+            .get(&resource_name)
+            .await
+            .map_err(|e| format!("failed to get {} {}: {}", kind, resource_name, e))?;
+
+        let mut diagnostics: TerminationDiagnostics = TerminationDiagnostics {
+            api_version: ar.api_version.clone(),
+            kind: ar.kind.clone(),
+            namespace: obj.metadata.namespace.clone(),
+            name: resource_name.clone(),
+            terminating: obj.metadata.deletion_timestamp.is_some(),
+            deletion_timestamp: obj.metadata.deletion_timestamp.as_ref().map(|t| t.0.to_rfc3339()),
+            finalizers: obj.metadata.finalizers.clone().unwrap_or_default(),
+            spec_finalizers: vec![],
+            conditions: Self::conditions(&obj.data),
+            remaining: vec![],
+            causes: vec![],
+        };
+
+        if Self::is_namespace(&ar) {
+            diagnostics.spec_finalizers = obj
+                .data
+                .pointer("/spec/finalizers")
+                .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                .unwrap_or_default();
+            let (remaining, mut causes): (Vec<RemainingResources>, Vec<String>) =
+                Self::remaining_resources(&client, &resource_name).await;
+            diagnostics.remaining = remaining;
+            causes.splice(0..0, Self::unavailable_api_services(&client).await);
+            causes.extend(Self::webhook_failures(&diagnostics.conditions));
+            diagnostics.causes = causes;
+        }
+        Ok(diagnostics)
+    }
+
+    /// Remove finalizers from any object. For namespaces, `spec.finalizers` (e.g.
+    /// `kubernetes`) are removed too, through the `finalize` subresource. `None` removes
+    /// every finalizer. `confirm` must repeat the object name.
+    pub async fn remove_finalizers(
+        context_name: String,
+        api_version: String,
+        kind: String,
+        namespace: Option<String>,
+        resource_name: String,
+        finalizers: Option<Vec<String>>,
+        confirm: String,
+    ) -> Result<Vec<String>, String> {
+        if confirm != resource_name {
+            return Err(format!(
+                "removing finalizers skips cleanup; confirm by typing the name {}",
+                resource_name
+            ));
+        }
+        let mut remaining: Vec<String> = DynamicK8sResources::remove_finalizers(
+            context_name.clone(),
+            api_version.clone(),
+            kind.clone(),
+            namespace,
+            resource_name.clone(),
+            finalizers.clone(),
+        )
+        .await?;
+
+        if api_version == "v1" && kind == "Namespace" {
+            remaining.extend(
+                Self::finalize_namespace(&context_name, &resource_name, finalizers.as_deref())
+                    .await?,
+            );
+        }
+        Ok(remaining)
+    }
+
+    async fn finalize_namespace(
+        context_name: &str,
+        name: &str,
+        finalizers: Option<&[String]>,
+    ) -> Result<Vec<String>, String> {
+        let client: Client = K8sClient::for_context(context_name).await?;
+        let api: Api<Namespace> = Api::all(client);
+        let mut ns: Namespace =
+            api.get(name).await.map_err(|e| format!("failed to get namespace {}: {}", name, e))?;
+        let Some(spec) = ns.spec.as_mut() else {
+            return Ok(vec![]);
+        };
+        let current: Vec<String> = spec.finalizers.clone().unwrap_or_default();
+        let remaining: Vec<String> = match finalizers {
+            Some(remove) => current.iter().filter(|f| !remove.contains(f)).cloned().collect(),
+            None => vec![],
+        };
+        if remaining.len() == current.len() {
+            return Ok(remaining);
+        }
+
+        spec.finalizers = Some(remaining.clone());
+        let body: Vec<u8> = serde_json::to_vec(&ns).map_err(|e| e.to_string())?;
+        api.replace_subresource("finalize", name, &PostParams::default(), body)
+            .await
+            .map_err(|e| format!("failed to finalize namespace {}: {}", name, e))?;
+        Ok(remaining)
+    }
+
+    fn is_namespace(ar: &ApiResource) -> bool {
+        ar.group.is_empty() && ar.kind == "Namespace"
+    }
+
+    fn conditions(data: &Value) -> Vec<TerminationCondition> {
+        data.pointer("/status/conditions")
+            .and_then(Value::as_array)
+            .map(|conditions| {
+                conditions
+                    .iter()
+                    .map(|c| TerminationCondition {
+                        type_: c
+                            .get("type")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                        status: c
+                            .get("status")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                        reason: c.get("reason").and_then(Value::as_str).map(str::to_string),
+                        message: c.get("message").and_then(Value::as_str).map(str::to_string),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Content deletion fails when an admission webhook rejects or cannot be reached
+    fn webhook_failures(conditions: &[TerminationCondition]) -> Vec<String> {
+        conditions
+            .iter()
+            .filter(|c| c.status == "True" && NAMESPACE_FAILURE_CONDITIONS.contains(&&*c.type_))
+            .filter_map(|c| c.message.as_ref())
+            .filter(|m| m.contains("webhook"))
+            .map(|m| format!("Webhook failure: {}", m))
+            .collect()
+    }
+
+    // An unavailable aggregated API blocks discovery, and with it namespace deletion
+    async fn unavailable_api_services(client: &Client) -> Vec<String> {
+        let gvk: GroupVersionKind =
+            GroupVersionKind::gvk("apiregistration.k8s.io", "v1", "APIService");
+        let ar: ApiResource = ApiResource::from_gvk_with_plural(&gvk, "apiservices");
+        let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
+        let list = match api.list(&ListParams::default()).await {
+            Ok(list) => list,
+            Err(e) => return vec![format!("Could not list APIServices: {}", e)],
+        };
+        list.items
+            .iter()
+            .filter_map(|svc| {
+                let available: TerminationCondition =
+                    Self::conditions(&svc.data).into_iter().find(|c| c.type_ == "Available")?;
+                if available.status == "True" {
+                    return None;
+                }
+                Some(format!(
+                    "APIService {} is unavailable: {}",
+                    svc.metadata.name.clone().unwrap_or_default(),
+                    available.message.or(available.reason).unwrap_or_default()
+                ))
+            })
+            .collect()
+    }
+
+    /// Objects left in the namespace across every listable namespaced kind, plus the
+    /// group versions that could not be discovered or listed.
+    async fn remaining_resources(
+        client: &Client,
+        namespace: &str,
+    ) -> (Vec<RemainingResources>, Vec<String>) {
+        let (resources, mut causes): (Vec<ApiResource>, Vec<String>) =
+            Self::namespaced_resources(client).await;
+
+        let results: Vec<(ApiResource, Result<Vec<ListedObject>, String>)> =
+            stream::iter(resources)
+                .map(|ar| {
+                    let api: Api<DynamicObject> =
+                        Api::namespaced_with(client.clone(), namespace, &ar);
+                    async move {
+                        let items = api
+                            .list_metadata(&ListParams::default())
+                            .await
+                            .map(|list| {
+                                list.items
+                                    .into_iter()
+                                    .map(|m| {
+                                        (
+                                            m.metadata.uid.unwrap_or_default(),
+                                            m.metadata.name.unwrap_or_default(),
+                                            m.metadata.finalizers.unwrap_or_default(),
+                                        )
+                                    })
+                                    .collect()
+                            })
+                            .map_err(|e| e.to_string());
+                        (ar, items)
+                    }
+                })
+                .buffered(TERMINATION_LIST_CONCURRENCY)
+                .collect()
+                .await;
+
+        // The same objects are served under more than one group (events), count them once
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut remaining: Vec<RemainingResources> = Vec::new();
+        for (ar, items) in results {
+            let items: Vec<ListedObject> = match items {
+                Ok(items) => items,
+                Err(e) => {
+                    causes.push(format!("Could not list {}.{}: {}", ar.plural, ar.api_version, e));
+                    continue;
+                }
+            };
+            let items: Vec<ListedObject> =
+                items.into_iter().filter(|(uid, _, _)| seen.insert(uid.clone())).collect();
+            if items.is_empty() {
+                continue;
+            }
+            let finalizers: BTreeSet<String> =
+                items.iter().flat_map(|(_, _, f)| f.iter().cloned()).collect();
+            remaining.push(RemainingResources {
+                api_version: ar.api_version.clone(),
+                kind: ar.kind.clone(),
+                plural: ar.plural.clone(),
+                count: items.len(),
+                names: items
+                    .into_iter()
+                    .take(TERMINATION_REMAINING_NAMES)
+                    .map(|(_, name, _)| name)
+                    .collect(),
+                finalizers: finalizers.into_iter().collect(),
+            });
+        }
+        (remaining, causes)
+    }
+
+    // Preferred version of every group; failures are returned instead of aborting, as
+    // they are usually the reason the namespace is stuck.
+    async fn namespaced_resources(client: &Client) -> (Vec<ApiResource>, Vec<String>) {
+        let mut lists: Vec<Result<APIResourceList, String>> =
+            vec![client.list_core_api_resources("v1").await.map_err(|e| format!("v1: {}", e))];
+        match client.list_api_groups().await {
+            Ok(groups) => {
+                for group in groups.groups {
+                    let Some(gv) = group
+                        .preferred_version
+                        .map(|v| v.group_version)
+                        .or_else(|| group.versions.first().map(|v| v.group_version.clone()))
+                    else {
+                        continue;
+                    };
+                    lists.push(
+                        client
+                            .list_api_group_resources(&gv)
+                            .await
+                            .map_err(|e| format!("{}: {}", gv, e)),
+                    );
+                }
+            }
+            Err(e) => lists.push(Err(format!("API groups: {}", e))),
+        }
+
+        let mut resources: Vec<ApiResource> = Vec::new();
+        let mut causes: Vec<String> = Vec::new();
+        for list in lists {
+            let list: APIResourceList = match list {
+                Ok(list) => list,
+                Err(e) => {
+                    causes.push(format!("Discovery failed for {}", e));
+                    continue;
+                }
+            };
+            let (group, version): (&str, &str) =
+                list.group_version.split_once('/').unwrap_or(("", &list.group_version));
+            resources.extend(
+                list.resources
+                    .iter()
+                    .filter(|r: &&APIResource| {
+                        r.namespaced && !r.name.contains('/') && r.verbs.iter().any(|v| v == "list")
+                    })
+                    .map(|r| {
+                        let gvk: GroupVersionKind = GroupVersionKind::gvk(group, version, &r.kind);
+                        ApiResource::from_gvk_with_plural(&gvk, &r.name)
+                    }),
+            );
+        }
+        (resources, causes)
+    }
+}
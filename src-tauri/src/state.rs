@@ -1,17 +1,31 @@
 use std::path::PathBuf;
 
-use crate::databases::{k8s_contexts::K8sContextsRepo, Database};
+use crate::databases::{
+    k8s_contexts::K8sContextsRepo, metrics_history::MetricsHistoryRepo,
+    port_forwards::PortForwardsRepo, prometheus::PrometheusConfigsRepo, Database,
+};
 use anyhow::Result;
 
 #[derive(Clone)]
 pub struct AppState {
     pub k8s_contexts: K8sContextsRepo,
+    pub port_forwards: PortForwardsRepo,
+    pub metrics_history: MetricsHistoryRepo,
+    pub prometheus: PrometheusConfigsRepo,
 }
 
 impl AppState {
     pub async fn init(data_dir: PathBuf) -> Result<Self> {
         let db: Database = Database::init(data_dir).await?;
         let k8s_contexts: K8sContextsRepo = K8sContextsRepo::new(&db.db)?;
-        Ok(Self { k8s_contexts })
+        let port_forwards: PortForwardsRepo = PortForwardsRepo::new(&db.db)?;
+        let metrics_history: MetricsHistoryRepo = MetricsHistoryRepo::new(&db.db)?;
+        let prometheus: PrometheusConfigsRepo = PrometheusConfigsRepo::new(&db.db)?;
+        Ok(Self {
+            k8s_contexts,
+            port_forwards,
+            metrics_history,
+            prometheus,
+        })
     }
 }
@@ -0,0 +1,56 @@
+use kube::api::{DeleteParams, Preconditions, PropagationPolicy};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// How dependents are handled when their owner is deleted.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeletePropagation {
+    Foreground,
+    Background,
+    Orphan,
+}
+
+/// Only delete when the object still is the one the UI showed.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletePrecondition {
+    pub uid: Option<String>,
+    pub resource_version: Option<String>,
+}
+
+/// Options shared by every delete command; all optional, so `{}` is a plain delete.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeleteOptions {
+    pub grace_period_seconds: Option<u32>,
+    pub propagation_policy: Option<DeletePropagation>,
+    // Keyed by resource name; a mismatch fails that delete with a conflict
+    pub preconditions: HashMap<String, DeletePrecondition>,
+    // Delete immediately without waiting for graceful termination (grace period 0)
+    pub force: bool,
+}
+
+impl DeleteOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.force && self.grace_period_seconds.is_some_and(|g| g > 0) {
+            return Err("a forced delete always uses a grace period of 0".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn params_for(&self, name: &str) -> DeleteParams {
+        DeleteParams {
+            dry_run: false,
+            grace_period_seconds: if self.force { Some(0) } else { self.grace_period_seconds },
+            propagation_policy: self.propagation_policy.map(|p| match p {
+                DeletePropagation::Foreground => PropagationPolicy::Foreground,
+                DeletePropagation::Background => PropagationPolicy::Background,
+                DeletePropagation::Orphan => PropagationPolicy::Orphan,
+            }),
+            preconditions: self.preconditions.get(name).map(|p| Preconditions {
+                uid: p.uid.clone(),
+                resource_version: p.resource_version.clone(),
+            }),
+        }
+    }
+}
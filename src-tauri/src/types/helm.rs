@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A Helm v3 release as stored (gzipped JSON) in `sh.helm.release.v1.*` Secrets/ConfigMaps.
+/// Only the fields the app uses are decoded; chart templates and files are skipped.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct HelmRelease {
+    pub name: String,
+    #[serde(default)]
+    pub namespace: String,
+    // Revision number
+    #[serde(default)]
+    pub version: i32,
+    #[serde(default)]
+    pub info: HelmReleaseInfo,
+    #[serde(default)]
+    pub chart: HelmChart,
+    // User-supplied values
+    #[serde(default)]
+    pub config: Value,
+    #[serde(default)]
+    pub manifest: String,
+    #[serde(default)]
+    pub hooks: Vec<HelmHook>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct HelmReleaseInfo {
+    pub first_deployed: Option<String>,
+    pub last_deployed: Option<String>,
+    pub deleted: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct HelmChart {
+    #[serde(default)]
+    pub metadata: HelmChartMetadata,
+    // Chart default values
+    #[serde(default)]
+    pub values: Value,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HelmChartMetadata {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    pub app_version: Option<String>,
+    pub description: Option<String>,
+    pub api_version: Option<String>,
+    #[serde(rename = "type")]
+    pub chart_type: Option<String>,
+    pub home: Option<String>,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    pub kube_version: Option<String>,
+    pub deprecated: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct HelmHook {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub manifest: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub weight: i32,
+    #[serde(default)]
+    pub delete_policies: Vec<String>,
+    #[serde(default)]
+    pub last_run: Value,
+}
+
+/// Options for `helm install` as sent by the UI.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HelmInstallOptions {
+    pub version: Option<String>,
+    pub values: Option<Value>,
+    #[serde(default)]
+    pub create_namespace: bool,
+    #[serde(default)]
+    pub atomic: bool,
+    #[serde(default)]
+    pub wait: bool,
+    pub timeout_seconds: Option<u64>,
+    pub description: Option<String>,
+}
+
+/// Options for `helm template` as sent by the UI.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HelmTemplateOptions {
+    pub version: Option<String>,
+    pub values: Option<Value>,
+    #[serde(default)]
+    pub include_crds: bool,
+    // Kubernetes version to render for, e.g. "1.30.0"
+    pub kube_version: Option<String>,
+    // Extra API versions reported to `.Capabilities.APIVersions`
+    #[serde(default)]
+    pub api_versions: Vec<String>,
+}
+
+/// Credentials and TLS settings for `helm repo add`.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HelmRepoAddOptions {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub ca_file: Option<String>,
+    pub cert_file: Option<String>,
+    pub key_file: Option<String>,
+    #[serde(default)]
+    pub insecure_skip_tls_verify: bool,
+    // Send credentials to all domains the chart is downloaded from
+    #[serde(default)]
+    pub pass_credentials: bool,
+    // Replace an existing repository with the same name
+    #[serde(default)]
+    pub force_update: bool,
+}
+
+/// A chart as shown before install or upgrade (`helm_show_chart`).
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmChartInfo {
+    // Chart reference as passed to Helm, e.g. "bitnami/nginx" or "oci://..."
+    pub chart: String,
+    pub metadata: HelmChartMetadata,
+    // values.yaml as written by the chart authors, comments included
+    pub values_yaml: String,
+    pub values: Value,
+    // values.schema.json, when the chart ships one
+    pub schema: Option<Value>,
+    pub readme: Option<String>,
+    // Available chart versions, newest first
+    pub versions: Vec<String>,
+    // e.g. the version list could not be fetched
+    pub warnings: Vec<String>,
+}
+
+/// How the chart for upgrading a release is resolved from its stored metadata.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmChartResolution {
+    pub namespace: String,
+    pub chart_name: String,
+    pub current_version: String,
+    // Chart reference the upgrade will use; None when no or several repositories match
+    pub chart: Option<String>,
+    pub candidates: Vec<HelmChartCandidate>,
+}
+
+/// A repository chart matching a release's chart name.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmChartCandidate {
+    // "repo/chart"
+    pub chart: String,
+    pub latest_version: String,
+    pub app_version: Option<String>,
+    pub description: Option<String>,
+    // Whether the repository still serves the version that is deployed
+    pub has_current_version: bool,
+}
+
+/// Release details returned to the UI for a single revision.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmReleaseDetails {
+    pub name: String,
+    pub namespace: String,
+    pub revision: i32,
+    pub status: String,
+    pub description: String,
+    pub first_deployed: Option<String>,
+    pub last_deployed: Option<String>,
+    // "<chart name>-<chart version>", as printed by `helm list`
+    pub chart: String,
+    pub chart_version: String,
+    pub app_version: Option<String>,
+    pub chart_metadata: HelmChartMetadata,
+    pub user_values: Value,
+    // Chart defaults merged with the user-supplied values (`helm get values --all`)
+    pub computed_values: Value,
+    pub manifest: String,
+    pub notes: Option<String>,
+    pub hooks: Vec<HelmHook>,
+}
+
+/// Differences between two renderings of a release.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmReleaseDiff {
+    // e.g. "revision 3" or "proposed upgrade"
+    pub from: String,
+    pub to: String,
+    // Added, removed and modified resources; unchanged ones are only counted
+    pub resources: Vec<HelmResourceDiff>,
+    pub unchanged: usize,
+    // Changes to the user-supplied values, by dotted path
+    pub values: Vec<HelmValueChange>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmResourceDiff {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    // One of: added, removed, modified
+    pub change: String,
+    // Unified diff of the resource YAML
+    pub diff: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmValueChange {
+    pub path: String,
+    // One of: added, removed, changed
+    pub change: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// A resource rendered by a release together with its state in the cluster.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmReleaseResource {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub exists: bool,
+    // "name", or "annotation" when only an object carrying the release annotations was found
+    pub matched_by: Option<String>,
+    // Whether the live object carries this release's meta.helm.sh annotations
+    pub managed: bool,
+    // None when readiness doesn't apply or couldn't be determined
+    pub ready: Option<bool>,
+    pub message: Option<String>,
+    pub drifted: bool,
+    pub drift: Vec<HelmDriftField>,
+    // Discovery or lookup failure for this resource
+    pub error: Option<String>,
+}
+
+/// A field whose live value differs from the rendered manifest. Secret values are omitted.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmDriftField {
+    pub path: String,
+    pub expected: Option<Value>,
+    pub actual: Option<Value>,
+}
+
+/// A chart rendered locally with `helm template`.
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmTemplateResult {
+    pub chart: String,
+    pub release_name: String,
+    pub namespace: String,
+    // The full multi-document YAML, as accepted by the diff and apply flows
+    pub manifest: String,
+    pub objects: Vec<HelmRenderedObject>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct HelmRenderedObject {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    // Template path from the "# Source:" comment, e.g. "nginx/templates/deployment.yaml"
+    pub source: Option<String>,
+    // Hook events from the helm.sh/hook annotation, for hook resources
+    pub hook: Option<String>,
+    pub yaml: String,
+    pub object: Value,
+}
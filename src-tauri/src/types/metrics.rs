@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether the metrics.k8s.io API could be read.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsStatus {
+    Available,
+    // metrics-server (or another metrics.k8s.io provider) is not installed or not ready
+    Unavailable,
+    Error,
+}
+
+/// CPU in cores and memory in bytes. None when not set, e.g. a container without limits.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceAmounts {
+    pub cpu: Option<f64>,
+    pub memory: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerUsage {
+    pub name: String,
+    pub usage: ResourceAmounts,
+    pub requests: ResourceAmounts,
+    pub limits: ResourceAmounts,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PodUsage {
+    pub name: String,
+    pub namespace: String,
+    pub node_name: Option<String>,
+    pub timestamp: Option<String>,
+    pub window: Option<String>,
+    // Totals over all containers; limits are None when any container is unbounded
+    pub usage: ResourceAmounts,
+    pub requests: ResourceAmounts,
+    pub limits: ResourceAmounts,
+    pub containers: Vec<ContainerUsage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeUsage {
+    pub name: String,
+    pub timestamp: Option<String>,
+    pub window: Option<String>,
+    pub usage: ResourceAmounts,
+    pub capacity: ResourceAmounts,
+    pub allocatable: ResourceAmounts,
+    // Sums over the node's non-terminated pods
+    pub requests: ResourceAmounts,
+    pub limits: ResourceAmounts,
+    pub pod_count: usize,
+}
+
+/// One poll of the metrics API.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsReport<T> {
+    pub status: MetricsStatus,
+    pub message: Option<String>,
+    pub collected_at: String,
+    pub items: Vec<T>,
+}
+
+/// What a recorded usage series belongs to. Series keys are `namespace/pod` for pods,
+/// `namespace/pod/container` for containers and the node name for nodes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsSeriesKind {
+    Pod,
+    Container,
+    Node,
+}
+
+/// Per-context limits of the local usage history.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsHistorySettings {
+    // Off by default; recording lists every pod in the cluster each round
+    pub enabled: bool,
+    // Samples older than this are dropped from every tier
+    pub retention_hours: u32,
+    // New series are not recorded once the context has this many
+    pub max_series: u32,
+}
+
+impl Default for MetricsHistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_hours: 7 * 24,
+            max_series: 2000,
+        }
+    }
+}
+
+/// One bucket of a usage series; `timestamp` is the bucket start in unix milliseconds.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsHistoryPoint {
+    pub timestamp: i64,
+    // Averages and maxima over the samples in the bucket
+    pub cpu: Option<f64>,
+    pub cpu_max: Option<f64>,
+    pub memory: Option<f64>,
+    pub memory_max: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsHistorySeries {
+    pub kind: MetricsSeriesKind,
+    pub key: String,
+    pub resolution_seconds: u64,
+    pub points: Vec<MetricsHistoryPoint>,
+}
@@ -1,2 +1,12 @@
+pub mod delete;
 pub mod event;
+pub mod helm;
 pub mod k8s_contexts;
+pub mod metrics;
+pub mod nodes;
+pub mod pod_files;
+pub mod port_forward;
+pub mod prometheus;
+pub mod rollouts;
+pub mod scaling;
+pub mod termination;
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// Drain settings as sent by the UI; the defaults match `kubectl drain`.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NodeDrainOptions {
+    // Leave DaemonSet pods in place instead of refusing to drain
+    pub ignore_daemon_sets: bool,
+    // Evict pods using emptyDir volumes, losing their data
+    pub delete_empty_dir_data: bool,
+    // Evict pods that no controller will recreate
+    pub force: bool,
+    // Overrides each pod's terminationGracePeriodSeconds
+    pub grace_period_seconds: Option<u32>,
+    // Give up on pods that are still blocked or terminating after this long; without it
+    // the drain retries until it is cancelled
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DrainPodStatus {
+    Evicting,
+    // Eviction refused because it would violate a PodDisruptionBudget; retried until the timeout
+    Blocked,
+    // Eviction accepted, waiting for the pod to go away
+    Terminating,
+    Evicted,
+    Skipped,
+    Failed,
+}
+
+/// Progress of one pod, emitted on every change and repeated in the final report.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainPodProgress {
+    pub namespace: String,
+    pub name: String,
+    pub status: DrainPodStatus,
+    pub message: Option<String>,
+    // PodDisruptionBudgets selecting the pod, when its eviction was blocked
+    pub disruption_budgets: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDrainReport {
+    pub node: String,
+    // True when every pod that had to go was evicted
+    pub completed: bool,
+    // Stopped through cancel_drain before every pod was gone
+    pub cancelled: bool,
+    pub pods: Vec<DrainPodProgress>,
+}
+
+/// A taint to add, or to update when the node already has the key with the same effect.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTaintSpec {
+    pub key: String,
+    pub value: Option<String>,
+    // NoSchedule, PreferNoSchedule or NoExecute
+    pub effect: String,
+}
+
+/// A taint to remove; without an effect every taint with the key goes.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTaintRemoval {
+    pub key: String,
+    pub effect: Option<String>,
+}
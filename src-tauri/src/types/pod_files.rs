@@ -0,0 +1,38 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PodFileEntry {
+    pub name: String,
+    pub path: String,
+    // One of: file, directory, symlink, fifo, socket, block, char, unknown
+    pub kind: String,
+    pub size: Option<u64>,
+    // Octal permission bits, e.g. "755"
+    pub mode: Option<String>,
+    // Seconds since the Unix epoch; not available from the `ls` fallback
+    pub modified: Option<i64>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub link_target: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PodFileListing {
+    pub path: String,
+    pub entries: Vec<PodFileEntry>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PodFilePreview {
+    pub path: String,
+    pub size: Option<u64>,
+    pub bytes_read: usize,
+    pub truncated: bool,
+    pub is_binary: bool,
+    // "utf8" for text previews, "base64" for binary ones
+    pub encoding: String,
+    pub content: String,
+}
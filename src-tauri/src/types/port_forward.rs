@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::port_forward::{PortForwardMode, PortForwardPort};
+
+/// A saved port-forward, restored (stopped) on the next app start.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForwardDefinition {
+    // Session id the forward is known by in `PortForwardManager`
+    pub id: String,
+    // Optional human-friendly name shown instead of the resource
+    pub name: Option<String>,
+    // Named set the forward belongs to, started/stopped together
+    pub group: Option<String>,
+    pub context: String,
+    pub namespace: String,
+    pub resource_kind: String,
+    pub resource_name: String,
+    pub bind_address: String,
+    pub ports: Vec<PortForwardPort>,
+    pub mode: PortForwardMode,
+    // Start automatically once the context connects
+    pub auto_start: bool,
+    pub created_at: i64,
+}
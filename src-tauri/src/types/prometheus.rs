@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How the app reaches the in-cluster Prometheus.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PrometheusAccess {
+    // apiserver `services/{name}:{port}/proxy` subresource
+    #[default]
+    ServiceProxy,
+    // A short-lived port-forward to a ready pod behind the service, per request
+    PortForward,
+}
+
+/// Prometheus service of a context, saved in the app database.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusConfig {
+    pub namespace: String,
+    pub service: String,
+    // Service port name, or its number when the port is unnamed
+    pub port: String,
+    // "http" (default) or "https"
+    pub scheme: Option<String>,
+    // Prefix Prometheus is served under, e.g. "/prometheus" with --web.route-prefix
+    pub path_prefix: Option<String>,
+    #[serde(default)]
+    pub access: PrometheusAccess,
+    // Replace built-in queries with the same id or add new ones
+    #[serde(default)]
+    pub queries: Vec<PrometheusQueryTemplate>,
+}
+
+/// A PromQL expression with `$namespace`, `$pod`, `$workload`, `$node` and `$rate`
+/// placeholders filled in from the variables of a run. `$workload_pods` is a regex for
+/// the workload's pods, built from the `workload` and `workload_kind` variables.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusQueryTemplate {
+    pub id: String,
+    pub title: String,
+    // pod, workload, namespace or node; tells the UI which variables to supply
+    pub scope: String,
+    // cores, bytes, bytesPerSecond or count
+    pub unit: String,
+    pub expr: String,
+    // Set for queries that ship with the app and are not overridden by the context
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+/// Evaluation range of a range query; unix seconds, step in seconds.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusRange {
+    pub start: f64,
+    pub end: f64,
+    pub step: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusSeries {
+    pub metric: BTreeMap<String, String>,
+    // (unix seconds, value); NaN and infinities are None
+    pub values: Vec<(f64, Option<f64>)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusQueryResult {
+    pub query: String,
+    // vector, matrix, scalar or string
+    pub result_type: String,
+    pub series: Vec<PrometheusSeries>,
+    pub warnings: Vec<String>,
+}
+
+/// A service that looks like Prometheus, offered when configuring a context.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrometheusCandidate {
+    pub namespace: String,
+    pub service: String,
+    pub port: String,
+}
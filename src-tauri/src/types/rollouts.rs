@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Workload kinds with a rollout, as named by the API.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RolloutKind {
+    Deployment,
+    StatefulSet,
+    DaemonSet,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RolloutPhase {
+    Progressing,
+    Complete,
+    // Deployments only: progressDeadlineSeconds was exceeded
+    Failed,
+    Paused,
+}
+
+/// Rollout state computed from the workload status and conditions, like
+/// `kubectl rollout status`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutStatus {
+    pub kind: RolloutKind,
+    pub namespace: String,
+    pub name: String,
+    pub phase: RolloutPhase,
+    pub message: String,
+    // Failing conditions, e.g. "FailedCreate: exceeded quota"
+    pub reasons: Vec<String>,
+    // Deployment revision annotation, or the StatefulSet update revision
+    pub revision: Option<String>,
+    pub desired: i32,
+    pub updated: i32,
+    pub ready: i32,
+    pub available: i32,
+}
+
+/// One entry of the rollout history: a ReplicaSet for Deployments, a ControllerRevision
+/// for StatefulSets and DaemonSets.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutRevision {
+    pub revision: i64,
+    pub name: String,
+    pub created_at: Option<String>,
+    // kubernetes.io/change-cause annotation
+    pub change_cause: Option<String>,
+    // Current replicas of the ReplicaSet; None for ControllerRevisions
+    pub replicas: Option<i32>,
+    pub current: bool,
+    pub template: Value,
+    // Unified YAML diff of the pod template against the previous revision
+    pub diff: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RolloutOutcome {
+    Complete,
+    Failed,
+    // Not complete within the timeout, progressDeadlineSeconds for Deployments
+    TimedOut,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutReplicaSet {
+    pub name: String,
+    pub revision: Option<i64>,
+    pub desired: i32,
+    pub ready: i32,
+    pub available: i32,
+    // Owns the pods of the current revision
+    pub new: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutPod {
+    pub name: String,
+    pub phase: Option<String>,
+    pub ready: bool,
+    pub terminating: bool,
+    pub restarts: i32,
+    pub node: Option<String>,
+    // Why a container is waiting or last terminated, e.g. CrashLoopBackOff or OOMKilled
+    pub reason: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Snapshot emitted by `watch_rollout` whenever it changes; the last one has an outcome.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloutProgress {
+    pub status: RolloutStatus,
+    // Deployments only
+    pub replica_sets: Vec<RolloutReplicaSet>,
+    pub new_pods: Vec<RolloutPod>,
+    pub old_pods: Vec<RolloutPod>,
+    pub timeout_seconds: u64,
+    pub outcome: Option<RolloutOutcome>,
+}
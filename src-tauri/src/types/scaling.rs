@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// A HorizontalPodAutoscaler targeting the scaled resource.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleAutoscaler {
+    pub name: String,
+    pub min_replicas: Option<i32>,
+    pub max_replicas: i32,
+    pub current_replicas: i32,
+    pub desired_replicas: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleResult {
+    // False when an autoscaler manages the target and the scale was not forced
+    pub scaled: bool,
+    pub previous_replicas: Option<i32>,
+    pub replicas: Option<i32>,
+    pub autoscaler: Option<ScaleAutoscaler>,
+    pub warning: Option<String>,
+}
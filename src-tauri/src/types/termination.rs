@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// A status condition of the object being diagnosed, e.g. `NamespaceContentRemaining`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminationCondition {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Objects of one kind still present in a terminating namespace.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RemainingResources {
+    pub api_version: String,
+    pub kind: String,
+    pub plural: String,
+    pub count: usize,
+    // At most TERMINATION_REMAINING_NAMES of them
+    pub names: Vec<String>,
+    // Distinct finalizers across all of them
+    pub finalizers: Vec<String>,
+}
+
+/// Why a namespace or object is stuck in Terminating.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminationDiagnostics {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub terminating: bool,
+    pub deletion_timestamp: Option<String>,
+    // metadata.finalizers
+    pub finalizers: Vec<String>,
+    // Namespace spec.finalizers, removed through the finalize subresource
+    pub spec_finalizers: Vec<String>,
+    pub conditions: Vec<TerminationCondition>,
+    // Namespaces only
+    pub remaining: Vec<RemainingResources>,
+    // Unavailable APIServices, failing webhooks and group versions that could not be listed
+    pub causes: Vec<String>,
+}
@@ -0,0 +1,116 @@
+// Lines of unchanged context around each change
+const CONTEXT_LINES: usize = 3;
+// Beyond this many line pairs the text is shown as fully replaced instead of diffed
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+enum LineOp<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line diff of two texts in unified format: `@@` hunks with three lines of context.
+pub fn unified_diff(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops: Vec<LineOp> = line_ops(&old_lines, &new_lines);
+
+    // Group changes into hunks with surrounding context
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, LineOp::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for idx in changed {
+        let start: usize = idx.saturating_sub(CONTEXT_LINES);
+        let end: usize = (idx + CONTEXT_LINES + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out: String = String::new();
+    for (start, end) in hunks {
+        // Line numbers (1-based) of the hunk start in the old and new text
+        let mut old_no: usize = 1;
+        let mut new_no: usize = 1;
+        for op in ops[..start].iter() {
+            match op {
+                LineOp::Same(_) => {
+                    old_no += 1;
+                    new_no += 1;
+                }
+                LineOp::Removed(_) => old_no += 1,
+                LineOp::Added(_) => new_no += 1,
+            }
+        }
+        let old_count: usize =
+            ops[start..end].iter().filter(|op| !matches!(op, LineOp::Added(_))).count();
+        let new_count: usize =
+            ops[start..end].iter().filter(|op| !matches!(op, LineOp::Removed(_))).count();
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_count == 0 { old_no - 1 } else { old_no },
+            old_count,
+            if new_count == 0 { new_no - 1 } else { new_no },
+            new_count
+        ));
+        for op in ops[start..end].iter() {
+            let (prefix, text): (char, &str) = match op {
+                LineOp::Same(t) => (' ', t),
+                LineOp::Removed(t) => ('-', t),
+                LineOp::Added(t) => ('+', t),
+            };
+            out.push(prefix);
+            out.push_str(text);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+// Longest-common-subsequence line diff; large inputs degrade to remove-all/add-all.
+fn line_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<LineOp<'a>> {
+    let (n, m): (usize, usize) = (old.len(), new.len());
+    if n.saturating_mul(m) > MAX_LCS_CELLS {
+        return old
+            .iter()
+            .map(|l| LineOp::Removed(l))
+            .chain(new.iter().map(|l| LineOp::Added(l)))
+            .collect();
+    }
+
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs: Vec<Vec<u32>> = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<LineOp> = Vec::with_capacity(n + m);
+    let (mut i, mut j): (usize, usize) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(LineOp::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(LineOp::Removed(old[i]));
+            i += 1;
+        } else {
+            ops.push(LineOp::Added(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|l| LineOp::Removed(l)));
+    ops.extend(new[j..].iter().map(|l| LineOp::Added(l)));
+    ops
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Running node drains, keyed by their progress event name, so they can be cancelled.
+#[derive(Default)]
+pub struct DrainManager {
+    drains: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl DrainManager {
+    pub async fn start(&self, id: &str) -> Result<CancellationToken, String> {
+        let mut drains = self.drains.lock().await;
+        if drains.contains_key(id) {
+            return Err("a drain of this node is already running".to_string());
+        }
+        let token: CancellationToken = CancellationToken::new();
+        drains.insert(id.to_string(), token.clone());
+        Ok(token)
+    }
+
+    pub async fn finish(&self, id: &str) {
+        self.drains.lock().await.remove(id);
+    }
+
+    pub async fn cancel(&self, id: &str) -> Result<(), String> {
+        match self.drains.lock().await.get(id) {
+            Some(token) => {
+                token.cancel();
+                Ok(())
+            }
+            None => Err("no drain of this node is running".to_string()),
+        }
+    }
+}
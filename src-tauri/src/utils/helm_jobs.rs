@@ -0,0 +1,138 @@
+use k8s_openapi::chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+// Finished jobs kept for the job list; running jobs are never dropped
+const FINISHED_JOB_LIMIT: usize = 50;
+
+#[derive(Default)]
+pub struct HelmJobManager {
+    jobs: Mutex<HashMap<String, HelmJob>>,
+}
+
+pub struct HelmJob {
+    pub cancel_token: Option<CancellationToken>,
+    pub context: String,
+    pub namespace: String,
+    pub release: String,
+    // install, upgrade, rollback or uninstall
+    pub operation: String,
+    pub event_name: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub state: HelmJobState,
+    pub error: Option<String>,
+    // Checks that were skipped or problems that did not stop the job
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HelmJobState {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HelmJobItem {
+    pub job_id: String,
+    pub context: String,
+    pub namespace: String,
+    pub release: String,
+    pub operation: String,
+    pub event_name: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub state: HelmJobState,
+    pub error: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+impl HelmJobManager {
+    pub async fn insert(&self, id: String, job: HelmJob) -> HelmJobItem {
+        let mut jobs = self.jobs.lock().await;
+        Self::prune(&mut jobs);
+        let item: HelmJobItem = Self::item(&id, &job);
+        jobs.insert(id, job);
+        item
+    }
+
+    pub async fn finish(&self, id: &str, state: HelmJobState, error: Option<String>) {
+        let mut jobs = self.jobs.lock().await;
+        if let Some(job) = jobs.get_mut(id) {
+            // A cancelled job stays cancelled even though the process exits with an error
+            if job.state == HelmJobState::Running {
+                job.state = state;
+                job.error = error;
+            }
+            job.finished_at = Some(Utc::now().to_rfc3339());
+            job.cancel_token = None;
+        }
+    }
+
+    pub async fn add_warnings(&self, id: &str, warnings: &[String]) {
+        if let Some(job) = self.jobs.lock().await.get_mut(id) {
+            job.warnings.extend_from_slice(warnings);
+        }
+    }
+
+    pub async fn cancel(&self, id: &str) -> Result<(), String> {
+        let mut jobs = self.jobs.lock().await;
+        let job: &mut HelmJob =
+            jobs.get_mut(id).ok_or_else(|| format!("helm job '{}' not found", id))?;
+        if job.state != HelmJobState::Running {
+            return Err(format!("helm job '{}' is not running", id));
+        }
+        if let Some(token) = job.cancel_token.take() {
+            token.cancel();
+        }
+        job.state = HelmJobState::Cancelled;
+        Ok(())
+    }
+
+    // Newest first.
+    pub async fn list(&self) -> Vec<HelmJobItem> {
+        let jobs = self.jobs.lock().await;
+        let mut items: Vec<HelmJobItem> =
+            jobs.iter().map(|(id, job)| Self::item(id, job)).collect();
+        items.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        items
+    }
+
+    fn prune(jobs: &mut HashMap<String, HelmJob>) {
+        let mut finished: Vec<(String, String)> = jobs
+            .iter()
+            .filter(|(_, job)| job.state != HelmJobState::Running)
+            .map(|(id, job)| (job.started_at.clone(), id.clone()))
+            .collect();
+        if finished.len() < FINISHED_JOB_LIMIT {
+            return;
+        }
+        finished.sort();
+        let excess: usize = finished.len() + 1 - FINISHED_JOB_LIMIT;
+        for (_, id) in finished.into_iter().take(excess) {
+            jobs.remove(&id);
+        }
+    }
+
+    fn item(id: &str, job: &HelmJob) -> HelmJobItem {
+        HelmJobItem {
+            job_id: id.to_string(),
+            context: job.context.clone(),
+            namespace: job.namespace.clone(),
+            release: job.release.clone(),
+            operation: job.operation.clone(),
+            event_name: job.event_name.clone(),
+            started_at: job.started_at.clone(),
+            finished_at: job.finished_at.clone(),
+            state: job.state,
+            error: job.error.clone(),
+            warnings: job.warnings.clone(),
+        }
+    }
+}
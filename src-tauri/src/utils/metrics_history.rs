@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Background tasks sampling usage history, one per connected context.
+#[derive(Default)]
+pub struct MetricsRecorderManager {
+    handles: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl MetricsRecorderManager {
+    // Does nothing when the context already has a running recorder.
+    pub async fn start<Fut>(&self, context: String, record: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut handles = self.handles.lock().await;
+        if handles.get(&context).is_some_and(|h| !h.is_finished()) {
+            return;
+        }
+        handles.insert(context, tokio::spawn(record));
+    }
+
+    pub async fn stop(&self, context: &str) {
+        if let Some(handle) = self.handles.lock().await.remove(context) {
+            handle.abort();
+        }
+    }
+}
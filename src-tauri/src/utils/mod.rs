@@ -1,5 +1,11 @@
 pub mod connections;
 pub mod crypto;
+pub mod diff;
+pub mod drains;
 pub mod exec;
+pub mod helm_jobs;
+pub mod metrics_history;
 pub mod port_forward;
+pub mod quantity;
+pub mod validation;
 pub mod watcher;
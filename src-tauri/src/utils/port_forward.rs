@@ -1,9 +1,16 @@
-use serde::Serialize;
-use std::collections::HashMap;
+use crate::types::port_forward::PortForwardDefinition;
+use k8s_openapi::chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio::sync::{mpsc::Sender, Mutex};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
+// Closed connections kept in a session's connection log
+const CONNECTION_LOG_LIMIT: usize = 100;
+
 #[derive(Default)]
 pub struct PortForwardManager {
     sessions: Mutex<HashMap<String, PortForwardSession>>,
@@ -17,9 +24,246 @@ pub struct PortForwardSession {
     pub namespace: String,
     pub resource_kind: String,
     pub resource_name: String,
+    pub bind_address: String,
+    pub ports: Vec<PortForwardPort>,
+    pub status: String,
+    pub mode: PortForwardMode,
+    pub meta: PortForwardMeta,
+    pub stats: Arc<PortForwardStats>,
+}
+
+/// How a session carries traffic: raw TCP through a pod port-forward, or HTTP(S)
+/// through the apiserver's service proxy.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PortForwardMode {
+    #[default]
+    Tcp,
+    Http,
+    Https,
+}
+
+impl PortForwardMode {
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            PortForwardMode::Tcp => "TCP",
+            PortForwardMode::Http => "HTTP",
+            PortForwardMode::Https => "HTTPS",
+        }
+    }
+}
+
+/// User-facing settings of a session that are persisted with its definition.
+#[derive(Clone, Default)]
+pub struct PortForwardMeta {
+    pub name: Option<String>,
+    pub group: Option<String>,
+    pub auto_start: bool,
+    pub created_at: i64,
+}
+
+/// Traffic counters of a running session, shared with its forwarding tasks.
+#[derive(Default)]
+pub struct PortForwardStats {
+    active_connections: AtomicU64,
+    total_connections: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    next_connection_id: AtomicU64,
+    last_error: std::sync::Mutex<Option<String>>,
+    connections: std::sync::Mutex<VecDeque<PortForwardConnection>>,
+}
+
+/// One client connection accepted by a session.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForwardConnection {
+    pub id: u64,
+    pub client_address: String,
     pub local_port: u16,
     pub remote_port: u16,
-    pub status: String,
+    pub pod: String,
+    // Unix milliseconds
+    pub opened_at: i64,
+    pub closed_at: Option<i64>,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForwardStatsSnapshot {
+    pub active_connections: u64,
+    pub total_connections: u64,
+    // Bytes received from the pod
+    pub bytes_in: u64,
+    // Bytes sent to the pod
+    pub bytes_out: u64,
+    pub last_error: Option<String>,
+    // Open connections followed by the most recent closed ones, oldest first
+    pub connections: Vec<PortForwardConnection>,
+}
+
+impl PortForwardStats {
+    pub fn open_connection(
+        &self,
+        client_address: String,
+        local_port: u16,
+        remote_port: u16,
+        pod: String,
+    ) -> u64 {
+        let id: u64 = self.next_connection_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+        self.total_connections.fetch_add(1, Ordering::Relaxed);
+
+        let mut connections = self.connections.lock().unwrap_or_else(|e| e.into_inner());
+        connections.push_back(PortForwardConnection {
+            id,
+            client_address,
+            local_port,
+            remote_port,
+            pod,
+            opened_at: Utc::now().timestamp_millis(),
+            closed_at: None,
+            bytes_in: 0,
+            bytes_out: 0,
+            error: None,
+        });
+        Self::trim_log(&mut connections);
+        id
+    }
+
+    pub fn close_connection(&self, id: u64, bytes_in: u64, bytes_out: u64, error: Option<String>) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+        if let Some(error) = &error {
+            self.record_error(error.clone());
+        }
+
+        let mut connections = self.connections.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(conn) = connections.iter_mut().find(|c| c.id == id) {
+            conn.closed_at = Some(Utc::now().timestamp_millis());
+            conn.bytes_in = bytes_in;
+            conn.bytes_out = bytes_out;
+            conn.error = error;
+        }
+        Self::trim_log(&mut connections);
+    }
+
+    pub fn add_bytes_in(&self, n: u64) {
+        self.bytes_in.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn add_bytes_out(&self, n: u64) {
+        self.bytes_out.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self, error: String) {
+        *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(error);
+    }
+
+    pub fn snapshot(&self) -> PortForwardStatsSnapshot {
+        PortForwardStatsSnapshot {
+            active_connections: self.active_connections.load(Ordering::Relaxed),
+            total_connections: self.total_connections.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            last_error: self.last_error.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            connections: self
+                .connections
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .cloned()
+                .collect(),
+        }
+    }
+
+    // Drop the oldest closed connections beyond the limit; open ones are always kept.
+    fn trim_log(connections: &mut VecDeque<PortForwardConnection>) {
+        let mut closed: usize = connections.iter().filter(|c| c.closed_at.is_some()).count();
+        while closed > CONNECTION_LOG_LIMIT {
+            match connections.iter().position(|c| c.closed_at.is_some()) {
+                Some(idx) => {
+                    connections.remove(idx);
+                    closed -= 1;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Optional settings for a new session.
+#[derive(Default)]
+pub struct PortForwardOptions {
+    pub bind_address: Option<String>,
+    pub meta: PortForwardMeta,
+}
+
+/// Settings for an HTTP(S) service proxy session as sent by the UI.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceProxyOptions {
+    // "http" (default) or "https" for the scheme used towards the service
+    pub scheme: Option<String>,
+    // 0 or missing picks a free local port
+    pub local_port: Option<u16>,
+    pub bind_address: Option<String>,
+    pub display_name: Option<String>,
+    pub group: Option<String>,
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+/// A resolved local -> remote port pair of a session.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForwardPort {
+    pub local_port: u16,
+    pub remote_port: u16,
+    // What the user asked for (service port, container port or port name); re-resolved on resume
+    pub requested_port: RemotePort,
+    // Name of the matched Service/container port, if it has one
+    pub name: Option<String>,
+    pub protocol: String,
+}
+
+/// Remote port as requested by the UI: a number or a named port from the Service/container spec.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum RemotePort {
+    Number(u16),
+    Name(String),
+}
+
+impl std::fmt::Display for RemotePort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemotePort::Number(n) => write!(f, "{}", n),
+            RemotePort::Name(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForwardPortSpec {
+    // 0 picks a free local port
+    #[serde(default)]
+    pub local_port: u16,
+    pub remote_port: RemotePort,
+}
+
+#[derive(Clone)]
+pub struct PortForwardConfig {
+    pub context: String,
+    pub namespace: String,
+    pub resource_kind: String,
+    pub resource_name: String,
+    pub bind_address: String,
+    pub ports: Vec<PortForwardPort>,
+    pub mode: PortForwardMode,
 }
 
 #[derive(Serialize, Clone)]
@@ -30,29 +274,114 @@ pub struct PortForwardItem {
     pub namespace: String,
     pub resource_kind: String,
     pub resource_name: String,
+    pub bind_address: String,
+    // First port pair, kept for callers that only know about single-port sessions
     pub local_port: u16,
     pub remote_port: u16,
+    pub ports: Vec<PortForwardPort>,
     pub protocol: String,
+    pub mode: PortForwardMode,
     pub status: String,
+    pub name: Option<String>,
+    pub group: Option<String>,
+    pub auto_start: bool,
+    pub stats: PortForwardStatsSnapshot,
 }
 
 impl PortForwardManager {
-    pub async fn insert(&self, id: String, session: PortForwardSession) {
-        self.sessions.lock().await.insert(id, session);
+    // Re-inserting an existing id (resume) keeps the session's name, group and flags.
+    pub async fn insert(&self, id: String, mut session: PortForwardSession) {
+        let mut sessions = self.sessions.lock().await;
+        if let Some(existing) = sessions.get(&id) {
+            session.meta = existing.meta.clone();
+        }
+        sessions.insert(id, session);
+    }
+
+    /// Register saved definitions as stopped sessions so they can be resumed.
+    pub async fn restore(&self, definitions: Vec<PortForwardDefinition>) {
+        let mut sessions = self.sessions.lock().await;
+        for def in definitions {
+            sessions.insert(
+                def.id,
+                PortForwardSession {
+                    kill_tx: None,
+                    handle: None,
+                    cancel_token: None,
+                    context: def.context,
+                    namespace: def.namespace,
+                    resource_kind: def.resource_kind,
+                    resource_name: def.resource_name,
+                    bind_address: def.bind_address,
+                    ports: def.ports,
+                    status: "Stopped".to_string(),
+                    mode: def.mode,
+                    meta: PortForwardMeta {
+                        name: def.name,
+                        group: def.group,
+                        auto_start: def.auto_start,
+                        created_at: def.created_at,
+                    },
+                    stats: Arc::default(),
+                },
+            );
+        }
+    }
+
+    pub async fn definition(&self, id: &str) -> Option<PortForwardDefinition> {
+        let sessions = self.sessions.lock().await;
+        sessions.get(id).map(|s| PortForwardDefinition {
+            id: id.to_string(),
+            name: s.meta.name.clone(),
+            group: s.meta.group.clone(),
+            context: s.context.clone(),
+            namespace: s.namespace.clone(),
+            resource_kind: s.resource_kind.clone(),
+            resource_name: s.resource_name.clone(),
+            bind_address: s.bind_address.clone(),
+            ports: s.ports.clone(),
+            mode: s.mode,
+            auto_start: s.meta.auto_start,
+            created_at: s.meta.created_at,
+        })
+    }
+
+    pub async fn update_meta(
+        &self,
+        id: &str,
+        update: impl FnOnce(&mut PortForwardMeta),
+    ) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        let sess = sessions.get_mut(id).ok_or_else(|| "session not found".to_string())?;
+        update(&mut sess.meta);
+        Ok(())
+    }
+
+    pub async fn group_ids(&self, group: &str) -> Vec<String> {
+        let sessions = self.sessions.lock().await;
+        let mut ids: Vec<String> = sessions
+            .iter()
+            .filter(|(_, s)| s.meta.group.as_deref() == Some(group))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    // Stopped sessions of a context flagged to start when the context connects.
+    pub async fn auto_start_ids(&self, context: &str) -> Vec<String> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .iter()
+            .filter(|(_, s)| s.meta.auto_start && s.context == context && s.status == "Stopped")
+            .map(|(id, _)| id.clone())
+            .collect()
     }
 
     pub async fn stop(&self, id: &str) -> Result<(), String> {
         let mut sessions = self.sessions.lock().await;
         if let Some(sess) = sessions.get_mut(id) {
-            if let Some(tx) = &sess.kill_tx {
-                let _ = tx.send(()).await;
-            }
-            if let Some(handle) = &sess.handle {
-                handle.abort();
-            }
-            if let Some(token) = &sess.cancel_token {
-                token.cancel();
-            }
+            Self::shut_down(sess).await;
             sess.kill_tx = None;
             sess.handle = None;
             sess.cancel_token = None;
@@ -62,55 +391,109 @@ impl PortForwardManager {
     }
 
     pub async fn delete(&self, id: &str) -> Result<(), String> {
-        {
-            let mut sessions = self.sessions.lock().await;
-            if let Some(sess) = sessions.get_mut(id) {
-                if let Some(tx) = &sess.kill_tx {
-                    let _ = tx.send(()).await;
-                }
-                if let Some(handle) = &sess.handle {
-                    handle.abort();
-                }
-                if let Some(token) = &sess.cancel_token {
-                    token.cancel();
-                }
-            }
-        }
-
         let mut sessions = self.sessions.lock().await;
-        sessions.remove(id);
+        if let Some(sess) = sessions.remove(id) {
+            Self::shut_down(&sess).await;
+        }
         Ok(())
     }
 
+    async fn shut_down(sess: &PortForwardSession) {
+        if let Some(tx) = &sess.kill_tx {
+            let _ = tx.send(()).await;
+        }
+        if let Some(handle) = &sess.handle {
+            handle.abort();
+        }
+        if let Some(token) = &sess.cancel_token {
+            token.cancel();
+        }
+    }
+
     pub async fn list(&self) -> Vec<PortForwardItem> {
         let sessions = self.sessions.lock().await;
         sessions
             .iter()
-            .map(|(id, s)| PortForwardItem {
-                session_id: id.clone(),
-                context: s.context.clone(),
-                namespace: s.namespace.clone(),
-                resource_kind: s.resource_kind.clone(),
-                resource_name: s.resource_name.clone(),
-                local_port: s.local_port,
-                remote_port: s.remote_port,
-                protocol: "TCP".to_string(),
-                status: s.status.clone(),
+            .map(|(id, s)| {
+                let first: Option<&PortForwardPort> = s.ports.first();
+                PortForwardItem {
+                    session_id: id.clone(),
+                    context: s.context.clone(),
+                    namespace: s.namespace.clone(),
+                    resource_kind: s.resource_kind.clone(),
+                    resource_name: s.resource_name.clone(),
+                    bind_address: s.bind_address.clone(),
+                    local_port: first.map(|p| p.local_port).unwrap_or_default(),
+                    remote_port: first.map(|p| p.remote_port).unwrap_or_default(),
+                    ports: s.ports.clone(),
+                    protocol: s.mode.protocol().to_string(),
+                    mode: s.mode,
+                    status: s.status.clone(),
+                    name: s.meta.name.clone(),
+                    group: s.meta.group.clone(),
+                    auto_start: s.meta.auto_start,
+                    stats: s.stats.snapshot(),
+                }
             })
             .collect()
     }
 
-    pub async fn get_config(&self, id: &str) -> Option<(String, String, String, String, u16, u16)> {
-        let sessions = self.sessions.lock().await;
-        sessions.get(id).map(|s| {
-            (
-                s.context.clone(),
-                s.namespace.clone(),
-                s.resource_kind.clone(),
-                s.resource_name.clone(),
-                s.local_port,
-                s.remote_port,
-            )
-        })
+    // Used by the session supervisor; a stopped session keeps its "Stopped" status, and a
+    // session being started only changes once `finish_start` installs it.
+    pub async fn set_status(&self, id: &str, status: &str) {
+        let mut sessions = self.sessions.lock().await;
+        if let Some(sess) = sessions.get_mut(id) {
+            if !matches!(sess.status.as_str(), "Stopped" | "Starting") {
+                sess.status = status.to_string();
+            }
+        }
+    }
+
+    /// Claim a stopped session for starting. The status becomes "Starting" under the lock,
+    /// so concurrent resumes (auto-start runs on connect and on warmup) bind only once.
+    /// None when the session is already running or being started.
+    pub async fn begin_start(&self, id: &str) -> Result<Option<PortForwardConfig>, String> {
+        let mut sessions = self.sessions.lock().await;
+        let s: &mut PortForwardSession =
+            sessions.get_mut(id).ok_or_else(|| "session not found".to_string())?;
+        if matches!(s.status.as_str(), "Running" | "Reconnecting" | "Starting") {
+            return Ok(None);
+        }
+        s.status = "Starting".to_string();
+        Ok(Some(PortForwardConfig {
+            context: s.context.clone(),
+            namespace: s.namespace.clone(),
+            resource_kind: s.resource_kind.clone(),
+            resource_name: s.resource_name.clone(),
+            bind_address: s.bind_address.clone(),
+            ports: s.ports.clone(),
+            mode: s.mode,
+        }))
+    }
+
+    // A start that failed leaves the session stopped again.
+    pub async fn abort_start(&self, id: &str) {
+        if let Some(sess) = self.sessions.lock().await.get_mut(id) {
+            if sess.status == "Starting" {
+                sess.status = "Stopped".to_string();
+            }
+        }
+    }
+
+    /// Install a started session. Returns false, and shuts the new session down, when it
+    /// was stopped or deleted while starting.
+    pub async fn finish_start(&self, id: &str, mut session: PortForwardSession) -> bool {
+        let mut sessions = self.sessions.lock().await;
+        match sessions.get_mut(id) {
+            Some(existing) if existing.status == "Starting" => {
+                session.meta = existing.meta.clone();
+                *existing = session;
+                true
+            }
+            _ => {
+                Self::shut_down(&session).await;
+                false
+            }
+        }
     }
 }
@@ -0,0 +1,58 @@
+// Local checks mirroring apimachinery's validation, so bad input is reported before
+// anything is sent to the apiserver.
+
+const NAME_MAX_LEN: usize = 63;
+const PREFIX_MAX_LEN: usize = 253;
+
+/// Label and annotation keys, and taint keys: `[prefix/]name` where the prefix is a DNS
+/// subdomain.
+pub fn validate_qualified_name(key: &str) -> Result<(), String> {
+    let (prefix, name): (Option<&str>, &str) = match key.split_once('/') {
+        Some((p, n)) => (Some(p), n),
+        None => (None, key),
+    };
+    if let Some(p) = prefix {
+        if p.is_empty() || p.len() > PREFIX_MAX_LEN || !is_dns_subdomain(p) {
+            return Err(format!(
+                "invalid key {:?}: prefix must be a lowercase DNS subdomain of at most {} characters",
+                key, PREFIX_MAX_LEN
+            ));
+        }
+    }
+    if name.is_empty() || !is_name_segment(name) {
+        return Err(format!(
+            "invalid key {:?}: name must be at most {} characters of alphanumerics, '-', '_' or '.', starting and ending with an alphanumeric",
+            key, NAME_MAX_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// Label values and taint values; empty is allowed.
+pub fn validate_label_value(value: &str) -> Result<(), String> {
+    if value.is_empty() || is_name_segment(value) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid value {:?}: must be at most {} characters of alphanumerics, '-', '_' or '.', starting and ending with an alphanumeric",
+            value, NAME_MAX_LEN
+        ))
+    }
+}
+
+fn is_name_segment(s: &str) -> bool {
+    s.len() <= NAME_MAX_LEN
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && s.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && s.ends_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+fn is_dns_subdomain(s: &str) -> bool {
+    s.split('.').all(|label| {
+        !label.is_empty()
+            && label.len() <= NAME_MAX_LEN
+            && label.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    })
+}
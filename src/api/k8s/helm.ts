@@ -30,6 +30,23 @@ export interface HelmHistoryEntry {
   description?: string;
 }
 
+export type HelmJobState = 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+export interface HelmJobItem {
+  jobId: string;
+  context: string;
+  namespace: string;
+  release: string;
+  operation: string;
+  eventName: string;
+  startedAt: string;
+  finishedAt?: string;
+  state: HelmJobState;
+  error?: string;
+  // e.g. values that could not be checked against the chart schema
+  warnings: string[];
+}
+
 export async function listHelmReleases({
   name,
   namespaces,
@@ -48,12 +65,18 @@ export async function uninstallHelmReleases({
   name: string;
   namespace?: string;
   releaseNames: string[];
-}): Promise<Array<{ status: 'Success' | 'Failure'; message?: string }>> {
-  return await invoke('helm_uninstall_releases', {
+}): Promise<HelmJobItem[]> {
+  const jobs = await invoke<HelmJobItem[]>('helm_uninstall_releases', {
     name,
     namespace,
     releaseNames,
   });
+  const results = await Promise.allSettled(jobs.map((job) => waitForHelmJob(job)));
+  const errors = results
+    .filter((r): r is PromiseRejectedResult => r.status === 'rejected')
+    .map((r) => (r.reason instanceof Error ? r.reason.message : String(r.reason)));
+  if (errors.length > 0) throw new Error(errors.join('; '));
+  return results.map((r) => (r as PromiseFulfilledResult<HelmJobItem>).value);
 }
 
 export async function listHelmCharts({ name }: { name: string }): Promise<HelmChart[]> {
@@ -100,8 +123,8 @@ export async function upgradeHelmRelease({
   values?: any;
   reuseValues?: boolean;
   version?: string;
-}): Promise<string> {
-  return await invoke<string>('helm_upgrade_release', {
+}): Promise<HelmJobItem> {
+  const job = await invoke<HelmJobItem>('helm_upgrade_release', {
     name,
     namespace,
     releaseName,
@@ -110,6 +133,7 @@ export async function upgradeHelmRelease({
     reuseValues: !!reuseValues,
     version,
   });
+  return await waitForHelmJob(job);
 }
 
 export async function rollbackHelmRelease({
@@ -122,13 +146,42 @@ export async function rollbackHelmRelease({
   namespace?: string;
   releaseName: string;
   revision: number;
-}): Promise<string> {
-  return await invoke<string>('helm_rollback_release', {
+}): Promise<HelmJobItem> {
+  const job = await invoke<HelmJobItem>('helm_rollback_release', {
     name,
     namespace,
     releaseName,
     revision,
   });
+  return await waitForHelmJob(job);
+}
+
+export async function listHelmJobs(): Promise<HelmJobItem[]> {
+  return await invoke<HelmJobItem[]>('helm_list_jobs');
+}
+
+export async function cancelHelmJob({ jobId }: { jobId: string }): Promise<void> {
+  await invoke('cancel_helm_job', { jobId });
+}
+
+// Helm operations run as background jobs; this resolves once the job has finished and
+// throws when Helm failed or the job was cancelled. Output streams on `job.eventName`.
+export async function waitForHelmJob(job: HelmJobItem, pollMs = 1000): Promise<HelmJobItem> {
+  let current = job;
+  while (current.state === 'running') {
+    await new Promise((resolve) => setTimeout(resolve, pollMs));
+    const found = (await listHelmJobs()).find((j) => j.jobId === job.jobId);
+    // Pruned from the finished job list; nothing more to report
+    if (!found) return current;
+    current = found;
+  }
+  if (current.state === 'failed') {
+    throw new Error(current.error ?? `helm ${current.operation} failed`);
+  }
+  if (current.state === 'cancelled') {
+    throw new Error(`helm ${current.operation} of ${current.release} was cancelled`);
+  }
+  return current;
 }
 
 export interface HelmReleaseEvent {
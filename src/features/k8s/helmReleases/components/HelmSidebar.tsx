@@ -87,7 +87,7 @@ export function HelmSidebar({ item, setItem, onDelete, contextName }: HelmSideba
       setPerforming(true);
       try {
         await rollbackHelmRelease({ name: contextName, namespace, releaseName, revision });
-        toast.success(`Rollback to ${revision} triggered`);
+        toast.success(`Rolled back to revision ${revision}`);
       } catch (err) {
         toast.error(`Rollback failed: ${err}`);
       } finally {